@@ -1,3 +1,4 @@
+// Stale: regenerate with `build_api.sh`, see "Pending regeneration" in README.md.
 #[allow(clippy::all)]
 #[allow(dead_code, unused_imports, non_camel_case_types)]
 #[allow(clippy::all)]
@@ -55,6 +55,7 @@ mod api_dev;
 pub use api_dev::api;
 
 pub mod helpers;
+pub mod nomad;
 
 #[allow(dead_code)]
 #[derive(Debug, StructOpt)]
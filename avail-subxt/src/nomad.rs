@@ -0,0 +1,292 @@
+//! Parsing of the messages dispatched through the Nomad home, as emitted by its `Dispatch`
+//! events, into the typed DA bridge messages.
+//!
+//! The layouts are the packed, big-endian ones of the `nomad-core` and `nomad-da-bridge` crates,
+//! which are also parsed by the Solidity contracts. Both sides are tested against the messages
+//! of `pallets/bridges/nomad/da-bridge/fixtures`, so they cannot drift apart.
+
+use derive_more::Display;
+use subxt::utils::H256;
+
+/// Length of the Nomad message fields which precede its body.
+pub const NON_BODY_LENGTH: usize = 4 + 32 + 4 + 4 + 32;
+
+/// Errors decoding a Nomad message or its DA bridge body.
+#[derive(Clone, Debug, Display, PartialEq, Eq)]
+pub enum DecodeError {
+	#[display(
+		fmt = "Nomad message has {} bytes, less than its {} header bytes",
+		_0,
+		NON_BODY_LENGTH
+	)]
+	TruncatedMessage(usize),
+	#[display(fmt = "DA bridge message body is empty")]
+	Empty,
+	#[display(fmt = "Unknown DA bridge message type {}", _0)]
+	UnknownType(u8),
+	#[display(fmt = "DA bridge message has {} bytes, expected {}", actual, expected)]
+	InvalidLength { expected: usize, actual: usize },
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Reads packed big-endian fields, whose total length has been checked in advance.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+	fn take<const N: usize>(&mut self) -> [u8; N] {
+		let (bytes, rest) = self.0.split_at(N);
+		self.0 = rest;
+		bytes.try_into().expect("Split at N .qed")
+	}
+
+	fn u16(&mut self) -> u16 {
+		u16::from_be_bytes(self.take())
+	}
+
+	fn u32(&mut self) -> u32 {
+		u32::from_be_bytes(self.take())
+	}
+
+	fn h256(&mut self) -> H256 {
+		H256(self.take())
+	}
+}
+
+/// A Nomad message, as dispatched by the home.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NomadMessage {
+	pub origin: u32,
+	pub sender: H256,
+	pub nonce: u32,
+	pub destination: u32,
+	pub recipient: H256,
+	pub body: Vec<u8>,
+}
+
+impl NomadMessage {
+	/// Decodes a formatted Nomad message.
+	pub fn decode(message: &[u8]) -> Result<Self, DecodeError> {
+		if message.len() < NON_BODY_LENGTH {
+			return Err(DecodeError::TruncatedMessage(message.len()));
+		}
+
+		let (header, body) = message.split_at(NON_BODY_LENGTH);
+		let mut reader = Reader(header);
+		Ok(Self {
+			origin: reader.u32(),
+			sender: reader.h256(),
+			nonce: reader.u32(),
+			destination: reader.u32(),
+			recipient: reader.h256(),
+			body: body.to_vec(),
+		})
+	}
+
+	/// Decodes the body as a DA bridge message.
+	pub fn da_bridge_message(&self) -> Result<DABridgeMessage, DecodeError> {
+		DABridgeMessage::decode(&self.body)
+	}
+}
+
+/// DA bridge messages, see `nomad_da_bridge::message::DABridgeMessages`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DABridgeMessage {
+	/// Data root of a single block.
+	DataRoot { block_number: u32, data_root: H256 },
+	/// Merkle root of the data roots from `start_block` to `end_block`, both included.
+	DataRootRange {
+		start_block: u32,
+		end_block: u32,
+		range_root: H256,
+	},
+	/// Commitments summary and data root of a single block header.
+	HeaderCommitment {
+		block_number: u32,
+		rows: u16,
+		cols: u16,
+		commitments_hash: H256,
+		data_root: H256,
+	},
+}
+
+impl DABridgeMessage {
+	pub const DATA_ROOT_TYPE: u8 = 1;
+	pub const DATA_ROOT_RANGE_TYPE: u8 = 2;
+	pub const HEADER_COMMITMENT_TYPE: u8 = 3;
+
+	/// Decodes a DA bridge message body, checking its length against its type tag.
+	pub fn decode(body: &[u8]) -> Result<Self, DecodeError> {
+		let (&tag, fields) = body.split_first().ok_or(DecodeError::Empty)?;
+		let expected = match tag {
+			Self::DATA_ROOT_TYPE => 1 + 4 + 32,
+			Self::DATA_ROOT_RANGE_TYPE => 1 + 4 + 4 + 32,
+			Self::HEADER_COMMITMENT_TYPE => 1 + 4 + 2 + 2 + 32 + 32,
+			_ => return Err(DecodeError::UnknownType(tag)),
+		};
+		if body.len() != expected {
+			return Err(DecodeError::InvalidLength {
+				expected,
+				actual: body.len(),
+			});
+		}
+
+		let mut reader = Reader(fields);
+		let message = match tag {
+			Self::DATA_ROOT_TYPE => Self::DataRoot {
+				block_number: reader.u32(),
+				data_root: reader.h256(),
+			},
+			Self::DATA_ROOT_RANGE_TYPE => Self::DataRootRange {
+				start_block: reader.u32(),
+				end_block: reader.u32(),
+				range_root: reader.h256(),
+			},
+			_ => Self::HeaderCommitment {
+				block_number: reader.u32(),
+				rows: reader.u16(),
+				cols: reader.u16(),
+				commitments_hash: reader.h256(),
+				data_root: reader.h256(),
+			},
+		};
+
+		Ok(message)
+	}
+}
+
+#[cfg(feature = "api-dev")]
+pub use events::{dispatched_messages, parse_dispatch};
+
+#[cfg(feature = "api-dev")]
+mod events {
+	use subxt::events::Events;
+
+	use super::{DABridgeMessage, DecodeError, NomadMessage};
+	use crate::{api::nomad_home::events::Dispatch, AvailConfig};
+
+	/// Parses the message of a `NomadHome::Dispatch` event into its DA bridge message.
+	pub fn parse_dispatch(
+		event: &Dispatch,
+	) -> Result<(NomadMessage, DABridgeMessage), DecodeError> {
+		let message = NomadMessage::decode(&event.message)?;
+		let da_message = message.da_bridge_message()?;
+		Ok((message, da_message))
+	}
+
+	/// Parses the messages of all `NomadHome::Dispatch` events of a block.
+	pub fn dispatched_messages(
+		events: &Events<AvailConfig>,
+	) -> anyhow::Result<Vec<(NomadMessage, DABridgeMessage)>> {
+		events
+			.find::<Dispatch>()
+			.map(|event| Ok(parse_dispatch(&event?)?))
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use hex_literal::hex;
+	use test_case::test_case;
+
+	use super::*;
+
+	/// Messages formatted by the home, which the tests of `nomad-da-bridge` check as well.
+	const DISPATCHED_MESSAGES: &str =
+		include_str!("../../pallets/bridges/nomad/da-bridge/fixtures/dispatched_messages.hex");
+
+	fn data_root_msg() -> Vec<u8> {
+		[
+			// origin 2000, sender
+			hex!("000007d0").as_slice(),
+			&[0xaa; 32],
+			// nonce 1, destination 1000, recipient
+			&hex!("00000001000003e8"),
+			&[0xbb; 32],
+			// Data root of block 5
+			&hex!("0100000005"),
+			&[0x01; 32],
+		]
+		.concat()
+	}
+
+	#[test]
+	fn it_decodes_nomad_message() {
+		let message = NomadMessage::decode(&data_root_msg()).unwrap();
+
+		assert_eq!(message.origin, 2000);
+		assert_eq!(message.sender, H256([0xaa; 32]));
+		assert_eq!(message.nonce, 1);
+		assert_eq!(message.destination, 1000);
+		assert_eq!(message.recipient, H256([0xbb; 32]));
+		assert_eq!(
+			message.da_bridge_message(),
+			Ok(DABridgeMessage::DataRoot {
+				block_number: 5,
+				data_root: H256([0x01; 32]),
+			})
+		);
+	}
+
+	#[test]
+	fn it_decodes_the_dispatched_messages_fixture() {
+		let expected = vec![
+			DABridgeMessage::DataRoot {
+				block_number: 5,
+				data_root: H256([0x01; 32]),
+			},
+			DABridgeMessage::DataRootRange {
+				start_block: 5,
+				end_block: 8,
+				range_root: H256([0x02; 32]),
+			},
+			DABridgeMessage::HeaderCommitment {
+				block_number: 5,
+				rows: 256,
+				cols: 512,
+				commitments_hash: H256([0x11; 32]),
+				data_root: H256([0x22; 32]),
+			},
+		];
+		let messages = DISPATCHED_MESSAGES
+			.lines()
+			.map(|line| NomadMessage::decode(&hex::decode(line).unwrap()).unwrap())
+			.collect::<Vec<_>>();
+		assert_eq!(messages.len(), expected.len());
+
+		for ((nonce, message), expected) in (1..).zip(messages).zip(expected) {
+			assert_eq!(message.origin, 2000);
+			assert_eq!(message.sender, H256([0xaa; 32]));
+			assert_eq!(message.nonce, nonce);
+			assert_eq!(message.destination, 1000);
+			assert_eq!(message.recipient, H256([0xbb; 32]));
+			assert_eq!(message.da_bridge_message(), Ok(expected));
+		}
+	}
+
+	#[test]
+	fn it_rejects_truncated_nomad_message() {
+		let message = data_root_msg();
+		assert_eq!(
+			NomadMessage::decode(&message[..NON_BODY_LENGTH - 1]),
+			Err(DecodeError::TruncatedMessage(NON_BODY_LENGTH - 1))
+		);
+	}
+
+	#[test_case(
+		[hex!("020000000500000008").as_slice(), &[0x01; 32]].concat()
+		=> Ok(DABridgeMessage::DataRootRange { start_block: 5, end_block: 8, range_root: H256([0x01; 32]) });
+		"data root range")]
+	#[test_case(
+		[hex!("030000000501000200").as_slice(), &[0x11; 32], &[0x22; 32]].concat()
+		=> Ok(DABridgeMessage::HeaderCommitment { block_number: 5, rows: 256, cols: 512, commitments_hash: H256([0x11; 32]), data_root: H256([0x22; 32]) });
+		"header commitment")]
+	#[test_case(vec![] => Err(DecodeError::Empty); "empty")]
+	#[test_case(vec![0; 37] => Err(DecodeError::UnknownType(0)); "invalid type")]
+	#[test_case(vec![1; 36] => Err(DecodeError::InvalidLength { expected: 37, actual: 36 }); "short data root")]
+	#[test_case(vec![3; 74] => Err(DecodeError::InvalidLength { expected: 73, actual: 74 }); "long header commitment")]
+	fn decode_da_bridge_message(body: Vec<u8>) -> Result<DABridgeMessage, DecodeError> {
+		DABridgeMessage::decode(&body)
+	}
+}
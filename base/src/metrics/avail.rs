@@ -1,6 +1,8 @@
 use core::time::Duration;
 
-use substrate_prometheus_endpoint::{Histogram, PrometheusError, Registry};
+use substrate_prometheus_endpoint::{
+	register, Counter, Gauge, Histogram, PrometheusError, Registry, U64,
+};
 
 use crate::metrics::LOG_TARGET;
 
@@ -11,6 +13,9 @@ pub struct AvailMetrics {
 	pub import_block: ImportBlockMetrics,
 	pub header_extension: HeaderExtensionBuilderMetrics,
 	pub kate_rpc: KateRpcMetrics,
+	pub nomad_rpc: NomadRpcMetrics,
+	pub unsafe_da_sync: UnsafeDaSyncMetrics,
+	pub data_root: DataRootMetrics,
 }
 
 impl AvailMetrics {
@@ -19,6 +24,9 @@ impl AvailMetrics {
 		let import_block = ImportBlockMetrics::new(registry)?;
 		let header_extension = HeaderExtensionBuilderMetrics::new(registry)?;
 		let kate_rpc = KateRpcMetrics::new(registry)?;
+		let nomad_rpc = NomadRpcMetrics::new(registry)?;
+		let unsafe_da_sync = UnsafeDaSyncMetrics::new(registry)?;
+		let data_root = DataRootMetrics::new(registry)?;
 
 		log::info!(
 			target: LOG_TARGET,
@@ -29,6 +37,9 @@ impl AvailMetrics {
 			import_block,
 			header_extension,
 			kate_rpc,
+			nomad_rpc,
+			unsafe_da_sync,
+			data_root,
 		})
 	}
 }
@@ -148,6 +159,8 @@ pub struct KateRpcMetrics {
 	pub query_proof_execution_time: Histogram,
 	pub query_block_length_execution_time: Histogram,
 	pub query_data_proof_execution_time: Histogram,
+	pub query_app_data_proof_execution_time: Histogram,
+	pub query_namespace_proof_execution_time: Histogram,
 }
 
 impl KateRpcMetrics {
@@ -211,6 +224,18 @@ impl KateRpcMetrics {
 			"Kate RPC - Query Data Proof Time in microseconds",
 			buckets.to_vec(),
 		)?;
+		let query_app_data_proof_execution_time = custom_histogram(
+			registry,
+			"avail_kate_rpc_query_app_data_proof_execution_time",
+			"Kate RPC - Query App Data Proof Time in microseconds",
+			buckets.to_vec(),
+		)?;
+		let query_namespace_proof_execution_time = custom_histogram(
+			registry,
+			"avail_kate_rpc_query_namespace_proof_execution_time",
+			"Kate RPC - Query Namespace Proof Time in microseconds",
+			buckets.to_vec(),
+		)?;
 
 		Ok(Self {
 			query_rows_execution_time,
@@ -218,6 +243,8 @@ impl KateRpcMetrics {
 			query_proof_execution_time,
 			query_block_length_execution_time,
 			query_data_proof_execution_time,
+			query_app_data_proof_execution_time,
+			query_namespace_proof_execution_time,
 		})
 	}
 
@@ -265,6 +292,57 @@ impl KateRpcMetrics {
 				.observe(duration.as_micros() as f64);
 		}
 	}
+
+	pub fn observe_query_app_data_proof_execution_time(duration: Duration) {
+		if let Some(metrics) = AVAIL_METRICS.get() {
+			metrics
+				.kate_rpc
+				.query_app_data_proof_execution_time
+				.observe(duration.as_micros() as f64);
+		}
+	}
+
+	pub fn observe_query_namespace_proof_execution_time(duration: Duration) {
+		if let Some(metrics) = AVAIL_METRICS.get() {
+			metrics
+				.kate_rpc
+				.query_namespace_proof_execution_time
+				.observe(duration.as_micros() as f64);
+		}
+	}
+}
+
+pub struct NomadRpcMetrics {
+	pub query_bridged_data_proof_execution_time: Histogram,
+}
+
+impl NomadRpcMetrics {
+	pub fn new(registry: &Registry) -> Result<Self, PrometheusError> {
+		let buckets = [
+			1000.0, 2500.0, 5000.0, 10000.0, 25000.0, // 1ms, 2.5ms, 5ms, 10ms, 25ms
+			50000.0, 100000.0, 250000.0, 500000.0,  // 50ms, 100ms, 250ms, 500ms
+			1000000.0, // 1s
+		];
+		let query_bridged_data_proof_execution_time = custom_histogram(
+			registry,
+			"avail_nomad_rpc_query_bridged_data_proof_execution_time",
+			"Nomad RPC - Query Bridged Data Proof Time in microseconds",
+			buckets.to_vec(),
+		)?;
+
+		Ok(Self {
+			query_bridged_data_proof_execution_time,
+		})
+	}
+
+	pub fn observe_query_bridged_data_proof_execution_time(duration: Duration) {
+		if let Some(metrics) = AVAIL_METRICS.get() {
+			metrics
+				.nomad_rpc
+				.query_bridged_data_proof_execution_time
+				.observe(duration.as_micros() as f64);
+		}
+	}
 }
 
 pub struct ImportBlockMetrics {
@@ -299,3 +377,144 @@ impl ImportBlockMetrics {
 		}
 	}
 }
+
+/// Metrics of the re-verification of blocks imported with `--unsafe-da-sync`.
+pub struct UnsafeDaSyncMetrics {
+	pub verified_blocks: Counter<U64>,
+	pub failed_blocks: Counter<U64>,
+	pub unverifiable_blocks: Counter<U64>,
+	pub pending_blocks: Gauge<U64>,
+}
+
+impl UnsafeDaSyncMetrics {
+	pub fn new(registry: &Registry) -> Result<Self, PrometheusError> {
+		let verified_blocks = register(
+			Counter::new(
+				"avail_unsafe_da_sync_verified_blocks",
+				"Unsafe DA Sync - Blocks whose header extension was re-verified successfully",
+			)?,
+			registry,
+		)?;
+		let failed_blocks = register(
+			Counter::new(
+				"avail_unsafe_da_sync_failed_blocks",
+				"Unsafe DA Sync - Blocks whose header extension does NOT match the recomputed one",
+			)?,
+			registry,
+		)?;
+		let unverifiable_blocks = register(
+			Counter::new(
+				"avail_unsafe_da_sync_unverifiable_blocks",
+				"Unsafe DA Sync - Blocks skipped because the state of their parent is pruned",
+			)?,
+			registry,
+		)?;
+		let pending_blocks = register(
+			Gauge::new(
+				"avail_unsafe_da_sync_pending_blocks",
+				"Unsafe DA Sync - Blocks imported without DA checks still waiting for re-verification",
+			)?,
+			registry,
+		)?;
+
+		Ok(Self {
+			verified_blocks,
+			failed_blocks,
+			unverifiable_blocks,
+			pending_blocks,
+		})
+	}
+
+	pub fn inc_verified_blocks() {
+		if let Some(metrics) = AVAIL_METRICS.get() {
+			metrics.unsafe_da_sync.verified_blocks.inc();
+		}
+	}
+
+	pub fn inc_failed_blocks() {
+		if let Some(metrics) = AVAIL_METRICS.get() {
+			metrics.unsafe_da_sync.failed_blocks.inc();
+		}
+	}
+
+	pub fn inc_unverifiable_blocks() {
+		if let Some(metrics) = AVAIL_METRICS.get() {
+			metrics.unsafe_da_sync.unverifiable_blocks.inc();
+		}
+	}
+
+	pub fn set_pending_blocks(value: u64) {
+		if let Some(metrics) = AVAIL_METRICS.get() {
+			metrics.unsafe_da_sync.pending_blocks.set(value);
+		}
+	}
+}
+
+/// Metrics of the data roots of the imported blocks, as reported by the runtime.
+pub struct DataRootMetrics {
+	pub total_extrinsics: Counter<U64>,
+	pub data_submit_extrinsics: Counter<U64>,
+	pub data_submit_leaves: Counter<U64>,
+	pub failed_decode_extrinsics: Counter<U64>,
+}
+
+impl DataRootMetrics {
+	pub fn new(registry: &Registry) -> Result<Self, PrometheusError> {
+		let total_extrinsics = register(
+			Counter::new(
+				"avail_data_root_total_extrinsics",
+				"Data Root - Extrinsics analysed to build the data root",
+			)?,
+			registry,
+		)?;
+		let data_submit_extrinsics = register(
+			Counter::new(
+				"avail_data_root_data_submit_extrinsics",
+				"Data Root - Extrinsics containing one or more submitted data",
+			)?,
+			registry,
+		)?;
+		let data_submit_leaves = register(
+			Counter::new(
+				"avail_data_root_data_submit_leaves",
+				"Data Root - Submitted data included in the data root",
+			)?,
+			registry,
+		)?;
+		let failed_decode_extrinsics = register(
+			Counter::new(
+				"avail_data_root_failed_decode_extrinsics",
+				"Data Root - Extrinsics dropped from the data root because they cannot be decoded",
+			)?,
+			registry,
+		)?;
+
+		Ok(Self {
+			total_extrinsics,
+			data_submit_extrinsics,
+			data_submit_leaves,
+			failed_decode_extrinsics,
+		})
+	}
+
+	pub fn inc_by(
+		total_extrinsics: u32,
+		data_submit_extrinsics: u32,
+		data_submit_leaves: u32,
+		failed_decode_extrinsics: u32,
+	) {
+		if let Some(metrics) = AVAIL_METRICS.get() {
+			let data_root = &metrics.data_root;
+			data_root.total_extrinsics.inc_by(total_extrinsics.into());
+			data_root
+				.data_submit_extrinsics
+				.inc_by(data_submit_extrinsics.into());
+			data_root
+				.data_submit_leaves
+				.inc_by(data_submit_leaves.into());
+			data_root
+				.failed_decode_extrinsics
+				.inc_by(failed_decode_extrinsics.into());
+		}
+	}
+}
@@ -2,9 +2,9 @@ use super::{get_account_id_from_seed, AuthorityKeys};
 use avail_core::{BLOCK_CHUNK_SIZE, NORMAL_DISPATCH_RATIO};
 use da_runtime::{
 	constants, wasm_binary_unwrap, AccountId, BabeConfig, Balance, BalancesConfig,
-	DataAvailabilityConfig, NomadHomeConfig, NomadUpdaterManagerConfig, NominationPoolsConfig,
-	RuntimeGenesisConfig, SessionConfig, StakerStatus, StakingConfig, SudoConfig, SystemConfig,
-	TechnicalCommitteeConfig, AVL,
+	DataAvailabilityConfig, NomadHome, NomadHomeConfig, NomadUpdaterManagerConfig,
+	NominationPoolsConfig, RuntimeGenesisConfig, SessionConfig, StakerStatus, StakingConfig,
+	SudoConfig, SystemConfig, TechnicalCommitteeConfig, AVL, CENTS,
 };
 use frame_system::limits::BlockLength;
 use hex_literal::hex;
@@ -17,6 +17,8 @@ use sp_runtime::AccountId32;
 pub const PROTOCOL_ID: Option<&str> = Some("Avail");
 pub const TELEMETRY_URL: &str = "ws://telemetry.avail.tools:8001/submit";
 const NOMAD_LOCAL_DOMAIN: u32 = 2000;
+const NOMAD_DESTINATION_DOMAINS: [u32; 1] = [1000];
+const NOMAD_BYTE_FEE: Balance = CENTS / 100;
 const NOMAD_UPDATER: H160 = H160(hex!("695dFcFc604F9b2992642BDC5b173d1a1ed60b03"));
 const ENDOWMENT: Balance = 1_000_000 * AVL;
 const STASH_BOND: Balance = ENDOWMENT / 100;
@@ -76,7 +78,13 @@ pub fn runtime_genesis_config(
 	technical_committee: Vec<AccountId32>,
 	session_keys: Vec<AuthorityKeys>,
 ) -> RuntimeGenesisConfig {
-	let balances = dev_endowed_accounts();
+	let mut balances = dev_endowed_accounts();
+	// The Nomad relayer reward pot must exist before the dispatch fees below the existential
+	// deposit can be paid into it.
+	balances.push((
+		NomadHome::relayer_pot(),
+		constants::balances::ExistentialDeposit::get(),
+	));
 	let stakers = session_keys
 		.iter()
 		.map(|k| {
@@ -121,12 +129,15 @@ pub fn runtime_genesis_config(
 		nomad_home: NomadHomeConfig {
 			local_domain: NOMAD_LOCAL_DOMAIN,
 			updater: NOMAD_UPDATER,
+			destinations: NOMAD_DESTINATION_DOMAINS.to_vec(),
+			byte_fee: NOMAD_BYTE_FEE,
 			..Default::default()
 		},
 		nomad_updater_manager: NomadUpdaterManagerConfig {
 			updater: NOMAD_UPDATER,
 			..Default::default()
 		},
+		nomad_governance_router: Default::default(),
 		nomination_pools: NominationPoolsConfig {
 			min_create_bond: constants::nomination_pools::MIN_CREATE_BOND,
 			min_join_bond: constants::nomination_pools::MIN_JOIN_BOND,
@@ -6,24 +6,28 @@
 /// Root`.
 use std::sync::Arc;
 
-use avail_base::metrics::avail::ImportBlockMetrics;
+use avail_base::metrics::avail::{DataRootMetrics, ImportBlockMetrics};
 use avail_core::{BlockLengthColumns, BlockLengthRows, OpaqueExtrinsic, BLOCK_CHUNK_SIZE};
+use codec::Encode;
 use da_runtime::{
 	apis::{DataAvailApi, ExtensionBuilder},
 	Header as DaHeader,
 };
 use derive_more::Constructor;
 use frame_support::ensure;
-use frame_system::limits::BlockLength;
+use frame_system::{limits::BlockLength, ExtensionVersion};
+use sc_client_api::AuxStore;
 use sc_consensus::{
 	block_import::{BlockCheckParams, BlockImport as BlockImportT, BlockImportParams},
 	ImportResult,
 };
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_consensus::{BlockOrigin, Error as ConsensusError};
 use sp_runtime::traits::Block as BlockT;
 
+use crate::da_sync_verifier::{unverified_range, UNVERIFIED_RANGE_KEY};
+
 #[derive(Constructor)]
 pub struct BlockImport<C, I> {
 	pub client: Arc<C>,
@@ -48,7 +52,7 @@ where
 	B: BlockT<Extrinsic = OpaqueExtrinsic, Header = DaHeader>,
 	I: BlockImportT<B> + Clone + Send + Sync,
 	I::Error: Into<ConsensusError>,
-	C: ProvideRuntimeApi<B> + HeaderBackend<B> + Send + Sync,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + AuxStore + Send + Sync,
 	C::Api: DataAvailApi<B>,
 	C::Api: ExtensionBuilder<B>,
 {
@@ -58,7 +62,7 @@ where
 	/// It verifies that header extension (Kate commitment & data root) is properly calculated.
 	async fn import_block(
 		&mut self,
-		block: BlockImportParams<B, Self::Transaction>,
+		mut block: BlockImportParams<B, Self::Transaction>,
 	) -> Result<ImportResult, Self::Error> {
 		let import_block_start = std::time::Instant::now();
 
@@ -72,51 +76,34 @@ where
 		);
 		let skip_sync = self.unsafe_da_sync && is_sync;
 
+		if skip_sync {
+			// Keep track of the skipped blocks, so they can be re-verified once sync finishes.
+			let number = block.header.number;
+			let range = unverified_range(&*self.client)
+				.map_or((number, number), |(first, last)| {
+					(first.min(number), last.max(number))
+				});
+			block
+				.auxiliary
+				.push((UNVERIFIED_RANGE_KEY.to_vec(), Some(range.encode())));
+		}
+
 		let should_verify = !is_own && !skip_sync;
 		if should_verify {
 			let no_extrinsics = vec![];
 			let extrinsics = block.body.as_ref().unwrap_or(&no_extrinsics);
-			let best_hash = self.client.info().best_hash;
-
-			let data_root = self
-				.client
-				.runtime_api()
-				.build_data_root(best_hash, extrinsics.clone())
-				.map_err(|e| {
-					ConsensusError::ClientImport(format!("Data root cannot be calculated: {e:?}"))
-				})?;
-
-			let extension = &block.header.extension;
-			let block_len = BlockLength::with_normal_ratio(
-				BlockLengthRows(extension.rows() as u32),
-				BlockLengthColumns(extension.cols() as u32),
-				BLOCK_CHUNK_SIZE,
-				sp_runtime::Perbill::from_percent(90),
-			)
-			.expect("Valid BlockLength at genesis .qed");
-
-			let generated_ext = self
-				.client
-				.runtime_api()
-				.build_extension(
-					best_hash,
-					extrinsics.clone(),
-					data_root,
-					block_len,
-					block.header.number,
-				)
-				.map_err(|e| {
-					ConsensusError::ClientImport(format!("Build extension fails due to: {e:?}"))
-				})?;
-
-			ensure!(
-				extension == &generated_ext,
-				ConsensusError::ClientImport(
-					format!("DA Extension does NOT match\nExpected: {extension:#?}\nGenerated:{generated_ext:#?}"))
-			);
+			// The extension is built by the runtime of the parent, which may not be the best block.
+			let parent_hash = block.header.parent_hash;
+
+			verify_extension_version(&*self.client, parent_hash, &block.header)?;
+			verify_header_extension(&*self.client, parent_hash, extrinsics, &block.header)?;
 		}
 
+		let hash = block.post_hash();
 		let import_block_res = self.inner.import_block(block).await.map_err(Into::into);
+		if let Ok(ImportResult::Imported(_)) = import_block_res {
+			report_data_root_metrics(&*self.client, hash);
+		}
 
 		// Metrics
 		ImportBlockMetrics::observe_total_execution_time(import_block_start.elapsed());
@@ -131,3 +118,143 @@ where
 		self.inner.check_block(block).await.map_err(Into::into)
 	}
 }
+
+/// Reports the data root metrics of the imported block `at` to Prometheus.
+///
+/// Blocks built by runtimes without `DataAvailApi::data_root_metrics`, or whose state is not
+/// available, are skipped.
+fn report_data_root_metrics<B, C>(client: &C, at: B::Hash)
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B>,
+	C::Api: DataAvailApi<B>,
+{
+	let api = client.runtime_api();
+	let api_version = api
+		.api_version::<dyn DataAvailApi<B>>(at)
+		.ok()
+		.flatten()
+		.unwrap_or(1);
+	if api_version < 8 {
+		return;
+	}
+
+	match api.data_root_metrics(at) {
+		Ok(metrics) => DataRootMetrics::inc_by(
+			metrics.total_extrinsics,
+			metrics.data_submit_extrinsics,
+			metrics.data_submit_leaves,
+			metrics.failed_decode_extrinsics,
+		),
+		Err(e) => log::debug!("Data root metrics of {at:?} cannot be fetched: {e:?}"),
+	}
+}
+
+/// Checks that the header extension version of `header` is the one enabled on-chain for its
+/// block number, using the runtime at `at`.
+pub fn verify_extension_version<B, C>(
+	client: &C,
+	at: B::Hash,
+	header: &DaHeader,
+) -> Result<(), ConsensusError>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B>,
+	C::Api: DataAvailApi<B>,
+{
+	let api = client.runtime_api();
+	let api_version = api
+		.api_version::<dyn DataAvailApi<B>>(at)
+		.map_err(|e| {
+			ConsensusError::ClientImport(format!("DataAvailApi version cannot be fetched: {e:?}"))
+		})?
+		.unwrap_or(1);
+
+	// Runtimes without on-chain header extension versions only support `V1`.
+	let expected = if api_version >= 2 {
+		api.header_extension_version(at, header.number)
+			.map_err(|e| {
+				ConsensusError::ClientImport(format!(
+					"Header extension version cannot be fetched: {e:?}"
+				))
+			})?
+	} else {
+		ExtensionVersion::V1
+	};
+
+	let version = ExtensionVersion::from(&header.extension);
+	ensure!(
+		version == expected,
+		ConsensusError::ClientImport(format!(
+			"DA Extension version does NOT match\nExpected: {expected:?}\nFound: {version:?}"
+		))
+	);
+
+	Ok(())
+}
+
+/// Recomputes the data root and the header extension of `extrinsics` using the runtime at `at`,
+/// and checks that they match the extension of the given `header`.
+///
+/// The extension is rebuilt using the version recorded in `header`.
+pub fn verify_header_extension<B, C>(
+	client: &C,
+	at: B::Hash,
+	extrinsics: &[OpaqueExtrinsic],
+	header: &DaHeader,
+) -> Result<(), ConsensusError>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B>,
+	C::Api: ExtensionBuilder<B>,
+{
+	let data_root = client
+		.runtime_api()
+		.build_data_root(at, extrinsics.to_vec())
+		.map_err(|e| {
+			ConsensusError::ClientImport(format!("Data root cannot be calculated: {e:?}"))
+		})?;
+
+	let extension = &header.extension;
+	let block_len = BlockLength::with_normal_ratio(
+		BlockLengthRows(extension.rows() as u32),
+		BlockLengthColumns(extension.cols() as u32),
+		BLOCK_CHUNK_SIZE,
+		sp_runtime::Perbill::from_percent(90),
+	)
+	.expect("Valid BlockLength at genesis .qed");
+
+	let api = client.runtime_api();
+	let api_version = api
+		.api_version::<dyn ExtensionBuilder<B>>(at)
+		.map_err(|e| {
+			ConsensusError::ClientImport(format!(
+				"ExtensionBuilder version cannot be fetched: {e:?}"
+			))
+		})?
+		.unwrap_or(1);
+
+	let generated_ext = if api_version >= 2 {
+		let version = ExtensionVersion::from(extension);
+		api.build_versioned_extension(
+			at,
+			extrinsics.to_vec(),
+			data_root,
+			block_len,
+			header.number,
+			version,
+		)
+	} else {
+		api.build_extension(at, extrinsics.to_vec(), data_root, block_len, header.number)
+	}
+	.map_err(|e| ConsensusError::ClientImport(format!("Build extension fails due to: {e:?}")))?;
+
+	ensure!(
+		extension == &generated_ext,
+		ConsensusError::ClientImport(format!(
+			"DA Extension does NOT match\nExpected: {extension:#?}\nGenerated:{generated_ext:#?}"
+		))
+	);
+
+	Ok(())
+}
@@ -0,0 +1,342 @@
+/// # Unsafe DA Sync re-verification
+///
+/// Blocks imported from `NetworkInitialSync` or `File` while `--unsafe-da-sync` is enabled skip
+/// the header extension check on `BlockImport`. Their numbers are recorded in aux storage, and
+/// this worker re-verifies them once the major sync is over, persisting its progress so it can
+/// resume after a restart.
+///
+/// Each block is verified using the runtime at its parent, so the extension version and the
+/// builder are the ones it was produced with, even across runtime upgrades. Hence the state of
+/// the parents must still be available: blocks whose parent state was pruned are skipped and
+/// reported as unverifiable, so the node should run with `--state-pruning archive`.
+///
+/// Both aux entries are cleared once the whole range has been walked.
+use std::sync::Arc;
+
+use avail_base::metrics::avail::UnsafeDaSyncMetrics;
+use avail_core::OpaqueExtrinsic;
+use codec::{Decode, Encode};
+use da_runtime::{
+	apis::{DataAvailApi, ExtensionBuilder},
+	Header as DaHeader,
+};
+use derive_more::Display;
+use futures::StreamExt;
+use sc_client_api::{AuxStore, Backend, BlockBackend, BlockchainEvents};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_consensus::{Error as ConsensusError, SyncOracle};
+use sp_runtime::traits::Block as BlockT;
+
+use crate::da_block_import::{verify_extension_version, verify_header_extension};
+
+const LOG_TARGET: &str = "avail::node::da_sync_verifier";
+
+/// Aux storage key of the range of block numbers imported without DA checks.
+pub const UNVERIFIED_RANGE_KEY: &[u8] = b"avail_unsafe_da_sync_unverified_range";
+/// Aux storage key of the last block number processed by the re-verification worker.
+pub const VERIFIED_CURSOR_KEY: &[u8] = b"avail_unsafe_da_sync_verified_cursor";
+
+/// Inclusive range `(first, last)` of block numbers.
+pub type BlockRange = (u32, u32);
+
+/// Reasons why a block does not pass the re-verification.
+#[derive(Debug, Display)]
+pub enum VerifyError {
+	/// The canonical hash, header or body of the block is not stored.
+	#[display(fmt = "Block #{} is not available", _0)]
+	Unavailable(u32),
+	/// The state of the parent of the block is pruned, so the block cannot be re-verified.
+	#[display(
+		fmt = "Block #{} cannot be re-verified, the state of its parent is pruned",
+		_0
+	)]
+	Unverifiable(u32),
+	/// The recomputed header extension, or its version, does not match the imported one.
+	#[display(fmt = "Block #{} does NOT match: {}", _0, _1)]
+	Invalid(u32, ConsensusError),
+}
+
+/// Returns the range of block numbers imported without DA checks, if any.
+pub fn unverified_range<C: AuxStore>(client: &C) -> Option<BlockRange> {
+	load_aux(client, UNVERIFIED_RANGE_KEY)
+}
+
+/// Returns the last block number processed by the re-verification worker, if any.
+pub fn verified_cursor<C: AuxStore>(client: &C) -> Option<u32> {
+	load_aux(client, VERIFIED_CURSOR_KEY)
+}
+
+fn load_aux<C: AuxStore, T: Decode>(client: &C, key: &[u8]) -> Option<T> {
+	let raw = client
+		.get_aux(key)
+		.map_err(|e| log::warn!(target: LOG_TARGET, "Aux storage cannot be read: {e:?}"))
+		.ok()
+		.flatten()?;
+	T::decode(&mut raw.as_slice())
+		.map_err(|e| log::warn!(target: LOG_TARGET, "Aux storage value cannot be decoded: {e:?}"))
+		.ok()
+}
+
+/// Number of blocks of `from..=last` still waiting for re-verification.
+fn pending_blocks(from: u32, last: u32) -> u64 {
+	(u64::from(last) + 1).saturating_sub(u64::from(from))
+}
+
+/// Re-verifies the header extension of every finalized block imported without DA checks.
+///
+/// It runs after each finality notification while the node is not major syncing. Any mismatch,
+/// or any block which is not available anymore, is logged as an error and reported through
+/// `UnsafeDaSyncMetrics`. Blocks whose parent state is pruned are reported apart.
+pub async fn run<B, C, BE, S>(client: Arc<C>, backend: Arc<BE>, sync_oracle: S)
+where
+	B: BlockT<Extrinsic = OpaqueExtrinsic, Header = DaHeader>,
+	C: ProvideRuntimeApi<B>
+		+ HeaderBackend<B>
+		+ BlockBackend<B>
+		+ BlockchainEvents<B>
+		+ AuxStore
+		+ Send
+		+ Sync,
+	C::Api: DataAvailApi<B> + ExtensionBuilder<B>,
+	BE: Backend<B>,
+	S: SyncOracle + Send + Sync,
+{
+	let mut finality_stream = client.finality_notification_stream();
+
+	while let Some(notification) = finality_stream.next().await {
+		if sync_oracle.is_major_syncing() {
+			continue;
+		}
+		let Some((first, last)) = unverified_range(&*client) else {
+			continue;
+		};
+
+		let from = verified_cursor(&*client)
+			.map_or(first, |cursor| cursor.saturating_add(1))
+			.max(first);
+		let to = last.min(notification.header.number);
+		UnsafeDaSyncMetrics::set_pending_blocks(pending_blocks(from, last));
+		if from > to {
+			continue;
+		}
+
+		log::info!(
+			target: LOG_TARGET,
+			"Re-verifying blocks #{from}..=#{to} imported with unsafe DA sync"
+		);
+		verify_range(&*client, from, to, last, |number| {
+			verify_block(&*client, &*backend, number)
+		});
+	}
+}
+
+/// Re-verifies the blocks `from..=to` in order with `verify`, persisting the cursor after each
+/// one.
+///
+/// Failed blocks are reported and then skipped, so a single bad block does not stall the worker.
+/// It stops early if the cursor cannot be persisted, and returns the last processed block.
+///
+/// Once `last` is processed, and no block was added to the range meanwhile, both the range and
+/// the cursor are cleared.
+fn verify_range<C, F>(client: &C, from: u32, to: u32, last: u32, mut verify: F) -> Option<u32>
+where
+	C: AuxStore,
+	F: FnMut(u32) -> Result<(), VerifyError>,
+{
+	let mut processed = None;
+	for number in from..=to {
+		match verify(number) {
+			Ok(()) => UnsafeDaSyncMetrics::inc_verified_blocks(),
+			Err(e @ VerifyError::Unverifiable(_)) => {
+				log::warn!(target: LOG_TARGET, "Block imported with unsafe DA sync skipped: {e}");
+				UnsafeDaSyncMetrics::inc_unverifiable_blocks();
+			},
+			Err(e) => {
+				log::error!(
+					target: LOG_TARGET,
+					"Block imported with unsafe DA sync FAILED re-verification: {e}"
+				);
+				UnsafeDaSyncMetrics::inc_failed_blocks();
+			},
+		}
+
+		let cursor = number.encode();
+		let finished = number == last && unverified_range(client).map(|(_, l)| l) == Some(last);
+		let res = if finished {
+			client.insert_aux(&[], &[UNVERIFIED_RANGE_KEY, VERIFIED_CURSOR_KEY])
+		} else {
+			client.insert_aux(&[(VERIFIED_CURSOR_KEY, cursor.as_slice())], &[])
+		};
+		if let Err(e) = res {
+			log::warn!(
+				target: LOG_TARGET,
+				"Progress of block #{number} cannot be persisted: {e:?}"
+			);
+			break;
+		}
+		processed = Some(number);
+		UnsafeDaSyncMetrics::set_pending_blocks(pending_blocks(number.saturating_add(1), last));
+	}
+
+	processed
+}
+
+/// Recomputes the header extension of the canonical block `number` using the runtime of its
+/// parent, and checks it and its version against the imported ones.
+fn verify_block<B, C, BE>(client: &C, backend: &BE, number: u32) -> Result<(), VerifyError>
+where
+	B: BlockT<Extrinsic = OpaqueExtrinsic, Header = DaHeader>,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + BlockBackend<B>,
+	C::Api: DataAvailApi<B> + ExtensionBuilder<B>,
+	BE: Backend<B>,
+{
+	let hash = client
+		.hash(number)
+		.ok()
+		.flatten()
+		.ok_or(VerifyError::Unavailable(number))?;
+	let header = client
+		.header(hash)
+		.ok()
+		.flatten()
+		.ok_or(VerifyError::Unavailable(number))?;
+	let extrinsics = client
+		.block_body(hash)
+		.ok()
+		.flatten()
+		.ok_or(VerifyError::Unavailable(number))?;
+
+	let parent = header.parent_hash;
+	if !backend.have_state_at(parent, number.saturating_sub(1)) {
+		return Err(VerifyError::Unverifiable(number));
+	}
+
+	verify_extension_version(client, parent, &header)
+		.and_then(|_| verify_header_extension(client, parent, &extrinsics, &header))
+		.map_err(|e| VerifyError::Invalid(number, e))
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{collections::HashMap, sync::Mutex};
+
+	use super::*;
+
+	/// In-memory aux storage.
+	#[derive(Default)]
+	struct MemAux(Mutex<HashMap<Vec<u8>, Vec<u8>>>);
+
+	impl AuxStore for MemAux {
+		fn insert_aux<
+			'a,
+			'b: 'a,
+			'c: 'a,
+			I: IntoIterator<Item = &'a (&'c [u8], &'c [u8])>,
+			D: IntoIterator<Item = &'a &'b [u8]>,
+		>(
+			&self,
+			insert: I,
+			delete: D,
+		) -> sp_blockchain::Result<()> {
+			let mut map = self.0.lock().unwrap();
+			for (key, value) in insert {
+				map.insert(key.to_vec(), value.to_vec());
+			}
+			for key in delete {
+				map.remove(*key);
+			}
+			Ok(())
+		}
+
+		fn get_aux(&self, key: &[u8]) -> sp_blockchain::Result<Option<Vec<u8>>> {
+			Ok(self.0.lock().unwrap().get(key).cloned())
+		}
+	}
+
+	#[test]
+	fn pending_blocks_are_counted_inclusively() {
+		assert_eq!(pending_blocks(1, 10), 10);
+		assert_eq!(pending_blocks(10, 10), 1);
+		assert_eq!(pending_blocks(11, 10), 0);
+		assert_eq!(pending_blocks(20, 10), 0);
+		assert_eq!(pending_blocks(0, u32::MAX), u64::from(u32::MAX) + 1);
+	}
+
+	#[test]
+	fn verify_range_persists_cursor() {
+		let aux = MemAux::default();
+		let mut verified = vec![];
+
+		let processed = verify_range(&aux, 5, 8, 10, |number| {
+			verified.push(number);
+			Ok(())
+		});
+
+		assert_eq!(processed, Some(8));
+		assert_eq!(verified, vec![5, 6, 7, 8]);
+		assert_eq!(verified_cursor(&aux), Some(8));
+	}
+
+	#[test]
+	fn verify_range_reports_and_skips_failed_blocks() {
+		let aux = MemAux::default();
+		let mut failed = vec![];
+
+		let processed = verify_range(&aux, 1, 4, 4, |number| {
+			let res = match number {
+				2 => Err(VerifyError::Unavailable(number)),
+				3 => Err(VerifyError::Invalid(
+					number,
+					ConsensusError::ClientImport("DA Extension does NOT match".into()),
+				)),
+				4 => Err(VerifyError::Unverifiable(number)),
+				_ => Ok(()),
+			};
+			if res.is_err() {
+				failed.push(number);
+			}
+			res
+		});
+
+		assert_eq!(failed, vec![2, 3, 4]);
+		assert_eq!(processed, Some(4));
+		assert_eq!(verified_cursor(&aux), Some(4));
+	}
+
+	#[test]
+	fn verify_range_resumes_from_cursor() {
+		let aux = MemAux::default();
+		aux.insert_aux(
+			&[(UNVERIFIED_RANGE_KEY, (1u32, 6u32).encode().as_slice())],
+			&[],
+		)
+		.unwrap();
+		verify_range(&aux, 1, 3, 6, |_| Ok(()));
+
+		let (first, last) = unverified_range(&aux).unwrap();
+		let from = verified_cursor(&aux).map_or(first, |cursor| cursor + 1);
+		assert_eq!(from, 4);
+		assert_eq!(pending_blocks(from, last), 3);
+	}
+
+	#[test]
+	fn verify_range_clears_the_finished_range() {
+		let aux = MemAux::default();
+		aux.insert_aux(
+			&[(UNVERIFIED_RANGE_KEY, (1u32, 5u32).encode().as_slice())],
+			&[],
+		)
+		.unwrap();
+
+		// Blocks added to the range while it is walked are kept.
+		verify_range(&aux, 1, 3, 3, |_| Ok(()));
+		assert_eq!(unverified_range(&aux), Some((1, 5)));
+		assert_eq!(verified_cursor(&aux), Some(3));
+
+		let processed = verify_range(&aux, 4, 5, 5, |_| Ok(()));
+		assert_eq!(processed, Some(5));
+		assert_eq!(unverified_range(&aux), None);
+		assert_eq!(verified_cursor(&aux), None);
+	}
+}
@@ -2,6 +2,7 @@ pub mod chains;
 
 pub mod da_block_import;
 pub use da_block_import::BlockImport;
+pub mod da_sync_verifier;
 
 pub mod cli;
 pub mod rpc;
@@ -8,6 +8,7 @@ mod service;
 mod cli;
 mod command;
 mod da_block_import;
+mod da_sync_verifier;
 mod rpc;
 
 fn main() -> sc_cli::Result<()> {
@@ -30,6 +30,8 @@
 
 //! # Data Availability Changes
 //! - Add Kate RPC extension.
+//! - Add Nomad home RPC extension.
+//! - Add Nomad DA bridge RPC extension.
 //! - Remove `sc_rpc::dev` extension.
 
 #![warn(missing_docs)]
@@ -37,7 +39,8 @@
 use std::sync::Arc;
 
 use da_runtime::{
-	apis::DataAvailApi, AccountId, Balance, BlockNumber, Hash, Index, NodeBlock as Block,
+	apis::{DABridgeApi, DataAvailApi, NomadHomeApi},
+	AccountId, Balance, BlockNumber, Hash, Index, NodeBlock as Block,
 };
 use jsonrpsee::RpcModule;
 use sc_client_api::AuxStore;
@@ -115,6 +118,8 @@ where
 	C::Api: BabeApi<Block>,
 	C::Api: BlockBuilder<Block>,
 	C::Api: DataAvailApi<Block>,
+	C::Api: DABridgeApi<Block>,
+	C::Api: NomadHomeApi<Block>,
 	P: TransactionPool + 'static,
 	SC: SelectChain<Block> + 'static,
 	B: sc_client_api::Backend<Block> + Send + Sync + 'static,
@@ -124,6 +129,9 @@ where
 	use kate_rpc::KateApiMetricsServer;
 	use kate_rpc::{Kate, KateApiServer};
 	use mmr_rpc::{Mmr, MmrApiServer};
+	#[cfg(feature = "kate-rpc-metrics")]
+	use nomad_rpc::NomadDABridgeApiMetricsServer;
+	use nomad_rpc::{NomadDABridge, NomadDABridgeApiServer, NomadHome, NomadHomeApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use sc_consensus_babe_rpc::{Babe, BabeApiServer};
 	use sc_consensus_grandpa_rpc::{Grandpa, GrandpaApiServer};
@@ -210,6 +218,17 @@ where
 	)?;
 
 	io.merge(StateMigration::new(client.clone(), backend, deny_unsafe).into_rpc())?;
+	io.merge(NomadHome::<C, Block>::new(client.clone()).into_rpc())?;
+
+	#[cfg(feature = "kate-rpc-metrics")]
+	{
+		let nomad_da_bridge = NomadDABridge::<C, Block>::new(client.clone());
+		io.merge(NomadDABridgeApiMetricsServer::into_rpc(nomad_da_bridge))?;
+	}
+
+	io.merge(NomadDABridgeApiServer::into_rpc(
+		NomadDABridge::<C, Block>::new(client.clone()),
+	))?;
 
 	#[cfg(feature = "kate-rpc-metrics")]
 	io.merge(KateApiMetricsServer::into_rpc(Kate::<C, Block>::new(
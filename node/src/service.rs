@@ -357,6 +357,19 @@ pub fn new_full_base(
 		None
 	};
 
+	// Blocks imported with unsafe DA sync are re-verified at the state of their parents.
+	if unsafe_da_sync
+		&& !config
+			.state_pruning
+			.as_ref()
+			.map_or(false, |pruning| pruning.is_archive())
+	{
+		log::warn!(
+			"Unsafe DA sync without `--state-pruning archive`: blocks whose parent state is \
+			 pruned before the sync finishes cannot be re-verified"
+		);
+	}
+
 	let sc_service::PartialComponents {
 		client,
 		backend,
@@ -440,6 +453,13 @@ pub fn new_full_base(
 		}
 	}
 
+	// Re-verify any block imported without DA checks once the major sync is over.
+	task_manager.spawn_handle().spawn_blocking(
+		"da-sync-verifier",
+		None,
+		crate::da_sync_verifier::run(client.clone(), backend.clone(), sync_service.clone()),
+	);
+
 	let (block_import, grandpa_link, babe_link) = import_setup;
 
 	(with_startup_data)(&block_import, &babe_link);
@@ -1,11 +1,12 @@
 use avail_core::traits::{ExtendedBlock, ExtendedHeader};
 use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::{traits::Get, BoundedVec};
 use frame_system::{pallet_prelude::BlockNumberFor, BlockHash, RawOrigin};
 use hex_literal::hex;
-use nomad_home::Nonces;
+use nomad_home::{EnrolledDestinations, Nonces};
 use sp_core::H256;
 use sp_runtime::traits::Header as _;
-use sp_std::boxed::Box;
+use sp_std::{boxed::Box, vec::Vec};
 
 use crate::*;
 
@@ -46,6 +47,7 @@ benchmarks! {
 		let origin = RawOrigin::Signed(whitelisted_caller::<T::AccountId>());
 		let destination_domain = 1000;
 		let recipient_address = H256::zero();
+		EnrolledDestinations::<T>::insert(destination_domain, true);
 
 		let pre_nonce = Nonces::<T>::get(destination_domain);
 
@@ -54,4 +56,68 @@ benchmarks! {
 		let post_nonce = Nonces::<T>::get(destination_domain);
 		assert_eq!(pre_nonce +1, post_nonce);
 	}
+
+	try_dispatch_data_root_range {
+		let n in 1 .. T::MaxDataRootRange::get();
+
+		// Create and register the headers of blocks `1..=n`
+		let headers = (1..=n).map(|number| {
+			let block_number: BlockNumberFor<T> = number.into();
+			let header = <<<T as frame_system::Config>::Block as ExtendedBlock<_>>::DaHeader as ExtendedHeader<_,_,_,_>>::new(
+				block_number,
+				H256::repeat_byte(3u8).into(),
+				H256::repeat_byte(2u8).into(),
+				H256::repeat_byte(1u8).into(),
+				Default::default(),
+				Default::default());
+			BlockHash::<T>::insert(block_number, header.hash());
+			header
+		}).collect::<Vec<_>>();
+		let headers: BoundedVec<_, T::MaxDataRootRange> = headers.try_into().unwrap();
+
+		let origin = RawOrigin::Signed(whitelisted_caller::<T::AccountId>());
+		let destination_domain = 1000;
+		let recipient_address = H256::zero();
+		EnrolledDestinations::<T>::insert(destination_domain, true);
+
+		let pre_nonce = Nonces::<T>::get(destination_domain);
+
+	}: _(origin, destination_domain, recipient_address, headers)
+	verify {
+		let post_nonce = Nonces::<T>::get(destination_domain);
+		assert_eq!(pre_nonce +1, post_nonce);
+	}
+
+	try_dispatch_header_commitment {
+		let block_number: BlockNumberFor<T> = 10u32.into();
+		let header = Box::new(<<<T as frame_system::Config>::Block as ExtendedBlock<_>>::DaHeader as ExtendedHeader<_,_,_,_>>::new(
+			block_number,
+			H256::repeat_byte(3u8).into(),
+			H256::repeat_byte(2u8).into(),
+			H256::repeat_byte(1u8).into(),
+			Default::default(),
+			Default::default()));
+		BlockHash::<T>::insert(block_number, header.hash());
+
+		let origin = RawOrigin::Signed(whitelisted_caller::<T::AccountId>());
+		let destination_domain = 1000;
+		let recipient_address = H256::zero();
+		EnrolledDestinations::<T>::insert(destination_domain, true);
+
+	}: _(origin, destination_domain, recipient_address, header)
+	verify {
+		assert!(HeaderCommitmentDispatches::<T>::contains_key(10, destination_domain));
+	}
+
+	set_auto_dispatch_destinations {
+		let destinations = (0..T::MaxAutoDispatchDestinations::get())
+			.map(|domain| (domain, H256::repeat_byte(1u8)))
+			.collect::<Vec<_>>();
+		let destinations: BoundedVec<_, T::MaxAutoDispatchDestinations> = destinations.try_into().unwrap();
+		let expected = destinations.clone();
+
+	}: _(RawOrigin::Root, destinations)
+	verify {
+		assert_eq!(AutoDispatchDestinations::<T>::get(), expected);
+	}
 }
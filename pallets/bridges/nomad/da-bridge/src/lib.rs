@@ -5,9 +5,13 @@
 /// <https://docs.substrate.io/v3/runtime/frame>
 pub use pallet::*;
 
-mod message;
+pub mod message;
+pub mod mmr;
+pub mod placeholder_weights;
+pub mod range;
 pub mod weights;
 
+pub use mmr::BlockHashMmr;
 pub use weights::WeightInfo;
 
 #[cfg(test)]
@@ -21,17 +25,26 @@ mod benchmarking;
 
 #[frame_support::pallet]
 pub mod pallet {
-	use avail_core::traits::ExtendedHeader;
+	use avail_core::{header::HeaderExtension, traits::ExtendedHeader};
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
-	use nomad_core::TypedMessage;
-	use nomad_home::Pallet as Home;
+	use nomad_core::{NomadMessage, TypedMessage};
+	use nomad_home::{MessageProof, Pallet as Home};
 	use sp_core::{bounded::BoundedVec, Get, H256};
-	use sp_runtime::{traits::Header as _, SaturatedConversion};
-	use sp_std::boxed::Box;
+	use sp_runtime::{
+		traits::{Hash, Header as _, Keccak256, Zero},
+		SaturatedConversion,
+	};
+	use sp_std::{boxed::Box, vec::Vec};
 
-	use super::weights::WeightInfo;
-	use crate::message::{DABridgeMessages, DataRootMessage};
+	use super::{placeholder_weights, weights::WeightInfo};
+	use crate::{
+		message::{
+			DABridgeMessages, DataRootMessage, DataRootRangeMessage, HeaderCommitmentMessage,
+		},
+		mmr::BlockHashMmr,
+		range::range_root,
+	};
 
 	/// Default implementations of [`DefaultConfig`], which can be used to implement [`Config`].
 	pub mod config_preludes {
@@ -45,6 +58,12 @@ pub mod pallet {
 		#[frame_support::register_default_impl(TestDefaultConfig)]
 		impl DefaultConfig for TestDefaultConfig {
 			type DABridgePalletId = ();
+			type MaxDataRootRange = frame_support::traits::ConstU32<16>;
+			type AutoDispatchPeriod = frame_support::traits::ConstU32<0>;
+			type MaxAutoDispatchDestinations = frame_support::traits::ConstU32<4>;
+			type MmrRootHistory = frame_support::traits::ConstU32<0>;
+			type MaxDataRootDispatchRecords = frame_support::traits::ConstU32<4>;
+			type MaxDispatchedRanges = frame_support::traits::ConstU32<4>;
 			type WeightInfo = ();
 		}
 	}
@@ -56,6 +75,41 @@ pub mod pallet {
 		#[pallet::constant]
 		type DABridgePalletId: Get<H256>;
 
+		/// Max number of blocks whose data roots are dispatched in a single range message.
+		#[pallet::constant]
+		type MaxDataRootRange: Get<u32>;
+
+		/// Number of blocks between automatic dispatches of the data roots of the last blocks
+		/// to the `AutoDispatchDestinations`. Zero disables automatic dispatches.
+		///
+		/// Dispatches also happen earlier if `MaxDataRootRange` data roots are pending.
+		#[pallet::constant]
+		type AutoDispatchPeriod: Get<u32>;
+
+		/// Max number of destinations of automatic dispatches.
+		#[pallet::constant]
+		type MaxAutoDispatchDestinations: Get<u32>;
+
+		/// MMR of the block hashes, which proves the headers of blocks older than
+		/// `BlockHashCount`.
+		#[pallet::no_default]
+		type BlockHashMmr: BlockHashMmr<BlockNumberFor<Self>, Self::Hash>;
+
+		/// Number of past MMR roots which header proofs can be verified against, so proofs
+		/// remain valid for a while after new leaves are added to the MMR.
+		#[pallet::constant]
+		type MmrRootHistory: Get<u32>;
+
+		/// Number of the last data root dispatches to each destination whose messages are
+		/// recorded, so their proofs can be served.
+		#[pallet::constant]
+		type MaxDataRootDispatchRecords: Get<u32>;
+
+		/// Max number of disjoint ranges of dispatched blocks remembered for each destination
+		/// and recipient. The data roots of the blocks of older ranges can be dispatched again.
+		#[pallet::constant]
+		type MaxDispatchedRanges: Get<u32>;
+
 		/// Weights for this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -63,6 +117,117 @@ pub mod pallet {
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
+	/// Home message which dispatched the data root of a block, or of a range of blocks.
+	#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+	pub struct DataRootDispatch {
+		pub start_block: u32,
+		/// Same as `start_block` for a data root message.
+		pub end_block: u32,
+		/// Whether the message is a data root range message.
+		pub is_range: bool,
+		/// Leaf index of the message in the home tree.
+		pub leaf_index: u32,
+		/// Nonce of the message for its destination domain.
+		pub nonce: u32,
+		pub recipient_address: H256,
+		/// Data root of the block, or range root of the blocks.
+		pub root: H256,
+	}
+
+	impl DataRootDispatch {
+		/// Body of the dispatched message.
+		pub fn message(&self) -> DABridgeMessages {
+			if self.is_range {
+				DataRootRangeMessage {
+					start_block: self.start_block,
+					end_block: self.end_block,
+					range_root: self.root,
+				}
+				.into()
+			} else {
+				DataRootMessage {
+					block_number: self.start_block,
+					data_root: self.root,
+				}
+				.into()
+			}
+		}
+	}
+
+	/// Nomad message of a dispatched data root and its proof against the committed home root.
+	#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebug)]
+	pub struct DataRootMessageProof {
+		/// Formatted Nomad message, as emitted by `nomad_home::Event::Dispatch`.
+		pub message: Vec<u8>,
+		pub proof: MessageProof,
+		/// Dispatch of the message. The data root of a block dispatched by a range message is
+		/// proven against its range root with the data roots of the other blocks of the range.
+		pub dispatch: DataRootDispatch,
+	}
+
+	// Destination domain and sequence number to the last `MaxDataRootDispatchRecords` data root
+	// dispatches to it, in the order of their messages.
+	#[pallet::storage]
+	pub type DataRootDispatches<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, u32, Twox64Concat, u32, DataRootDispatch>;
+
+	// Destination domain to the number of data root dispatches to it
+	#[pallet::storage]
+	#[pallet::getter(fn data_root_dispatch_count)]
+	pub type DataRootDispatchCount<T: Config> = StorageMap<_, Twox64Concat, u32, u32, ValueQuery>;
+
+	/// Destination domains and recipient addresses of automatic data root range dispatches.
+	#[pallet::storage]
+	#[pallet::getter(fn auto_dispatch_destinations)]
+	pub type AutoDispatchDestinations<T: Config> =
+		StorageValue<_, BoundedVec<(u32, H256), T::MaxAutoDispatchDestinations>, ValueQuery>;
+
+	/// First block of the `PendingDataRoots`.
+	#[pallet::storage]
+	pub type PendingRangeStart<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Data roots of contiguous blocks waiting for the next automatic dispatch.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_data_roots)]
+	pub type PendingDataRoots<T: Config> =
+		StorageValue<_, BoundedVec<H256, T::MaxDataRootRange>, ValueQuery>;
+
+	// Block number and destination domain to the leaf index and hash of the message which
+	// dispatched its header commitment. They are tracked apart from the data root dispatches, so
+	// they do not move the `LastDispatchedBlock`. A dispatch is void once its message is pruned
+	// from the home tree, i.e. the leaf does not hold its hash anymore.
+	#[pallet::storage]
+	#[pallet::getter(fn header_commitment_dispatch)]
+	pub type HeaderCommitmentDispatches<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, u32, Twox64Concat, u32, (u32, H256)>;
+
+	// Destination domain and recipient address to the sorted and disjoint ranges of blocks whose
+	// data roots were dispatched to them, by any kind of data root dispatch. A data root is
+	// dispatched at most once to each recipient, so the home tree does not grow with duplicated
+	// messages. Adjacent ranges are merged, and the oldest range is forgotten once there are
+	// more than `MaxDispatchedRanges`.
+	#[pallet::storage]
+	pub type DispatchedRanges<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		u32,
+		Blake2_128Concat,
+		H256,
+		BoundedVec<(u32, u32), T::MaxDispatchedRanges>,
+		ValueQuery,
+	>;
+
+	// Destination domain to the last block whose data root was dispatched to it, for the
+	// relayers. Earlier blocks which were not dispatched can still be dispatched.
+	#[pallet::storage]
+	#[pallet::getter(fn last_dispatched_block)]
+	pub type LastDispatchedBlock<T: Config> = StorageMap<_, Twox64Concat, u32, u32>;
+
+	// Number of leaves of the MMR of block hashes to its root
+	#[pallet::storage]
+	#[pallet::getter(fn mmr_root)]
+	pub type MmrRoots<T: Config> = StorageMap<_, Twox64Concat, u64, T::Hash>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -72,6 +237,31 @@ pub mod pallet {
 			block_number: BlockNumberFor<T>,
 			data_root: H256,
 		},
+		DataRootRangeDispatched {
+			destination_domain: u32,
+			recipient_address: H256,
+			start_block: BlockNumberFor<T>,
+			end_block: BlockNumberFor<T>,
+			range_root: H256,
+		},
+		HeaderCommitmentDispatched {
+			destination_domain: u32,
+			recipient_address: H256,
+			block_number: BlockNumberFor<T>,
+			rows: u16,
+			cols: u16,
+			commitments_hash: H256,
+			data_root: H256,
+		},
+		AutoDispatchDestinationsSet {
+			destinations: Vec<(u32, H256)>,
+		},
+		AutoDispatchFailed {
+			destination_domain: u32,
+			start_block: BlockNumberFor<T>,
+			end_block: BlockNumberFor<T>,
+			error: DispatchError,
+		},
 	}
 
 	#[pallet::error]
@@ -79,6 +269,25 @@ pub mod pallet {
 		InitializationError,
 		HashOfBlockNotMatchBlockNumber,
 		DABridgeMessageExceedsMaxMessageSize,
+		EmptyDataRootRange,
+		NonContiguousDataRootRange,
+		DataRootAlreadyDispatched,
+		UnknownMmrRoot,
+		InvalidHeaderProof,
+		HeaderCommitmentAlreadyDispatched,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T>
+	where
+		[u8; 32]: From<T::AccountId>,
+		H256: From<T::Hash>,
+	{
+		/// Queues the data root of the parent block and dispatches the pending data roots to the
+		/// `AutoDispatchDestinations` every `AutoDispatchPeriod` blocks.
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			Self::note_mmr_root().saturating_add(Self::auto_dispatch(now.saturated_into()))
+		}
 	}
 
 	#[pallet::call]
@@ -89,7 +298,11 @@ pub mod pallet {
 	{
 		/// Dispatch a data root message to the home if the header is valid.
 		#[pallet::call_index(0)]
-		#[pallet::weight(<T as Config>::WeightInfo::try_dispatch_data_root())]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::try_dispatch_data_root().saturating_add(
+				placeholder_weights::try_dispatch_data_root_extra(T::DbWeight::get())
+			)
+		)]
 		pub fn try_dispatch_data_root(
 			origin: OriginFor<T>,
 			#[pallet::compact] destination_domain: u32,
@@ -100,6 +313,79 @@ pub mod pallet {
 			Self::ensure_valid_header(&header)?;
 			Self::do_dispatch_data_root(destination_domain, recipient_address, &header)
 		}
+
+		/// Dispatch a message with the Merkle root of the data roots of a contiguous range of
+		/// blocks to the home if all the headers are valid.
+		#[pallet::call_index(1)]
+		#[pallet::weight(placeholder_weights::try_dispatch_data_root_range(
+			T::DbWeight::get(),
+			headers.len() as u32,
+			headers.encoded_size() as u32,
+		))]
+		pub fn try_dispatch_data_root_range(
+			origin: OriginFor<T>,
+			#[pallet::compact] destination_domain: u32,
+			recipient_address: H256,
+			headers: BoundedVec<DaHeaderFor<T>, T::MaxDataRootRange>,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			Self::ensure_valid_range(&headers)?;
+			Self::do_dispatch_data_root_range(destination_domain, recipient_address, &headers)
+		}
+
+		/// Set the destination domains and recipient addresses which the data roots are
+		/// automatically dispatched to.
+		#[pallet::call_index(2)]
+		#[pallet::weight(placeholder_weights::set_auto_dispatch_destinations(T::DbWeight::get()))]
+		pub fn set_auto_dispatch_destinations(
+			origin: OriginFor<T>,
+			destinations: BoundedVec<(u32, H256), T::MaxAutoDispatchDestinations>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			AutoDispatchDestinations::<T>::put(&destinations);
+
+			Self::deposit_event(Event::<T>::AutoDispatchDestinationsSet {
+				destinations: destinations.into_inner(),
+			});
+
+			Ok(().into())
+		}
+
+		/// Dispatch a data root message to the home for a header which is no longer in the
+		/// block hash mapping, if `proof` proves its hash against a recent MMR root.
+		#[pallet::call_index(3)]
+		#[pallet::weight(placeholder_weights::try_dispatch_data_root_with_proof(
+			T::DbWeight::get(),
+			header.encoded_size().saturating_add(proof.encoded_size()) as u32,
+		))]
+		pub fn try_dispatch_data_root_with_proof(
+			origin: OriginFor<T>,
+			#[pallet::compact] destination_domain: u32,
+			recipient_address: H256,
+			header: Box<DaHeaderFor<T>>,
+			proof: Box<<T::BlockHashMmr as BlockHashMmr<BlockNumberFor<T>, T::Hash>>::Proof>,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			Self::ensure_proven_header(&header, *proof)?;
+			Self::do_dispatch_data_root(destination_domain, recipient_address, &header)
+		}
+
+		/// Dispatch a header commitment message to the home if the header is valid.
+		#[pallet::call_index(4)]
+		#[pallet::weight(placeholder_weights::try_dispatch_header_commitment(
+			T::DbWeight::get(),
+			header.encoded_size() as u32,
+		))]
+		pub fn try_dispatch_header_commitment(
+			origin: OriginFor<T>,
+			#[pallet::compact] destination_domain: u32,
+			recipient_address: H256,
+			header: Box<DaHeaderFor<T>>,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			Self::ensure_valid_header(&header)?;
+			Self::do_dispatch_header_commitment(destination_domain, recipient_address, &header)
+		}
 	}
 
 	impl<T: Config> Pallet<T>
@@ -115,6 +401,12 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			// Safety: Even if a BlockNumber type is larger than u32, it won't pose any issues for the next 2000+ years
 			let block_number: u32 = (*header.number()).saturated_into();
+			Self::ensure_not_dispatched(
+				destination_domain,
+				recipient_address,
+				block_number,
+				block_number,
+			)?;
 			let data_root = header.extension().data_root();
 
 			let message: DABridgeMessages = DataRootMessage {
@@ -128,12 +420,25 @@ pub mod pallet {
 				.try_into()
 				.map_err(|_| Error::<T>::DABridgeMessageExceedsMaxMessageSize)?;
 
-			Home::<T>::do_dispatch(
+			let nonce = Home::<T>::nonces(destination_domain);
+			let leaf_index = Home::<T>::do_dispatch(
 				T::DABridgePalletId::get(),
 				destination_domain,
 				recipient_address,
 				body,
 			)?;
+			Self::record_data_root_dispatch(
+				destination_domain,
+				DataRootDispatch {
+					start_block: block_number,
+					end_block: block_number,
+					is_range: false,
+					leaf_index,
+					nonce,
+					recipient_address,
+					root: data_root,
+				},
+			);
 
 			Self::deposit_event(Event::<T>::DataRootDispatched {
 				destination_domain,
@@ -145,6 +450,297 @@ pub mod pallet {
 			Ok(().into())
 		}
 
+		/// Dispatch a header commitment message for a valid header.
+		fn do_dispatch_header_commitment(
+			destination_domain: u32,
+			recipient_address: H256,
+			header: &DaHeaderFor<T>,
+		) -> DispatchResultWithPostInfo {
+			let block_number: u32 = (*header.number()).saturated_into();
+			let dispatched = Self::header_commitment_dispatch(block_number, destination_domain)
+				.map_or(false, |(leaf_index, message_hash)| {
+					Home::<T>::leaf(leaf_index) == Some(message_hash)
+				});
+			ensure!(!dispatched, Error::<T>::HeaderCommitmentAlreadyDispatched);
+			let message = header_commitment_message(block_number, header.extension());
+			let event = Event::<T>::HeaderCommitmentDispatched {
+				destination_domain,
+				recipient_address,
+				block_number: block_number.into(),
+				rows: message.rows,
+				cols: message.cols,
+				commitments_hash: message.commitments_hash,
+				data_root: message.data_root,
+			};
+
+			let body: BoundedVec<u8, T::MaxMessageBodyBytes> = DABridgeMessages::from(message)
+				.encode()
+				.try_into()
+				.map_err(|_| Error::<T>::DABridgeMessageExceedsMaxMessageSize)?;
+
+			let leaf_index = Home::<T>::do_dispatch(
+				T::DABridgePalletId::get(),
+				destination_domain,
+				recipient_address,
+				body,
+			)?;
+			if let Some(message_hash) = Home::<T>::leaf(leaf_index) {
+				HeaderCommitmentDispatches::<T>::insert(
+					block_number,
+					destination_domain,
+					(leaf_index, message_hash),
+				);
+			}
+
+			Self::deposit_event(event);
+
+			Ok(().into())
+		}
+
+		/// Dispatch a data root range message for valid and contiguous headers.
+		fn do_dispatch_data_root_range(
+			destination_domain: u32,
+			recipient_address: H256,
+			headers: &[DaHeaderFor<T>],
+		) -> DispatchResultWithPostInfo {
+			let first = headers.first().ok_or(Error::<T>::EmptyDataRootRange)?;
+			let start_block: u32 = (*first.number()).saturated_into();
+
+			let data_roots = headers
+				.iter()
+				.map(|header| header.extension().data_root())
+				.collect::<Vec<_>>();
+			Self::dispatch_range_root(
+				destination_domain,
+				recipient_address,
+				start_block,
+				&data_roots,
+			)?;
+
+			Ok(().into())
+		}
+
+		/// Dispatch a data root range message for the data roots of contiguous blocks from
+		/// `start_block`.
+		fn dispatch_range_root(
+			destination_domain: u32,
+			recipient_address: H256,
+			start_block: u32,
+			data_roots: &[H256],
+		) -> DispatchResult {
+			ensure!(!data_roots.is_empty(), Error::<T>::EmptyDataRootRange);
+			let end_block = start_block.saturating_add(data_roots.len() as u32 - 1);
+			Self::ensure_not_dispatched(
+				destination_domain,
+				recipient_address,
+				start_block,
+				end_block,
+			)?;
+			let range_root = range_root(data_roots);
+
+			let message: DABridgeMessages = DataRootRangeMessage {
+				start_block,
+				end_block,
+				range_root,
+			}
+			.into();
+
+			let body: BoundedVec<u8, T::MaxMessageBodyBytes> = message
+				.encode()
+				.try_into()
+				.map_err(|_| Error::<T>::DABridgeMessageExceedsMaxMessageSize)?;
+
+			let nonce = Home::<T>::nonces(destination_domain);
+			let leaf_index = Home::<T>::do_dispatch(
+				T::DABridgePalletId::get(),
+				destination_domain,
+				recipient_address,
+				body,
+			)?;
+			Self::record_data_root_dispatch(
+				destination_domain,
+				DataRootDispatch {
+					start_block,
+					end_block,
+					is_range: true,
+					leaf_index,
+					nonce,
+					recipient_address,
+					root: range_root,
+				},
+			);
+
+			Self::deposit_event(Event::<T>::DataRootRangeDispatched {
+				destination_domain,
+				recipient_address,
+				start_block: start_block.into(),
+				end_block: end_block.into(),
+				range_root,
+			});
+
+			Ok(())
+		}
+
+		/// Ensure none of the data roots from `start_block` to `end_block` has been dispatched
+		/// to `recipient_address` of `destination_domain` yet.
+		fn ensure_not_dispatched(
+			destination_domain: u32,
+			recipient_address: H256,
+			start_block: u32,
+			end_block: u32,
+		) -> DispatchResult {
+			let ranges = DispatchedRanges::<T>::get(destination_domain, recipient_address);
+			ensure!(
+				!overlaps(&ranges, start_block, end_block),
+				Error::<T>::DataRootAlreadyDispatched
+			);
+			Ok(())
+		}
+
+		/// Record `dispatch` as the last data root dispatch to `destination_domain`, and forget
+		/// the ones older than `MaxDataRootDispatchRecords`.
+		fn record_data_root_dispatch(destination_domain: u32, dispatch: DataRootDispatch) {
+			let sequence = Self::data_root_dispatch_count(destination_domain);
+			DispatchedRanges::<T>::mutate(
+				destination_domain,
+				dispatch.recipient_address,
+				|ranges| {
+					let mut updated = ranges.to_vec();
+					insert_range(&mut updated, dispatch.start_block, dispatch.end_block);
+					*ranges = BoundedVec::truncate_from(oldest_dropped::<T>(updated));
+				},
+			);
+			LastDispatchedBlock::<T>::mutate(destination_domain, |last| {
+				*last = Some(last.map_or(dispatch.end_block, |last| last.max(dispatch.end_block)))
+			});
+			DataRootDispatches::<T>::insert(destination_domain, sequence, dispatch);
+			DataRootDispatchCount::<T>::insert(destination_domain, sequence.saturating_add(1));
+			if let Some(expired) = sequence.checked_sub(T::MaxDataRootDispatchRecords::get()) {
+				DataRootDispatches::<T>::remove(destination_domain, expired);
+			}
+		}
+
+		/// Queue the data root of the block before `now`, and dispatch the queued data roots
+		/// to each of the `AutoDispatchDestinations` once the period ends or the queue is full.
+		///
+		/// The data roots stay queued until they are dispatched to every destination, so a
+		/// failed dispatch is retried with the next one.
+		fn auto_dispatch(now: u32) -> Weight {
+			let period = T::AutoDispatchPeriod::get();
+			let destinations = AutoDispatchDestinations::<T>::get();
+			let mut weight = T::DbWeight::get().reads(1);
+			if period.is_zero() || destinations.is_empty() {
+				return weight;
+			}
+
+			// The data root of the parent block is only known once it has been finalized.
+			let parent = now.saturating_sub(1);
+			if parent.is_zero() {
+				return weight;
+			}
+			weight.saturating_accrue(T::DbWeight::get().reads(3));
+			let Some(data_root) = frame_system::Pallet::<T>::data_root() else {
+				return weight;
+			};
+
+			let mut start_block = PendingRangeStart::<T>::get();
+			let mut data_roots = PendingDataRoots::<T>::get().into_inner();
+			let contiguous = start_block.saturating_add(data_roots.len() as u32) == parent;
+			if data_roots.is_empty() || !contiguous {
+				start_block = parent;
+				data_roots.clear();
+			}
+			// The queue is still full if a destination keeps failing, which then misses the
+			// oldest data root.
+			let max_range = T::MaxDataRootRange::get() as usize;
+			let slid = data_roots.len() >= max_range;
+			if slid {
+				let excess = (data_roots.len() + 1 - max_range).min(data_roots.len());
+				data_roots.drain(..excess);
+				start_block = start_block.saturating_add(excess as u32);
+			}
+			data_roots.push(data_root);
+
+			let became_full = !slid && data_roots.len() >= max_range;
+			if became_full || parent % period == 0 {
+				let dispatched = Self::dispatch_pending_data_roots(
+					destinations,
+					start_block,
+					&data_roots,
+					&mut weight,
+				);
+				data_roots.drain(..dispatched);
+				start_block = start_block.saturating_add(dispatched as u32);
+			}
+
+			weight.saturating_accrue(T::DbWeight::get().writes(2));
+			PendingRangeStart::<T>::put(start_block);
+			PendingDataRoots::<T>::put(BoundedVec::truncate_from(data_roots));
+			weight
+		}
+
+		/// Dispatch the pending data roots of the blocks from `start_block` to each of the
+		/// `destinations`, skipping the blocks already dispatched to it. The blocks between
+		/// two dispatched blocks are dispatched by a range of their own.
+		///
+		/// Returns the number of data roots dispatched to every destination, which can be
+		/// dequeued. The other ones are dispatched again with the next automatic dispatch.
+		fn dispatch_pending_data_roots(
+			destinations: BoundedVec<(u32, H256), T::MaxAutoDispatchDestinations>,
+			start_block: u32,
+			data_roots: &[H256],
+			weight: &mut Weight,
+		) -> usize {
+			let end_block = start_block.saturating_add(data_roots.len() as u32 - 1);
+			let mut dispatched = data_roots.len();
+			for (destination_domain, recipient_address) in destinations {
+				weight.saturating_accrue(T::DbWeight::get().reads(1));
+				let ranges = DispatchedRanges::<T>::get(destination_domain, recipient_address);
+				for (range_start, range_end) in gaps(&ranges, start_block, end_block) {
+					let first = (range_start - start_block) as usize;
+					let pending = &data_roots[first..=(range_end - start_block) as usize];
+					weight.saturating_accrue(placeholder_weights::try_dispatch_data_root_range(
+						T::DbWeight::get(),
+						pending.len() as u32,
+						0,
+					));
+					if let Err(error) = Self::dispatch_range_root(
+						destination_domain,
+						recipient_address,
+						range_start,
+						pending,
+					) {
+						dispatched = dispatched.min(first);
+						Self::deposit_event(Event::<T>::AutoDispatchFailed {
+							destination_domain,
+							start_block: range_start.into(),
+							end_block: range_end.into(),
+							error,
+						});
+						break;
+					}
+				}
+			}
+
+			dispatched
+		}
+
+		/// Ensure the given headers are valid and belong to consecutive blocks.
+		fn ensure_valid_range(headers: &[DaHeaderFor<T>]) -> DispatchResultWithPostInfo {
+			ensure!(!headers.is_empty(), Error::<T>::EmptyDataRootRange);
+
+			let contiguous = headers
+				.windows(2)
+				.all(|pair| *pair[1].number() == pair[0].number().saturating_add(1u32.into()));
+			ensure!(contiguous, Error::<T>::NonContiguousDataRootRange);
+
+			for header in headers {
+				Self::ensure_valid_header(header)?;
+			}
+
+			Ok(().into())
+		}
+
 		/// Ensure a given header's hash has been recorded in the block hash
 		/// mapping.
 		fn ensure_valid_header(header: &DaHeaderFor<T>) -> DispatchResultWithPostInfo {
@@ -163,5 +759,261 @@ pub mod pallet {
 
 			Ok(().into())
 		}
+
+		/// Ensure a given header's hash is proven by `proof` against one of the recorded MMR
+		/// roots.
+		fn ensure_proven_header(
+			header: &DaHeaderFor<T>,
+			proof: <T::BlockHashMmr as BlockHashMmr<BlockNumberFor<T>, T::Hash>>::Proof,
+		) -> DispatchResultWithPostInfo {
+			let leaf_count = T::BlockHashMmr::proof_leaf_count(&proof);
+			let root = Self::mmr_root(leaf_count).ok_or(Error::<T>::UnknownMmrRoot)?;
+
+			let proven =
+				T::BlockHashMmr::verify_proof(root, *header.number(), header.hash(), proof);
+			ensure!(proven, Error::<T>::InvalidHeaderProof);
+
+			Ok(().into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Record the current root of the MMR of block hashes, and forget the roots older than
+		/// `MmrRootHistory`.
+		fn note_mmr_root() -> Weight {
+			let history = u64::from(T::MmrRootHistory::get());
+			if history.is_zero() {
+				return Weight::zero();
+			}
+
+			let (leaf_count, root) = T::BlockHashMmr::leaf_count_and_root();
+			MmrRoots::<T>::insert(leaf_count, root);
+			if let Some(expired) = leaf_count.checked_sub(history) {
+				MmrRoots::<T>::remove(expired);
+			}
+
+			T::DbWeight::get().reads_writes(2, 2)
+		}
+
+		/// Returns the last recorded dispatch of the data root of `block_number` to
+		/// `destination_domain`.
+		///
+		/// Blocks can be dispatched in any order, so the records are searched from the last
+		/// one, unless the block is not in the dispatched ranges of any recipient.
+		pub fn data_root_dispatch(
+			block_number: u32,
+			destination_domain: u32,
+		) -> Option<DataRootDispatch> {
+			let dispatched = DispatchedRanges::<T>::iter_prefix_values(destination_domain)
+				.any(|ranges| overlaps(&ranges, block_number, block_number));
+			if !dispatched {
+				return None;
+			}
+
+			// Pruned messages rewind the count, so the oldest records of the window may already
+			// be expired.
+			let count = Self::data_root_dispatch_count(destination_domain);
+			let oldest = count.saturating_sub(T::MaxDataRootDispatchRecords::get());
+			(oldest..count)
+				.rev()
+				.filter_map(|sequence| DataRootDispatches::<T>::get(destination_domain, sequence))
+				.find(|dispatch| {
+					dispatch.start_block <= block_number && block_number <= dispatch.end_block
+				})
+		}
+
+		/// Returns the message which dispatched the data root of `block_number` to
+		/// `destination_domain`, and its proof against the committed home root.
+		///
+		/// Combined with a `DataProof` of the block, it lets the destination verify that some
+		/// data was submitted in a bridged data root. The message can be a data root range
+		/// message, see `DataRootMessageProof::dispatch`.
+		pub fn data_root_message_proof(
+			block_number: u32,
+			destination_domain: u32,
+		) -> Option<DataRootMessageProof> {
+			let dispatch = Self::data_root_dispatch(block_number, destination_domain)?;
+
+			let body: BoundedVec<u8, T::MaxMessageBodyBytes> =
+				dispatch.message().encode().try_into().ok()?;
+			let message = NomadMessage {
+				origin: Home::<T>::base().local_domain,
+				sender: T::DABridgePalletId::get(),
+				nonce: dispatch.nonce,
+				destination: destination_domain,
+				recipient: dispatch.recipient_address,
+				body,
+			};
+
+			let proof = Home::<T>::message_proof(dispatch.leaf_index)?;
+			if proof.leaf != message.hash() {
+				return None;
+			}
+
+			Some(DataRootMessageProof {
+				message: message.to_vec(),
+				proof,
+				dispatch,
+			})
+		}
+	}
+
+	impl<T: Config> nomad_home::OnPrunedMessages for Pallet<T> {
+		/// Forget the data root dispatches of the pruned messages, remove their blocks from the
+		/// dispatched ranges so they can be dispatched again, and rewind the
+		/// `LastDispatchedBlock` of their destinations.
+		fn on_pruned_messages(first_leaf_index: u32) -> Weight {
+			let (mut reads, mut writes) = (0, 0);
+			for (destination_domain, count) in DataRootDispatchCount::<T>::iter() {
+				reads += 1;
+				let oldest = count.saturating_sub(T::MaxDataRootDispatchRecords::get());
+
+				// Dispatches are recorded in leaf order, so the pruned ones are the last ones.
+				let mut remaining = count;
+				while let Some(sequence) = remaining.checked_sub(1).filter(|s| *s >= oldest) {
+					reads += 1;
+					let Some(dispatch) = DataRootDispatches::<T>::get(destination_domain, sequence)
+						.filter(|dispatch| dispatch.leaf_index >= first_leaf_index)
+					else {
+						break;
+					};
+					DataRootDispatches::<T>::remove(destination_domain, sequence);
+					DispatchedRanges::<T>::mutate(
+						destination_domain,
+						dispatch.recipient_address,
+						|ranges| {
+							let mut updated = ranges.to_vec();
+							remove_range(&mut updated, dispatch.start_block, dispatch.end_block);
+							*ranges = BoundedVec::truncate_from(oldest_dropped::<T>(updated));
+						},
+					);
+					reads += 1;
+					writes += 2;
+					remaining = sequence;
+				}
+				if remaining == count {
+					continue;
+				}
+
+				let last_dispatched = DispatchedRanges::<T>::iter_prefix_values(destination_domain)
+					.filter_map(|ranges| {
+						reads += 1;
+						ranges.last().map(|(_, end_block)| *end_block)
+					})
+					.max();
+				DataRootDispatchCount::<T>::insert(destination_domain, remaining);
+				LastDispatchedBlock::<T>::set(destination_domain, last_dispatched);
+				writes += 2;
+			}
+
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+	}
+
+	/// Whether any of the sorted and disjoint `ranges` overlaps the blocks from `start_block` to
+	/// `end_block`.
+	fn overlaps(ranges: &[(u32, u32)], start_block: u32, end_block: u32) -> bool {
+		ranges
+			.iter()
+			.any(|(start, end)| *start <= end_block && start_block <= *end)
+	}
+
+	/// Insert the blocks from `start_block` to `end_block`, which do not overlap `ranges`, into
+	/// the sorted and disjoint `ranges`, merging the adjacent ranges.
+	fn insert_range(ranges: &mut Vec<(u32, u32)>, start_block: u32, end_block: u32) {
+		let index = ranges.partition_point(|(start, _)| *start < start_block);
+		let merges_next = ranges
+			.get(index)
+			.map_or(false, |(start, _)| end_block.checked_add(1) == Some(*start));
+		let merges_previous = index
+			.checked_sub(1)
+			.and_then(|previous| ranges.get(previous))
+			.map_or(false, |(_, end)| end.checked_add(1) == Some(start_block));
+
+		match (merges_previous, merges_next) {
+			(true, true) => {
+				ranges[index - 1].1 = ranges[index].1;
+				ranges.remove(index);
+			},
+			(true, false) => ranges[index - 1].1 = end_block,
+			(false, true) => ranges[index].0 = start_block,
+			(false, false) => ranges.insert(index, (start_block, end_block)),
+		}
+	}
+
+	/// Remove the blocks from `start_block` to `end_block` from the sorted and disjoint
+	/// `ranges`, splitting the ranges which contain them.
+	fn remove_range(ranges: &mut Vec<(u32, u32)>, start_block: u32, end_block: u32) {
+		*ranges = ranges
+			.iter()
+			.flat_map(|(start, end)| {
+				let before = (*start < start_block).then(|| (*start, (*end).min(start_block - 1)));
+				let after = (*end > end_block).then(|| ((*start).max(end_block + 1), *end));
+				[before, after]
+			})
+			.flatten()
+			.collect();
+	}
+
+	/// Drop the oldest `ranges` beyond `MaxDispatchedRanges`.
+	fn oldest_dropped<T: Config>(mut ranges: Vec<(u32, u32)>) -> Vec<(u32, u32)> {
+		let excess = ranges
+			.len()
+			.saturating_sub(T::MaxDispatchedRanges::get() as usize);
+		ranges.drain(..excess);
+		ranges
+	}
+
+	/// Ranges of the blocks from `start_block` to `end_block` which are not in the sorted and
+	/// disjoint `ranges`.
+	fn gaps(ranges: &[(u32, u32)], start_block: u32, end_block: u32) -> Vec<(u32, u32)> {
+		let mut gaps = Vec::new();
+		let mut next = start_block;
+		for (start, end) in ranges {
+			if *end < next {
+				continue;
+			}
+			if *start > end_block {
+				break;
+			}
+			if *start > next {
+				gaps.push((next, start - 1));
+			}
+			match end.checked_add(1) {
+				Some(after) => next = after,
+				None => return gaps,
+			}
+		}
+		if next <= end_block {
+			gaps.push((next, end_block));
+		}
+		gaps
+	}
+
+	/// Header commitment message of the block `block_number` with the header `extension`.
+	pub(crate) fn header_commitment_message(
+		block_number: u32,
+		extension: &HeaderExtension,
+	) -> HeaderCommitmentMessage {
+		let (rows, cols, commitment) = match extension {
+			HeaderExtension::V1(ext) => (
+				ext.commitment.rows,
+				ext.commitment.cols,
+				&ext.commitment.commitment,
+			),
+			HeaderExtension::V2(ext) => (
+				ext.commitment.rows,
+				ext.commitment.cols,
+				&ext.commitment.commitment,
+			),
+		};
+
+		HeaderCommitmentMessage {
+			block_number,
+			rows,
+			cols,
+			commitments_hash: Keccak256::hash(commitment),
+			data_root: extension.data_root(),
+		}
 	}
 }
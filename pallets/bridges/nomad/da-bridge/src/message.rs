@@ -11,6 +11,8 @@ use sp_std::vec::Vec;
 pub enum DABridgeMessageTypes {
 	Invalid = 0,
 	DataRootMessage = 1,
+	DataRootRangeMessage = 2,
+	HeaderCommitmentMessage = 3,
 }
 
 #[derive(PartialEq)]
@@ -20,6 +22,54 @@ pub enum TryFromError {
 	Unknown,
 }
 
+/// Errors decoding a DABridge message body.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DecodeError {
+	/// The body is empty, so it has no type tag.
+	Empty,
+	/// The type tag is unknown or `Invalid`.
+	UnknownType(u8),
+	/// The type tag does not match the decoded variant.
+	UnexpectedType(u8),
+	/// The length of the body does not match the one of its type.
+	InvalidLength { expected: usize, actual: usize },
+}
+
+/// Checks the type tag and the length of `body`, and returns the fields after the tag.
+fn variant_fields<V: TypedMessageVariant>(body: &[u8], len: usize) -> Result<&[u8], DecodeError> {
+	let (tag, fields) = body.split_first().ok_or(DecodeError::Empty)?;
+	if *tag != V::MESSAGE_TYPE {
+		return Err(DecodeError::UnexpectedType(*tag));
+	}
+	if body.len() != len {
+		return Err(DecodeError::InvalidLength {
+			expected: len,
+			actual: body.len(),
+		});
+	}
+
+	Ok(fields)
+}
+
+/// Reads the big-endian `u32` at `offset` of the length-checked `fields`.
+fn read_u32(fields: &[u8], offset: usize) -> u32 {
+	let mut bytes = [0u8; 4];
+	bytes.copy_from_slice(&fields[offset..offset + 4]);
+	u32::from_be_bytes(bytes)
+}
+
+/// Reads the big-endian `u16` at `offset` of the length-checked `fields`.
+fn read_u16(fields: &[u8], offset: usize) -> u16 {
+	let mut bytes = [0u8; 2];
+	bytes.copy_from_slice(&fields[offset..offset + 2]);
+	u16::from_be_bytes(bytes)
+}
+
+/// Reads the hash at `offset` of the length-checked `fields`.
+fn read_h256(fields: &[u8], offset: usize) -> H256 {
+	H256::from_slice(&fields[offset..offset + 32])
+}
+
 impl TryFrom<u8> for DABridgeMessageTypes {
 	type Error = TryFromError;
 
@@ -27,6 +77,8 @@ impl TryFrom<u8> for DABridgeMessageTypes {
 		match num {
 			0 => Ok(Self::Invalid),
 			1 => Ok(Self::DataRootMessage),
+			2 => Ok(Self::DataRootRangeMessage),
+			3 => Ok(Self::HeaderCommitmentMessage),
 			_ => Err(TryFromError::Unknown),
 		}
 	}
@@ -42,6 +94,21 @@ pub struct DataRootMessage {
 	pub data_root: H256,
 }
 
+impl DataRootMessage {
+	/// Length of the encoded message, including its type tag.
+	pub const LEN: usize = 1 + 4 + 32;
+
+	/// Decodes an encoded message, as the Solidity contracts parse it.
+	pub fn decode(body: &[u8]) -> Result<Self, DecodeError> {
+		let fields = variant_fields::<Self>(body, Self::LEN)?;
+
+		Ok(Self {
+			block_number: read_u32(fields, 0),
+			data_root: read_h256(fields, 4),
+		})
+	}
+}
+
 impl TypedMessageVariant for DataRootMessage {
 	const MESSAGE_TYPE: u8 = DABridgeMessageTypes::DataRootMessage as u8;
 
@@ -49,7 +116,7 @@ impl TypedMessageVariant for DataRootMessage {
 		let block_number_bytes = self.block_number.to_be_bytes();
 		let data_root_bytes = self.data_root.as_bytes();
 
-		let mut buf: Vec<u8> = Vec::with_capacity(self.len());
+		let mut buf: Vec<u8> = Vec::with_capacity(Self::LEN);
 
 		buf.push(Self::MESSAGE_TYPE);
 		buf.extend_from_slice(block_number_bytes.as_ref());
@@ -59,9 +126,107 @@ impl TypedMessageVariant for DataRootMessage {
 	}
 }
 
+/// Data root range message variant. Contains the Merkle root of the data roots of the blocks
+/// from `start_block` to `end_block`, both included. See `range::range_root`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataRootRangeMessage {
+	/// First block number of the range
+	pub start_block: u32,
+	/// Last block number of the range
+	pub end_block: u32,
+	/// Merkle root of the data roots of the range
+	pub range_root: H256,
+}
+
+impl DataRootRangeMessage {
+	/// Length of the encoded message, including its type tag.
+	pub const LEN: usize = 1 + 4 + 4 + 32;
+
+	/// Decodes an encoded message, as the Solidity contracts parse it.
+	pub fn decode(body: &[u8]) -> Result<Self, DecodeError> {
+		let fields = variant_fields::<Self>(body, Self::LEN)?;
+
+		Ok(Self {
+			start_block: read_u32(fields, 0),
+			end_block: read_u32(fields, 4),
+			range_root: read_h256(fields, 8),
+		})
+	}
+}
+
+impl TypedMessageVariant for DataRootRangeMessage {
+	const MESSAGE_TYPE: u8 = DABridgeMessageTypes::DataRootRangeMessage as u8;
+
+	fn encode(&self) -> Vec<u8> {
+		let mut buf: Vec<u8> = Vec::with_capacity(Self::LEN);
+
+		buf.push(Self::MESSAGE_TYPE);
+		buf.extend_from_slice(&self.start_block.to_be_bytes());
+		buf.extend_from_slice(&self.end_block.to_be_bytes());
+		buf.extend_from_slice(self.range_root.as_bytes());
+
+		buf
+	}
+}
+
+/// Header commitment message variant. Contains the dimensions of the data matrix of a block,
+/// the hash of its Kate commitments and its data root, so the destination can verify sampled
+/// cells.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HeaderCommitmentMessage {
+	/// Block number
+	pub block_number: u32,
+	/// Rows of the data matrix
+	pub rows: u16,
+	/// Columns of the data matrix
+	pub cols: u16,
+	/// Keccak-256 hash of the Kate commitments of the rows
+	pub commitments_hash: H256,
+	/// Data root
+	pub data_root: H256,
+}
+
+impl HeaderCommitmentMessage {
+	/// Length of the encoded message, including its type tag.
+	pub const LEN: usize = 1 + 4 + 2 + 2 + 32 + 32;
+
+	/// Decodes an encoded message, as the Solidity contracts parse it.
+	pub fn decode(body: &[u8]) -> Result<Self, DecodeError> {
+		let fields = variant_fields::<Self>(body, Self::LEN)?;
+
+		Ok(Self {
+			block_number: read_u32(fields, 0),
+			rows: read_u16(fields, 4),
+			cols: read_u16(fields, 6),
+			commitments_hash: read_h256(fields, 8),
+			data_root: read_h256(fields, 40),
+		})
+	}
+}
+
+impl TypedMessageVariant for HeaderCommitmentMessage {
+	const MESSAGE_TYPE: u8 = DABridgeMessageTypes::HeaderCommitmentMessage as u8;
+
+	fn encode(&self) -> Vec<u8> {
+		let mut buf: Vec<u8> = Vec::with_capacity(Self::LEN);
+
+		buf.push(Self::MESSAGE_TYPE);
+		buf.extend_from_slice(&self.block_number.to_be_bytes());
+		buf.extend_from_slice(&self.rows.to_be_bytes());
+		buf.extend_from_slice(&self.cols.to_be_bytes());
+		buf.extend_from_slice(self.commitments_hash.as_bytes());
+		buf.extend_from_slice(self.data_root.as_bytes());
+
+		buf
+	}
+}
+
 /// Enum of DABridge message types
+#[derive(Clone, Debug, PartialEq)]
 pub enum DABridgeMessages {
 	DataRootMessage(DataRootMessage),
+	DataRootRangeMessage(DataRootRangeMessage),
+	HeaderCommitmentMessage(HeaderCommitmentMessage),
 }
 
 impl From<DataRootMessage> for DABridgeMessages {
@@ -70,12 +235,45 @@ impl From<DataRootMessage> for DABridgeMessages {
 	}
 }
 
+impl From<DataRootRangeMessage> for DABridgeMessages {
+	fn from(data_root_range_msg: DataRootRangeMessage) -> Self {
+		Self::DataRootRangeMessage(data_root_range_msg)
+	}
+}
+
+impl From<HeaderCommitmentMessage> for DABridgeMessages {
+	fn from(header_commitment_msg: HeaderCommitmentMessage) -> Self {
+		Self::HeaderCommitmentMessage(header_commitment_msg)
+	}
+}
+
+impl DABridgeMessages {
+	/// Decodes a message body into the variant of its type tag.
+	pub fn decode(body: &[u8]) -> Result<Self, DecodeError> {
+		let tag = *body.first().ok_or(DecodeError::Empty)?;
+		match DABridgeMessageTypes::try_from(tag) {
+			Ok(DABridgeMessageTypes::DataRootMessage) => {
+				DataRootMessage::decode(body).map(Into::into)
+			},
+			Ok(DABridgeMessageTypes::DataRootRangeMessage) => {
+				DataRootRangeMessage::decode(body).map(Into::into)
+			},
+			Ok(DABridgeMessageTypes::HeaderCommitmentMessage) => {
+				HeaderCommitmentMessage::decode(body).map(Into::into)
+			},
+			Ok(DABridgeMessageTypes::Invalid) | Err(_) => Err(DecodeError::UnknownType(tag)),
+		}
+	}
+}
+
 impl TypedMessage for DABridgeMessages {
 	type MessageEnum = DABridgeMessageTypes;
 
 	fn encode(&self) -> Vec<u8> {
 		match self {
 			Self::DataRootMessage(msg) => msg.encode(),
+			Self::DataRootRangeMessage(msg) => msg.encode(),
+			Self::HeaderCommitmentMessage(msg) => msg.encode(),
 		}
 	}
 }
@@ -85,16 +283,23 @@ mod test {
 	use core::convert::TryInto;
 
 	use frame_support::{parameter_types, BoundedVec};
+	use hex_literal::hex;
 	use nomad_core::{NomadMessage, NON_BODY_LENGTH};
+	use proptest::prelude::*;
+	use test_case::test_case;
 
 	use super::*;
 
 	const DATA_ROOT_MSG_LEN: usize = 1 + 4 + 32;
+	const DATA_ROOT_RANGE_MSG_LEN: usize = 1 + 4 + 4 + 32;
 
 	parameter_types! {
 		const MaxMessageBodyBytes: u32 = 1024;
 	}
 
+	/// Formatted messages of the home, one per line, which `avail-subxt` decodes as well.
+	const DISPATCHED_MESSAGES: &str = include_str!("../fixtures/dispatched_messages.hex");
+
 	#[test]
 	fn it_creates_data_root_msg() {
 		let message = DataRootMessage {
@@ -119,4 +324,245 @@ mod test {
 		let nomad_msg_vec = nomad_msg.to_vec();
 		assert_eq!(nomad_msg_vec.len(), NON_BODY_LENGTH + DATA_ROOT_MSG_LEN);
 	}
+
+	#[test]
+	fn it_creates_data_root_range_msg() {
+		let message = DataRootRangeMessage {
+			start_block: 5,
+			end_block: 8,
+			range_root: H256::repeat_byte(1),
+		};
+
+		assert_eq!(message.len(), DATA_ROOT_RANGE_MSG_LEN);
+
+		let body: DABridgeMessages = message.into();
+		let encoded = body.encode();
+		assert_eq!(encoded[0], DABridgeMessageTypes::DataRootRangeMessage as u8);
+		assert_eq!(encoded[1..5], 5u32.to_be_bytes());
+		assert_eq!(encoded[5..9], 8u32.to_be_bytes());
+		assert_eq!(encoded[9..], [1u8; 32]);
+	}
+
+	#[test]
+	fn it_creates_header_commitment_msg() {
+		let message = HeaderCommitmentMessage {
+			block_number: 5,
+			rows: 256,
+			cols: 512,
+			commitments_hash: H256::repeat_byte(0x11),
+			data_root: H256::repeat_byte(0x22),
+		};
+
+		assert_eq!(message.len(), HeaderCommitmentMessage::LEN);
+
+		// Type tag, block number, rows and cols, packed big-endian.
+		let expected = [
+			hex!("030000000501000200").as_slice(),
+			hex!("1111111111111111111111111111111111111111111111111111111111111111").as_slice(),
+			hex!("2222222222222222222222222222222222222222222222222222222222222222").as_slice(),
+		]
+		.concat();
+		let body: DABridgeMessages = message.clone().into();
+		assert_eq!(body.encode(), expected);
+		assert_eq!(HeaderCommitmentMessage::decode(&expected), Ok(message));
+	}
+
+	#[test]
+	fn it_rejects_invalid_header_commitment_msg() {
+		let encoded = HeaderCommitmentMessage {
+			block_number: 5,
+			rows: 256,
+			cols: 512,
+			commitments_hash: H256::repeat_byte(0x11),
+			data_root: H256::repeat_byte(0x22),
+		}
+		.encode();
+
+		assert_eq!(
+			HeaderCommitmentMessage::decode(&encoded[..72]),
+			Err(DecodeError::InvalidLength {
+				expected: 73,
+				actual: 72
+			})
+		);
+		assert_eq!(
+			HeaderCommitmentMessage::decode(&[encoded.as_slice(), &[0]].concat()),
+			Err(DecodeError::InvalidLength {
+				expected: 73,
+				actual: 74
+			})
+		);
+
+		let mut wrong_type = encoded;
+		wrong_type[0] = DABridgeMessageTypes::DataRootMessage as u8;
+		assert_eq!(
+			HeaderCommitmentMessage::decode(&wrong_type),
+			Err(DecodeError::UnexpectedType(1))
+		);
+	}
+
+	#[test]
+	fn it_decodes_data_root_msg() {
+		// Type tag and block number, packed big-endian.
+		let body = [
+			hex!("0100000005").as_slice(),
+			hex!("0101010101010101010101010101010101010101010101010101010101010101").as_slice(),
+		]
+		.concat();
+
+		let expected = DataRootMessage {
+			block_number: 5,
+			data_root: H256::repeat_byte(1),
+		};
+		assert_eq!(DataRootMessage::decode(&body), Ok(expected.clone()));
+		assert_eq!(DABridgeMessages::decode(&body), Ok(expected.into()));
+	}
+
+	#[test]
+	fn it_decodes_data_root_range_msg() {
+		let body = [
+			hex!("020000000500000008").as_slice(),
+			hex!("0101010101010101010101010101010101010101010101010101010101010101").as_slice(),
+		]
+		.concat();
+
+		let expected = DataRootRangeMessage {
+			start_block: 5,
+			end_block: 8,
+			range_root: H256::repeat_byte(1),
+		};
+		assert_eq!(DataRootRangeMessage::decode(&body), Ok(expected.clone()));
+		assert_eq!(DABridgeMessages::decode(&body), Ok(expected.into()));
+	}
+
+	#[test]
+	fn it_formats_the_dispatched_messages_fixture() {
+		let bodies: Vec<DABridgeMessages> = vec![
+			DataRootMessage {
+				block_number: 5,
+				data_root: H256::repeat_byte(1),
+			}
+			.into(),
+			DataRootRangeMessage {
+				start_block: 5,
+				end_block: 8,
+				range_root: H256::repeat_byte(2),
+			}
+			.into(),
+			HeaderCommitmentMessage {
+				block_number: 5,
+				rows: 256,
+				cols: 512,
+				commitments_hash: H256::repeat_byte(0x11),
+				data_root: H256::repeat_byte(0x22),
+			}
+			.into(),
+		];
+		let fixture = DISPATCHED_MESSAGES
+			.lines()
+			.map(|line| hex::decode(line).unwrap())
+			.collect::<Vec<_>>();
+		assert_eq!(fixture.len(), bodies.len());
+
+		for ((nonce, body), expected) in (1..).zip(bodies).zip(fixture) {
+			let nomad_msg = NomadMessage::<MaxMessageBodyBytes> {
+				origin: 2000,
+				sender: H256::repeat_byte(0xaa),
+				nonce,
+				destination: 1000,
+				recipient: H256::repeat_byte(0xbb),
+				body: body.encode().try_into().unwrap(),
+			};
+			assert_eq!(nomad_msg.to_vec(), expected);
+			assert_eq!(
+				DABridgeMessages::decode(&expected[NON_BODY_LENGTH..]),
+				Ok(body)
+			);
+		}
+	}
+
+	#[test_case(&[] => Err(DecodeError::Empty); "empty body")]
+	#[test_case(&[0; 37] => Err(DecodeError::UnknownType(0)); "invalid type")]
+	#[test_case(&[4; 37] => Err(DecodeError::UnknownType(4)); "unknown type")]
+	#[test_case(&[1; 36] => Err(DecodeError::InvalidLength { expected: 37, actual: 36 }); "short data root")]
+	#[test_case(&[1; 41] => Err(DecodeError::InvalidLength { expected: 37, actual: 41 }); "long data root")]
+	#[test_case(&[2; 37] => Err(DecodeError::InvalidLength { expected: 41, actual: 37 }); "short data root range")]
+	#[test_case(&[3; 41] => Err(DecodeError::InvalidLength { expected: 73, actual: 41 }); "short header commitment")]
+	fn it_rejects_invalid_msgs(body: &[u8]) -> Result<DABridgeMessages, DecodeError> {
+		DABridgeMessages::decode(body)
+	}
+
+	fn any_h256() -> impl Strategy<Value = H256> {
+		any::<[u8; 32]>().prop_map(H256::from)
+	}
+
+	fn any_message() -> impl Strategy<Value = DABridgeMessages> {
+		prop_oneof![
+			(any::<u32>(), any_h256()).prop_map(|(block_number, data_root)| {
+				DataRootMessage {
+					block_number,
+					data_root,
+				}
+				.into()
+			}),
+			(any::<u32>(), any::<u32>(), any_h256()).prop_map(
+				|(start_block, end_block, range_root)| {
+					DataRootRangeMessage {
+						start_block,
+						end_block,
+						range_root,
+					}
+					.into()
+				}
+			),
+			(
+				any::<u32>(),
+				any::<u16>(),
+				any::<u16>(),
+				any_h256(),
+				any_h256()
+			)
+				.prop_map(|(block_number, rows, cols, commitments_hash, data_root)| {
+					HeaderCommitmentMessage {
+						block_number,
+						rows,
+						cols,
+						commitments_hash,
+						data_root,
+					}
+					.into()
+				}),
+		]
+	}
+
+	proptest! {
+		#[test]
+		fn it_roundtrips_msgs(message in any_message()) {
+			let body = message.encode();
+			prop_assert_eq!(DABridgeMessages::decode(&body), Ok(message));
+		}
+
+		#[test]
+		fn it_rejects_truncated_and_extended_msgs(message in any_message(), extra in 1usize..8) {
+			let body = message.encode();
+			let len = body.len();
+
+			let truncated = &body[..len - extra];
+			prop_assert_eq!(
+				DABridgeMessages::decode(truncated),
+				Err(DecodeError::InvalidLength { expected: len, actual: len - extra })
+			);
+
+			let extended = [body.as_slice(), &vec![0u8; extra]].concat();
+			prop_assert_eq!(
+				DABridgeMessages::decode(&extended),
+				Err(DecodeError::InvalidLength { expected: len, actual: len + extra })
+			);
+		}
+
+		#[test]
+		fn it_never_panics_decoding_arbitrary_bytes(body in proptest::collection::vec(any::<u8>(), 0..128)) {
+			let _ = DABridgeMessages::decode(&body);
+		}
+	}
 }
@@ -0,0 +1,36 @@
+use frame_support::Parameter;
+
+/// Merkle Mountain Range of the block hashes of the chain, like `pallet_mmr` with
+/// `ParentNumberAndHash` leaves.
+///
+/// It proves the hashes of blocks which were pruned from `frame_system::BlockHash`.
+pub trait BlockHashMmr<BlockNumber, Hash> {
+	/// Proof of a block hash against the root of the MMR.
+	type Proof: Parameter;
+
+	/// Number of leaves and root of the current MMR.
+	fn leaf_count_and_root() -> (u64, Hash);
+
+	/// Number of leaves of the MMR which `proof` was generated against.
+	fn proof_leaf_count(proof: &Self::Proof) -> u64;
+
+	/// Returns `true` if `proof` links `hash`, as the hash of block `number`, to `root`.
+	fn verify_proof(root: Hash, number: BlockNumber, hash: Hash, proof: Self::Proof) -> bool;
+}
+
+/// No MMR, so only the hashes of `frame_system::BlockHash` are known.
+impl<BlockNumber, Hash: Default> BlockHashMmr<BlockNumber, Hash> for () {
+	type Proof = ();
+
+	fn leaf_count_and_root() -> (u64, Hash) {
+		(0, Hash::default())
+	}
+
+	fn proof_leaf_count(_proof: &Self::Proof) -> u64 {
+		0
+	}
+
+	fn verify_proof(_root: Hash, _number: BlockNumber, _hash: Hash, _proof: Self::Proof) -> bool {
+		false
+	}
+}
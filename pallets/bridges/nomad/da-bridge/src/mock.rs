@@ -1,8 +1,12 @@
-use frame_support::{derive_impl, parameter_types};
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{ConstU32, ConstU64},
+	PalletId,
+};
 use frame_system::{self as system, header_builder::da, test_utils::TestRandomness};
 use nomad_base::NomadBase;
 use sp_core::{H160, H256};
-use sp_runtime::{traits::IdentityLookup, AccountId32, BuildStorage};
+use sp_runtime::{traits::IdentityLookup, AccountId32, BuildStorage, Perbill};
 
 use crate::{self as da_bridge};
 
@@ -15,6 +19,7 @@ type Block = frame_system::mocking::MockDaBlock<Test>;
 frame_support::construct_runtime!(
 	pub struct Test {
 		System: frame_system,
+		Balances: pallet_balances,
 		UpdaterManager: nomad_updater_manager,
 		Home: nomad_home,
 		DABridge: da_bridge,
@@ -23,10 +28,16 @@ frame_support::construct_runtime!(
 
 parameter_types! {
 	pub const BlockHashCount: u32 = 250;
+	pub const SlashRewardShare: Perbill = Perbill::from_percent(10);
+	pub const HomePalletId: PalletId = PalletId(*b"nmd/home");
 }
 
+/// Destination domains enrolled in the home.
+pub(crate) const TEST_DESTINATIONS: [u32; 2] = [1000, 1001];
+
 #[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
 impl system::Config for Test {
+	type AccountData = pallet_balances::AccountData<u64>;
 	type AccountId = AccountId32;
 	type BaseCallFilter = frame_support::traits::Everything;
 	type Block = Block;
@@ -43,20 +54,82 @@ impl system::Config for Test {
 	type UncheckedExtrinsic = UncheckedExtrinsic;
 }
 
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
+	type Balance = u64;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type MaxHolds = ();
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeHoldReason = ();
+	type WeightInfo = ();
+}
+
 impl nomad_updater_manager::Config for Test {
+	type Currency = Balances;
 	type RuntimeEvent = RuntimeEvent;
+	type SlashRewardShare = SlashRewardShare;
+	type Slashed = ();
+	type UnbondingDelay = ConstU32<10>;
+	type UpdaterBond = ConstU64<1_000>;
 }
 
 #[derive_impl(nomad_home::config_preludes::TestDefaultConfig as nomad_home::DefaultConfig)]
 impl nomad_home::Config for Test {
+	type OnPrunedMessages = DABridge;
+	type PalletId = HomePalletId;
 	type RuntimeEvent = RuntimeEvent;
 }
 
 #[derive_impl(da_bridge::config_preludes::TestDefaultConfig as da_bridge::DefaultConfig)]
 impl da_bridge::Config for Test {
+	type AutoDispatchPeriod = ConstU32<3>;
+	type BlockHashMmr = TestBlockHashMmr;
+	type MmrRootHistory = ConstU32<2>;
 	type RuntimeEvent = RuntimeEvent;
 }
 
+parameter_types! {
+	/// Leaves of the `TestBlockHashMmr`.
+	pub static MmrLeaves: Vec<(u32, H256)> = vec![];
+}
+
+/// MMR of the `MmrLeaves`, whose root is its number of leaves.
+pub(crate) struct TestBlockHashMmr;
+
+impl TestBlockHashMmr {
+	pub(crate) fn root(leaf_count: u64) -> H256 {
+		H256::from_low_u64_be(leaf_count)
+	}
+}
+
+impl da_bridge::BlockHashMmr<u32, H256> for TestBlockHashMmr {
+	/// Number of leaves of the proven MMR.
+	type Proof = u64;
+
+	fn leaf_count_and_root() -> (u64, H256) {
+		let leaf_count = MmrLeaves::get().len() as u64;
+		(leaf_count, Self::root(leaf_count))
+	}
+
+	fn proof_leaf_count(proof: &Self::Proof) -> u64 {
+		*proof
+	}
+
+	fn verify_proof(root: H256, number: u32, hash: H256, proof: Self::Proof) -> bool {
+		root == Self::root(proof)
+			&& MmrLeaves::get()
+				.into_iter()
+				.take(proof as usize)
+				.any(|leaf| leaf == (number, hash))
+	}
+}
+
 #[derive(Default)]
 pub(crate) struct ExtBuilder {
 	updater: H160,
@@ -82,7 +155,8 @@ impl ExtBuilder {
 			updater: self.updater,
 			local_domain: self.local_domain,
 			committed_root: self.committed_root,
-			_phantom: Default::default(),
+			destinations: TEST_DESTINATIONS.to_vec(),
+			..Default::default()
 		}
 		.assimilate_storage(&mut t)
 		.expect("Pallet base storage can be assimilated");
@@ -99,7 +173,7 @@ impl ExtBuilder {
 	}
 }
 
-pub(crate) fn _events() -> Vec<super::Event<Test>> {
+pub(crate) fn events() -> Vec<super::Event<Test>> {
 	System::events()
 		.into_iter()
 		.map(|r| r.event)
@@ -0,0 +1,72 @@
+use binary_merkle_tree::{merkle_proof, merkle_root, verify_proof, Leaf, MerkleProof};
+use sp_core::H256;
+use sp_runtime::traits::Keccak256;
+
+/// Root of the Keccak-256 Binary Merkle Tree whose leaves are the data roots of a contiguous
+/// range of blocks, in block order.
+pub fn range_root(data_roots: &[H256]) -> H256 {
+	merkle_root::<Keccak256, _>(data_roots.iter())
+}
+
+/// Proof of the data root at `index` of `data_roots` against their `range_root`.
+///
+/// The `index` of a block is its block number minus the start block of the range.
+pub fn range_proof(data_roots: &[H256], index: u32) -> Option<MerkleProof<H256, H256>> {
+	let index = usize::try_from(index).ok()?;
+	if index >= data_roots.len() {
+		return None;
+	}
+
+	Some(merkle_proof::<Keccak256, _, _>(
+		data_roots.iter().copied(),
+		index,
+	))
+}
+
+/// Returns `true` if `proof` links its data root to `range_root`.
+pub fn verify_range_proof(range_root: &H256, proof: &MerkleProof<H256, H256>) -> bool {
+	verify_proof::<Keccak256, _, _>(
+		range_root,
+		proof.proof.iter().copied(),
+		proof.number_of_leaves,
+		proof.leaf_index,
+		Leaf::Value(proof.leaf.as_bytes()),
+	)
+}
+
+#[cfg(test)]
+mod test {
+	use test_case::test_case;
+
+	use super::*;
+
+	fn data_roots(len: u8) -> Vec<H256> {
+		(0..len).map(H256::repeat_byte).collect()
+	}
+
+	#[test_case(1; "single block")]
+	#[test_case(4; "complete tree")]
+	#[test_case(7; "incomplete tree")]
+	fn proofs_link_each_data_root_to_the_range_root(len: u8) {
+		let data_roots = data_roots(len);
+		let root = range_root(&data_roots);
+
+		for (index, data_root) in data_roots.iter().enumerate() {
+			let proof = range_proof(&data_roots, index as u32).unwrap();
+			assert_eq!(proof.root, root);
+			assert_eq!(proof.leaf, *data_root);
+			assert!(verify_range_proof(&root, &proof));
+		}
+		assert!(range_proof(&data_roots, len.into()).is_none());
+	}
+
+	#[test]
+	fn proofs_do_not_verify_other_data_roots() {
+		let data_roots = data_roots(3);
+		let root = range_root(&data_roots);
+
+		let mut proof = range_proof(&data_roots, 1).unwrap();
+		proof.leaf = H256::repeat_byte(9);
+		assert!(!verify_range_proof(&root, &proof));
+	}
+}
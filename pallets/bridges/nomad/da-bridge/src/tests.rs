@@ -1,13 +1,24 @@
 use avail_core::header::{Header, HeaderExtension};
-use frame_support::assert_ok;
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::DispatchResultWithPostInfo,
+	traits::{Currency, Hooks},
+	BoundedVec,
+};
 use frame_system::pallet_prelude::BlockNumberFor;
 use hex_literal::hex;
 use nomad_base::testing::*;
+use nomad_core::{NomadMessage, TypedMessage};
 use nomad_merkle::Merkle;
-use sp_core::H256;
-use sp_runtime::{testing::Digest, traits::BlakeTwo256, AccountId32};
+use sp_core::{hashing::keccak_256, Get, H160, H256};
+use sp_runtime::{testing::Digest, traits::BlakeTwo256, AccountId32, DispatchError};
 
-use crate::mock::*;
+use crate::{
+	message::{DABridgeMessages, HeaderCommitmentMessage},
+	mock::*,
+	range::{range_proof, range_root, verify_range_proof},
+	Error, Event,
+};
 
 const TEST_SENDER_VEC: [u8; 32] = [2u8; 32];
 const TEST_SENDER_ACCOUNT: AccountId32 = AccountId32::new(TEST_SENDER_VEC);
@@ -53,7 +64,7 @@ fn it_accepts_valid_extrinsic_root() {
 				RuntimeOrigin::signed(TEST_SENDER_ACCOUNT),
 				DESTINATION_DOMAIN,
 				H256::zero(),
-				Box::new(header)
+				Box::new(header.clone())
 			));
 
 			// Get home's merkle root post-enqueue
@@ -63,5 +74,774 @@ fn it_accepts_valid_extrinsic_root() {
 			// Ensure home's merkle root changed after enqueueing message
 			assert_ne!(root_pre, root_post);
 			assert_eq!(nonce_pre + 1, nonce_post);
+
+			// The dispatched message can be proven once the home root is committed
+			assert_eq!(
+				DABridge::data_root_message_proof(10, DESTINATION_DOMAIN),
+				None
+			);
+			let signed_update = TEST_UPDATER.sign_update(Home::base().committed_root, root_post);
+			assert_ok!(Home::submit_update(
+				RuntimeOrigin::signed(TEST_SENDER_ACCOUNT),
+				signed_update
+			));
+			let proof = DABridge::data_root_message_proof(10, DESTINATION_DOMAIN).unwrap();
+			assert_eq!(proof.proof.root, root_post);
+			assert_eq!(proof.proof.leaf, H256(keccak_256(&proof.message)));
+			assert!(proof.proof.verify());
+			assert!(proof
+				.message
+				.ends_with(header.extension.data_root().as_bytes()));
+			assert_eq!(
+				DABridge::data_root_message_proof(10, DESTINATION_DOMAIN + 1),
+				None
+			);
+		})
+}
+
+/// Creates the header of block `number` and registers its hash in the block hash mapping.
+fn registered_header(number: u32) -> Header<BlockNumberFor<Test>, BlakeTwo256> {
+	let header = Header::<BlockNumberFor<Test>, BlakeTwo256> {
+		parent_hash: [1u8; 32].into(),
+		number,
+		state_root: [2u8; 32].into(),
+		extrinsics_root: [3u8; 32].into(),
+		digest: Digest { logs: vec![] },
+		extension: HeaderExtension::default(),
+	};
+	frame_system::BlockHash::<Test>::insert(number, header.hash());
+	header
+}
+
+fn bond(updater: H160) {
+	let bonder = AccountId32::new([5u8; 32]);
+	let _ = Balances::deposit_creating(&bonder, 10_000);
+	assert_ok!(UpdaterManager::do_bond(bonder, updater));
+}
+
+fn dispatch_range(
+	headers: Vec<Header<BlockNumberFor<Test>, BlakeTwo256>>,
+) -> DispatchResultWithPostInfo {
+	DABridge::try_dispatch_data_root_range(
+		RuntimeOrigin::signed(TEST_SENDER_ACCOUNT),
+		DESTINATION_DOMAIN,
+		H256::zero(),
+		BoundedVec::truncate_from(headers),
+	)
+}
+
+#[test]
+fn it_dispatches_data_root_range() {
+	ExtBuilder::default()
+		.with_base(*TEST_NOMAD_BASE)
+		.build()
+		.execute_with(|| {
+			let headers = (10..=12).map(registered_header).collect::<Vec<_>>();
+			let data_roots = headers
+				.iter()
+				.map(|header| header.extension.data_root())
+				.collect::<Vec<_>>();
+
+			let root_pre = Home::tree().root();
+			let nonce_pre = Home::nonces(DESTINATION_DOMAIN);
+
+			assert_ok!(dispatch_range(headers));
+
+			assert_ne!(root_pre, Home::tree().root());
+			assert_eq!(nonce_pre + 1, Home::nonces(DESTINATION_DOMAIN));
+			assert_eq!(
+				DABridge::last_dispatched_block(DESTINATION_DOMAIN),
+				Some(12)
+			);
+			assert_eq!(
+				events(),
+				vec![Event::DataRootRangeDispatched {
+					destination_domain: DESTINATION_DOMAIN,
+					recipient_address: H256::zero(),
+					start_block: 10,
+					end_block: 12,
+					range_root: range_root(&data_roots),
+				}]
+			);
+
+			// Each data root of the range is proven with the range message.
+			let signed_update =
+				TEST_UPDATER.sign_update(Home::base().committed_root, Home::tree().root());
+			assert_ok!(Home::submit_update(
+				RuntimeOrigin::signed(TEST_SENDER_ACCOUNT),
+				signed_update
+			));
+			let proof = DABridge::data_root_message_proof(11, DESTINATION_DOMAIN).unwrap();
+			assert!(proof.proof.verify());
+			assert_eq!(proof.proof.leaf, H256(keccak_256(&proof.message)));
+			assert!(proof.dispatch.is_range);
+			assert!(proof.message.ends_with(range_root(&data_roots).as_bytes()));
+			let range_proof = range_proof(&data_roots, 11 - proof.dispatch.start_block).unwrap();
+			assert!(verify_range_proof(&proof.dispatch.root, &range_proof));
+		})
+}
+
+#[test]
+fn it_rejects_duplicated_data_root_dispatches() {
+	ExtBuilder::default()
+		.with_base(*TEST_NOMAD_BASE)
+		.build()
+		.execute_with(|| {
+			let dispatch = |destination_domain, number| {
+				DABridge::try_dispatch_data_root(
+					RuntimeOrigin::signed(TEST_SENDER_ACCOUNT),
+					destination_domain,
+					H256::zero(),
+					Box::new(registered_header(number)),
+				)
+			};
+
+			assert_ok!(dispatch(DESTINATION_DOMAIN, 11));
+			assert_noop!(
+				dispatch(DESTINATION_DOMAIN, 11),
+				Error::<Test>::DataRootAlreadyDispatched
+			);
+			// Earlier blocks which were not dispatched can still be dispatched.
+			assert_ok!(dispatch(DESTINATION_DOMAIN, 10));
+			assert_noop!(
+				dispatch(DESTINATION_DOMAIN, 10),
+				Error::<Test>::DataRootAlreadyDispatched
+			);
+			assert_ok!(dispatch(DESTINATION_DOMAIN + 1, 10));
+
+			// Dispatches to other recipients are not duplicates.
+			assert_ok!(DABridge::try_dispatch_data_root(
+				RuntimeOrigin::signed(TEST_SENDER_ACCOUNT),
+				DESTINATION_DOMAIN,
+				H256::repeat_byte(1),
+				Box::new(registered_header(11)),
+			));
+
+			assert_eq!(
+				DABridge::last_dispatched_block(DESTINATION_DOMAIN),
+				Some(11)
+			);
+			assert_eq!(
+				DABridge::last_dispatched_block(DESTINATION_DOMAIN + 1),
+				Some(10)
+			);
+			assert_eq!(
+				DABridge::last_dispatched_block(DESTINATION_DOMAIN + 2),
+				None
+			);
+		})
+}
+
+#[test]
+fn it_rejects_overlapping_data_root_ranges() {
+	ExtBuilder::default()
+		.with_base(*TEST_NOMAD_BASE)
+		.build()
+		.execute_with(|| {
+			let range = |start, end| (start..=end).map(registered_header).collect::<Vec<_>>();
+			assert_ok!(dispatch_range(range(10, 12)));
+
+			assert_noop!(
+				dispatch_range(range(10, 12)),
+				Error::<Test>::DataRootAlreadyDispatched
+			);
+			assert_noop!(
+				dispatch_range(range(12, 14)),
+				Error::<Test>::DataRootAlreadyDispatched
+			);
+			assert_noop!(
+				DABridge::try_dispatch_data_root(
+					RuntimeOrigin::signed(TEST_SENDER_ACCOUNT),
+					DESTINATION_DOMAIN,
+					H256::zero(),
+					Box::new(registered_header(11)),
+				),
+				Error::<Test>::DataRootAlreadyDispatched
+			);
+
+			assert_ok!(dispatch_range(range(13, 14)));
+			assert_ok!(dispatch_range(range(7, 9)));
+			assert_noop!(
+				dispatch_range(range(5, 7)),
+				Error::<Test>::DataRootAlreadyDispatched
+			);
+			assert_eq!(
+				DABridge::last_dispatched_block(DESTINATION_DOMAIN),
+				Some(14)
+			);
+			assert_eq!(
+				crate::DispatchedRanges::<Test>::get(DESTINATION_DOMAIN, H256::zero()).to_vec(),
+				vec![(7, 14)]
+			);
+		})
+}
+
+#[test]
+fn it_forgets_the_oldest_dispatched_ranges() {
+	ExtBuilder::default()
+		.with_base(*TEST_NOMAD_BASE)
+		.build()
+		.execute_with(|| {
+			let max_ranges = <Test as crate::Config>::MaxDispatchedRanges::get();
+			let dispatch = |number| {
+				DABridge::try_dispatch_data_root(
+					RuntimeOrigin::signed(TEST_SENDER_ACCOUNT),
+					DESTINATION_DOMAIN,
+					H256::zero(),
+					Box::new(registered_header(number)),
+				)
+			};
+			for index in 1..=max_ranges + 1 {
+				assert_ok!(dispatch(index * 2));
+			}
+
+			// Block 2 is in the oldest range, which is forgotten.
+			let ranges = crate::DispatchedRanges::<Test>::get(DESTINATION_DOMAIN, H256::zero());
+			assert_eq!(ranges.len() as u32, max_ranges);
+			assert_eq!(ranges.first(), Some(&(4, 4)));
+			assert_ok!(dispatch(2));
+			assert_noop!(dispatch(4), Error::<Test>::DataRootAlreadyDispatched);
+
+			// Filling a gap merges the ranges around it.
+			assert_ok!(dispatch(5));
+			assert_eq!(
+				crate::DispatchedRanges::<Test>::get(DESTINATION_DOMAIN, H256::zero()).first(),
+				Some(&(4, 6))
+			);
+		})
+}
+
+#[test]
+fn it_records_the_last_data_root_dispatches() {
+	ExtBuilder::default()
+		.with_base(*TEST_NOMAD_BASE)
+		.build()
+		.execute_with(|| {
+			let max_records = <Test as crate::Config>::MaxDataRootDispatchRecords::get();
+			let range = |start, end| (start..=end).map(registered_header).collect::<Vec<_>>();
+			for start in 0..=max_records {
+				assert_ok!(dispatch_range(range(start * 10, start * 10 + 2)));
+			}
+			assert_eq!(
+				DABridge::data_root_dispatch_count(DESTINATION_DOMAIN),
+				max_records + 1
+			);
+
+			// The oldest dispatch is forgotten.
+			assert_eq!(DABridge::data_root_dispatch(1, DESTINATION_DOMAIN), None);
+			for start in 1..=max_records {
+				for number in start * 10..=start * 10 + 2 {
+					let dispatch =
+						DABridge::data_root_dispatch(number, DESTINATION_DOMAIN).unwrap();
+					assert_eq!(dispatch.start_block, start * 10);
+					assert_eq!(dispatch.end_block, start * 10 + 2);
+					assert!(dispatch.is_range);
+				}
+				// Blocks between the ranges were not dispatched.
+				assert_eq!(
+					DABridge::data_root_dispatch(start * 10 + 3, DESTINATION_DOMAIN),
+					None
+				);
+			}
+			assert_eq!(
+				DABridge::data_root_dispatch(15, DESTINATION_DOMAIN + 1),
+				None
+			);
+		})
+}
+
+#[test]
+fn it_forgets_the_dispatches_of_pruned_messages() {
+	ExtBuilder::default()
+		.with_base(*TEST_NOMAD_BASE)
+		.build()
+		.execute_with(|| {
+			let dispatch = |number| {
+				DABridge::try_dispatch_data_root(
+					RuntimeOrigin::signed(TEST_SENDER_ACCOUNT),
+					DESTINATION_DOMAIN,
+					H256::zero(),
+					Box::new(registered_header(number)),
+				)
+			};
+			let dispatch_header_commitment = |number| {
+				DABridge::try_dispatch_header_commitment(
+					RuntimeOrigin::signed(TEST_SENDER_ACCOUNT),
+					DESTINATION_DOMAIN,
+					H256::zero(),
+					Box::new(registered_header(number)),
+				)
+			};
+			assert_ok!(dispatch(10));
+			let committed_root = Home::tree().root();
+			let signed_update =
+				TEST_UPDATER.sign_update(Home::base().committed_root, committed_root);
+			assert_ok!(Home::submit_update(
+				RuntimeOrigin::signed(TEST_SENDER_ACCOUNT),
+				signed_update
+			));
+			assert_ok!(dispatch_range((11..=12).map(registered_header).collect()));
+			assert_ok!(dispatch_header_commitment(11));
+
+			// The home fails, and recovers pruning the messages after the committed root.
+			bond(TEST_UPDATER.address());
+			let improper = TEST_UPDATER.sign_update(committed_root, H256::repeat_byte(9));
+			assert_ok!(Home::improper_update(
+				RuntimeOrigin::signed(TEST_SENDER_ACCOUNT),
+				improper
+			));
+			bond(FAKE_UPDATER.address());
+			assert_ok!(Home::recover(
+				RuntimeOrigin::root(),
+				FAKE_UPDATER.address(),
+				committed_root,
+				nomad_home::PendingMessages::Prune,
+				2
+			));
+
+			assert_eq!(DABridge::data_root_dispatch_count(DESTINATION_DOMAIN), 1);
+			assert_eq!(DABridge::data_root_dispatch(11, DESTINATION_DOMAIN), None);
+			assert_eq!(
+				DABridge::last_dispatched_block(DESTINATION_DOMAIN),
+				Some(10)
+			);
+			assert!(DABridge::data_root_dispatch(10, DESTINATION_DOMAIN).is_some());
+
+			// The pruned blocks can be dispatched again, and take the pruned leaf indices.
+			assert_ok!(dispatch(11));
+			assert_ok!(dispatch_header_commitment(11));
+			assert_eq!(
+				DABridge::data_root_dispatch(11, DESTINATION_DOMAIN).map(|d| d.leaf_index),
+				Some(1)
+			);
+		})
+}
+
+#[test]
+fn it_dispatches_header_commitments() {
+	use avail_core::{header::extension::v1, kate_commitment::v1::KateCommitment};
+
+	ExtBuilder::default()
+		.with_base(*TEST_NOMAD_BASE)
+		.build()
+		.execute_with(|| {
+			let mut header = registered_header(10);
+			header.extension = v1::HeaderExtension {
+				app_lookup: Default::default(),
+				commitment: KateCommitment {
+					rows: 2,
+					cols: 4,
+					commitment: vec![5u8; 96],
+					data_root: H256::repeat_byte(6),
+				},
+			}
+			.into();
+			frame_system::BlockHash::<Test>::insert(10, header.hash());
+			let dispatch = |header| {
+				DABridge::try_dispatch_header_commitment(
+					RuntimeOrigin::signed(TEST_SENDER_ACCOUNT),
+					DESTINATION_DOMAIN,
+					H256::zero(),
+					Box::new(header),
+				)
+			};
+
+			let nonce_pre = Home::nonces(DESTINATION_DOMAIN);
+			assert_ok!(dispatch(header.clone()));
+			assert_eq!(nonce_pre + 1, Home::nonces(DESTINATION_DOMAIN));
+			assert_eq!(
+				DABridge::header_commitment_dispatch(10, DESTINATION_DOMAIN)
+					.map(|(leaf_index, _)| leaf_index),
+				Some(Home::tree().count() - 1)
+			);
+
+			let commitments_hash = H256(keccak_256(&[5u8; 96]));
+			assert_eq!(
+				events(),
+				vec![Event::HeaderCommitmentDispatched {
+					destination_domain: DESTINATION_DOMAIN,
+					recipient_address: H256::zero(),
+					block_number: 10,
+					rows: 2,
+					cols: 4,
+					commitments_hash,
+					data_root: H256::repeat_byte(6),
+				}]
+			);
+			let leaf = Home::leaf(Home::tree().count() - 1).unwrap();
+			let message = HeaderCommitmentMessage {
+				block_number: 10,
+				rows: 2,
+				cols: 4,
+				commitments_hash,
+				data_root: H256::repeat_byte(6),
+			};
+			let body: BoundedVec<u8, <Test as nomad_home::Config>::MaxMessageBodyBytes> =
+				DABridgeMessages::from(message).encode().try_into().unwrap();
+			let nomad_message = NomadMessage {
+				origin: TEST_NOMAD_BASE.local_domain,
+				sender: <Test as crate::Config>::DABridgePalletId::get(),
+				nonce: nonce_pre,
+				destination: DESTINATION_DOMAIN,
+				recipient: H256::zero(),
+				body,
+			};
+			assert_eq!(leaf, nomad_message.hash());
+
+			assert_noop!(
+				dispatch(header.clone()),
+				Error::<Test>::HeaderCommitmentAlreadyDispatched
+			);
+
+			// The data root of the block can still be dispatched.
+			assert_eq!(DABridge::last_dispatched_block(DESTINATION_DOMAIN), None);
+			assert_ok!(DABridge::try_dispatch_data_root(
+				RuntimeOrigin::signed(TEST_SENDER_ACCOUNT),
+				DESTINATION_DOMAIN,
+				H256::zero(),
+				Box::new(header.clone())
+			));
+			let mut unknown = header;
+			unknown.number = 11;
+			assert_noop!(
+				dispatch(unknown),
+				Error::<Test>::HashOfBlockNotMatchBlockNumber
+			);
+		})
+}
+
+#[test]
+fn it_rejects_invalid_data_root_ranges() {
+	ExtBuilder::default()
+		.with_base(*TEST_NOMAD_BASE)
+		.build()
+		.execute_with(|| {
+			assert_noop!(dispatch_range(vec![]), Error::<Test>::EmptyDataRootRange);
+
+			let gap = vec![registered_header(10), registered_header(12)];
+			assert_noop!(
+				dispatch_range(gap),
+				Error::<Test>::NonContiguousDataRootRange
+			);
+
+			let mut unknown = registered_header(14);
+			unknown.state_root = [9u8; 32].into();
+			let headers = vec![registered_header(13), unknown];
+			assert_noop!(
+				dispatch_range(headers),
+				Error::<Test>::HashOfBlockNotMatchBlockNumber
+			);
+		})
+}
+
+const AUTO_RECIPIENT: H256 = H256::repeat_byte(7);
+
+/// Finalizes blocks up to `n` with the data root `H256::repeat_byte(number)`, initializing the
+/// bridge for the next block each time.
+fn finalize_blocks_up_to(n: u8) {
+	let next = System::block_number() as u8;
+	for number in next..=n {
+		frame_system::DataRoot::<Test>::put(H256::repeat_byte(number));
+		System::set_block_number(u32::from(number) + 1);
+		DABridge::on_initialize(u32::from(number) + 1);
+	}
+}
+
+fn set_auto_dispatch_destinations(destinations: Vec<(u32, H256)>) {
+	assert_ok!(DABridge::set_auto_dispatch_destinations(
+		RuntimeOrigin::root(),
+		BoundedVec::truncate_from(destinations)
+	));
+}
+
+fn auto_dispatch_event(start_block: u8, end_block: u8) -> Event<Test> {
+	let data_roots = (start_block..=end_block)
+		.map(H256::repeat_byte)
+		.collect::<Vec<_>>();
+	Event::DataRootRangeDispatched {
+		destination_domain: DESTINATION_DOMAIN,
+		recipient_address: AUTO_RECIPIENT,
+		start_block: start_block.into(),
+		end_block: end_block.into(),
+		range_root: range_root(&data_roots),
+	}
+}
+
+#[test]
+fn it_auto_dispatches_data_roots_every_period() {
+	ExtBuilder::default()
+		.with_base(*TEST_NOMAD_BASE)
+		.build()
+		.execute_with(|| {
+			set_auto_dispatch_destinations(vec![(DESTINATION_DOMAIN, AUTO_RECIPIENT)]);
+			let nonce_pre = Home::nonces(DESTINATION_DOMAIN);
+
+			finalize_blocks_up_to(2);
+			assert_eq!(DABridge::pending_data_roots().len(), 2);
+			assert_eq!(nonce_pre, Home::nonces(DESTINATION_DOMAIN));
+
+			finalize_blocks_up_to(6);
+			assert!(DABridge::pending_data_roots().is_empty());
+			assert_eq!(nonce_pre + 2, Home::nonces(DESTINATION_DOMAIN));
+			assert_eq!(DABridge::last_dispatched_block(DESTINATION_DOMAIN), Some(6));
+			assert_eq!(
+				events()[1..],
+				[auto_dispatch_event(1, 3), auto_dispatch_event(4, 6)]
+			);
+		})
+}
+
+#[test]
+fn it_skips_already_dispatched_blocks() {
+	ExtBuilder::default()
+		.with_base(*TEST_NOMAD_BASE)
+		.build()
+		.execute_with(|| {
+			set_auto_dispatch_destinations(vec![(DESTINATION_DOMAIN, AUTO_RECIPIENT)]);
+			crate::DispatchedRanges::<Test>::insert(
+				DESTINATION_DOMAIN,
+				AUTO_RECIPIENT,
+				BoundedVec::truncate_from(vec![(2, 2), (5, 7)]),
+			);
+			let nonce_pre = Home::nonces(DESTINATION_DOMAIN);
+
+			// The blocks around a dispatched block are dispatched by ranges of their own.
+			finalize_blocks_up_to(3);
+			assert_eq!(nonce_pre + 2, Home::nonces(DESTINATION_DOMAIN));
+			assert_eq!(
+				events()[1..],
+				[auto_dispatch_event(1, 1), auto_dispatch_event(3, 3)]
+			);
+
+			finalize_blocks_up_to(9);
+			assert_eq!(nonce_pre + 4, Home::nonces(DESTINATION_DOMAIN));
+			assert_eq!(
+				events()[3..],
+				[auto_dispatch_event(4, 4), auto_dispatch_event(8, 9)]
+			);
+
+			// The data roots auto dispatched are not dispatched again by a range.
+			let headers = (9..=10).map(registered_header).collect::<Vec<_>>();
+			assert_noop!(
+				DABridge::try_dispatch_data_root_range(
+					RuntimeOrigin::signed(TEST_SENDER_ACCOUNT),
+					DESTINATION_DOMAIN,
+					AUTO_RECIPIENT,
+					BoundedVec::truncate_from(headers.clone()),
+				),
+				Error::<Test>::DataRootAlreadyDispatched
+			);
+			assert_ok!(dispatch_range(headers));
+		})
+}
+
+#[test]
+fn it_keeps_data_roots_queued_until_dispatched_to_every_destination() {
+	ExtBuilder::default()
+		.with_base(*TEST_NOMAD_BASE)
+		.build()
+		.execute_with(|| {
+			let failing = DESTINATION_DOMAIN + 1;
+			set_auto_dispatch_destinations(vec![
+				(DESTINATION_DOMAIN, AUTO_RECIPIENT),
+				(failing, AUTO_RECIPIENT),
+			]);
+			assert_ok!(Home::unenroll_destination(RuntimeOrigin::root(), failing));
+
+			finalize_blocks_up_to(3);
+			assert_eq!(
+				events()[1..],
+				[
+					auto_dispatch_event(1, 3),
+					Event::AutoDispatchFailed {
+						destination_domain: failing,
+						start_block: 1,
+						end_block: 3,
+						error: nomad_home::Error::<Test>::DestinationNotEnrolled.into(),
+					}
+				]
+			);
+			assert_eq!(DABridge::pending_data_roots().len(), 3);
+			assert_eq!(DABridge::last_dispatched_block(failing), None);
+
+			// The failed data roots are dispatched with the next ones.
+			assert_ok!(Home::enroll_destination(RuntimeOrigin::root(), failing));
+			let nonce_pre = Home::nonces(failing);
+			finalize_blocks_up_to(6);
+			assert_eq!(nonce_pre + 1, Home::nonces(failing));
+			assert_eq!(DABridge::last_dispatched_block(failing), Some(6));
+			assert_eq!(DABridge::last_dispatched_block(DESTINATION_DOMAIN), Some(6));
+			assert!(events().contains(&auto_dispatch_event(4, 6)));
+			assert_eq!(
+				DABridge::data_root_dispatch(1, failing).map(|d| (d.start_block, d.end_block)),
+				Some((1, 6))
+			);
+			assert!(DABridge::pending_data_roots().is_empty());
+		})
+}
+
+#[test]
+fn it_drops_the_oldest_data_root_of_a_full_queue() {
+	ExtBuilder::default()
+		.with_base(*TEST_NOMAD_BASE)
+		.build()
+		.execute_with(|| {
+			let max_range = <Test as crate::Config>::MaxDataRootRange::get() as u8;
+			set_auto_dispatch_destinations(vec![(DESTINATION_DOMAIN, AUTO_RECIPIENT)]);
+			assert_ok!(Home::unenroll_destination(
+				RuntimeOrigin::root(),
+				DESTINATION_DOMAIN
+			));
+
+			finalize_blocks_up_to(max_range + 2);
+			assert_eq!(DABridge::pending_data_roots().len(), max_range as usize);
+			assert_eq!(crate::PendingRangeStart::<Test>::get(), 3);
+
+			assert_ok!(Home::enroll_destination(
+				RuntimeOrigin::root(),
+				DESTINATION_DOMAIN
+			));
+			// The next period ends with a full queue.
+			let next_dispatch = (max_range + 2) / 3 * 3 + 3;
+			finalize_blocks_up_to(next_dispatch);
+			let start = next_dispatch + 1 - max_range;
+			assert_eq!(
+				events().last(),
+				Some(&auto_dispatch_event(start, next_dispatch))
+			);
+			assert!(DABridge::pending_data_roots().is_empty());
+		})
+}
+
+#[test]
+fn it_only_auto_dispatches_to_configured_destinations() {
+	ExtBuilder::default()
+		.with_base(*TEST_NOMAD_BASE)
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				DABridge::set_auto_dispatch_destinations(
+					RuntimeOrigin::signed(TEST_SENDER_ACCOUNT),
+					BoundedVec::truncate_from(vec![(DESTINATION_DOMAIN, AUTO_RECIPIENT)])
+				),
+				DispatchError::BadOrigin
+			);
+
+			finalize_blocks_up_to(6);
+			assert!(DABridge::pending_data_roots().is_empty());
+			assert!(events().is_empty());
+		})
+}
+
+fn dispatch_with_proof(
+	header: Header<BlockNumberFor<Test>, BlakeTwo256>,
+	proof: u64,
+) -> DispatchResultWithPostInfo {
+	DABridge::try_dispatch_data_root_with_proof(
+		RuntimeOrigin::signed(TEST_SENDER_ACCOUNT),
+		DESTINATION_DOMAIN,
+		H256::zero(),
+		Box::new(header),
+		Box::new(proof),
+	)
+}
+
+/// Adds the hash of `header` to the MMR and records the new MMR root.
+fn push_mmr_leaf(header: &Header<BlockNumberFor<Test>, BlakeTwo256>) {
+	MmrLeaves::mutate(|leaves| leaves.push((header.number, header.hash())));
+	DABridge::on_initialize(System::block_number());
+}
+
+#[test]
+fn it_dispatches_headers_proven_by_mmr() {
+	ExtBuilder::default()
+		.with_base(*TEST_NOMAD_BASE)
+		.build()
+		.execute_with(|| {
+			// Block 10 is no longer in the block hash mapping
+			let header = registered_header(10);
+			frame_system::BlockHash::<Test>::remove(10);
+			push_mmr_leaf(&header);
+			push_mmr_leaf(&registered_header(11));
+
+			let nonce_pre = Home::nonces(DESTINATION_DOMAIN);
+			assert_ok!(dispatch_with_proof(header.clone(), 1));
+
+			assert_eq!(nonce_pre + 1, Home::nonces(DESTINATION_DOMAIN));
+			assert_eq!(
+				events(),
+				vec![Event::DataRootDispatched {
+					destination_domain: DESTINATION_DOMAIN,
+					recipient_address: H256::zero(),
+					block_number: 10,
+					data_root: header.extension.data_root(),
+				}]
+			);
+		})
+}
+
+#[test]
+fn it_rejects_headers_not_proven_by_recent_mmr_roots() {
+	ExtBuilder::default()
+		.with_base(*TEST_NOMAD_BASE)
+		.build()
+		.execute_with(|| {
+			let header = registered_header(10);
+			for number in 10..=12 {
+				push_mmr_leaf(&registered_header(number));
+			}
+			assert_eq!(DABridge::mmr_root(3), Some(TestBlockHashMmr::root(3)));
+
+			// Only the last `MmrRootHistory` roots are kept
+			assert_noop!(
+				dispatch_with_proof(header.clone(), 1),
+				Error::<Test>::UnknownMmrRoot
+			);
+			assert_noop!(
+				dispatch_with_proof(header.clone(), 4),
+				Error::<Test>::UnknownMmrRoot
+			);
+
+			let mut unknown = header.clone();
+			unknown.state_root = [9u8; 32].into();
+			assert_noop!(
+				dispatch_with_proof(unknown, 3),
+				Error::<Test>::InvalidHeaderProof
+			);
+
+			assert_ok!(dispatch_with_proof(header, 2));
+		})
+}
+
+#[test]
+fn it_dispatches_undispatched_headers_proven_by_mmr_after_later_blocks() {
+	ExtBuilder::default()
+		.with_base(*TEST_NOMAD_BASE)
+		.build()
+		.execute_with(|| {
+			let header = registered_header(10);
+			frame_system::BlockHash::<Test>::remove(10);
+			push_mmr_leaf(&header);
+			push_mmr_leaf(&registered_header(11));
+			assert_ok!(DABridge::try_dispatch_data_root(
+				RuntimeOrigin::signed(TEST_SENDER_ACCOUNT),
+				DESTINATION_DOMAIN,
+				H256::zero(),
+				Box::new(registered_header(11)),
+			));
+
+			assert_ok!(dispatch_with_proof(header.clone(), 1));
+			assert_noop!(
+				dispatch_with_proof(header, 1),
+				Error::<Test>::DataRootAlreadyDispatched
+			);
+			assert_eq!(
+				DABridge::last_dispatched_block(DESTINATION_DOMAIN),
+				Some(11)
+			);
+			assert_eq!(
+				DABridge::data_root_dispatch(10, DESTINATION_DOMAIN).map(|d| d.start_block),
+				Some(10)
+			);
 		})
 }
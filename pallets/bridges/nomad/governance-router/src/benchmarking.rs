@@ -0,0 +1,58 @@
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::{assert_ok, traits::Currency};
+use frame_system::RawOrigin;
+use nomad_home::common_tests_and_benches::UPDATER_ADDRESS;
+use sp_core::H256;
+use sp_runtime::traits::Bounded;
+
+use crate::*;
+
+// Design of benchmark cases:
+// - `route` is benchmarked for each message, as they route to different pallets.
+// - `SetUpdater` sets a bonded updater, as required by the updater manager.
+
+/// Bonds `UPDATER_ADDRESS` with the funds of the whitelisted caller.
+fn bond_updater<T: Config>() {
+	type UpdaterCurrency<T> = <T as nomad_updater_manager::Config>::Currency;
+
+	let owner = whitelisted_caller::<T::AccountId>();
+	UpdaterCurrency::<T>::make_free_balance_be(
+		&owner,
+		nomad_updater_manager::BalanceOf::<T>::max_value() / 2u32.into(),
+	);
+	assert_ok!(nomad_updater_manager::Pallet::<T>::do_bond(
+		owner,
+		UPDATER_ADDRESS
+	));
+}
+
+benchmarks! {
+	route_set_updater {
+		bond_updater::<T>();
+		let message = GovernanceMessage::SetUpdater { updater: UPDATER_ADDRESS };
+	}: route(RawOrigin::Root, message)
+	verify {
+		assert_eq!(nomad_home::Pallet::<T>::base().updater, UPDATER_ADDRESS);
+	}
+
+	route_set_local_domain {
+		let message = GovernanceMessage::SetLocalDomain { domain: 3000 };
+	}: route(RawOrigin::Root, message)
+	verify {
+		assert_eq!(nomad_home::Pallet::<T>::base().local_domain, 3000);
+	}
+
+	route_enroll_router {
+		let router = H256::repeat_byte(1);
+		let message = GovernanceMessage::EnrollRouter { domain: 1000, router };
+	}: route(RawOrigin::Root, message)
+	verify {
+		assert_eq!(Routers::<T>::get(1000), Some(router));
+	}
+
+	set_governor_domain {
+	}: _(RawOrigin::Root, Some(1000))
+	verify {
+		assert_eq!(GovernorDomain::<T>::get(), Some(1000));
+	}
+}
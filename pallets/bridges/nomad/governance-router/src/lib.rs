@@ -0,0 +1,198 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Governance router of the Nomad bridge, which routes governance messages to the Nomad
+//! pallets.
+//!
+//! Messages are routed either from the local `GovernanceOrigin`, or from the Nomad messages
+//! sent to `RouterAddress` by the router enrolled for the governor domain, which makes the
+//! governance of the bridge possible from a remote chain.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod message;
+pub mod placeholder_weights;
+
+pub use message::GovernanceMessage;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{pallet_prelude::*, DefaultNoBound};
+	use frame_system::pallet_prelude::*;
+	use nomad_replica::HandleMessage;
+	use sp_core::H256;
+	use sp_std::vec::Vec;
+
+	use super::{message::GovernanceMessage, placeholder_weights};
+
+	/// Default implementations of [`DefaultConfig`], which can be used to implement [`Config`].
+	pub mod config_preludes {
+		use super::DefaultConfig;
+
+		/// Provides a viable default config that can be used with
+		/// [`derive_impl`](`frame_support::derive_impl`) to derive a testing pallet config
+		/// based on this one.
+		pub struct TestDefaultConfig;
+
+		#[frame_support::register_default_impl(TestDefaultConfig)]
+		impl DefaultConfig for TestDefaultConfig {
+			type RouterAddress = ();
+		}
+	}
+
+	#[pallet::config(with_default)]
+	pub trait Config: frame_system::Config + nomad_home::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Origin of the local governance.
+		#[pallet::no_default]
+		type GovernanceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Recipient of the governance messages of remote domains.
+		#[pallet::constant]
+		type RouterAddress: Get<H256>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	// Domain whose router is allowed to send governance messages.
+	#[pallet::storage]
+	#[pallet::getter(fn governor_domain)]
+	pub type GovernorDomain<T> = StorageValue<_, u32>;
+
+	// Governance router of each remote domain.
+	#[pallet::storage]
+	#[pallet::getter(fn router)]
+	pub type Routers<T> = StorageMap<_, Twox64Concat, u32, H256>;
+
+	// Genesis config
+	#[pallet::genesis_config]
+	#[derive(DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		pub governor_domain: Option<u32>,
+		pub routers: Vec<(u32, H256)>,
+		pub _phantom: PhantomData<T>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			GovernorDomain::<T>::set(self.governor_domain);
+			for (domain, router) in &self.routers {
+				Routers::<T>::insert(domain, router);
+			}
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub (super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A governance message was routed. `source` is the domain of its router, or `None`
+		/// for the local governance.
+		Routed {
+			source: Option<u32>,
+			message: GovernanceMessage,
+		},
+		GovernorDomainSet {
+			domain: Option<u32>,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The sender is not the router of the governor domain.
+		NotGovernorRouter,
+		InvalidMessage,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Route a governance message of the local governance.
+		#[pallet::call_index(0)]
+		#[pallet::weight(Pallet::<T>::route_weight(message))]
+		pub fn route(origin: OriginFor<T>, message: GovernanceMessage) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			Self::do_route(None, message)
+		}
+
+		/// Set the domain whose router is allowed to send governance messages, or disallow
+		/// remote governance if it is `None`.
+		#[pallet::call_index(1)]
+		#[pallet::weight(placeholder_weights::set_governor_domain(T::DbWeight::get()))]
+		pub fn set_governor_domain(origin: OriginFor<T>, domain: Option<u32>) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+
+			GovernorDomain::<T>::set(domain);
+			Self::deposit_event(Event::<T>::GovernorDomainSet { domain });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		fn do_route(source: Option<u32>, message: GovernanceMessage) -> DispatchResult {
+			match message {
+				GovernanceMessage::SetUpdater { updater } => {
+					nomad_home::Pallet::<T>::do_set_updater(updater)?
+				},
+				GovernanceMessage::SetLocalDomain { domain } => {
+					nomad_home::Pallet::<T>::set_local_domain(domain)?
+				},
+				GovernanceMessage::EnrollRouter { domain, router } => {
+					if router.is_zero() {
+						Routers::<T>::remove(domain);
+					} else {
+						Routers::<T>::insert(domain, router);
+					}
+				},
+			}
+
+			Self::deposit_event(Event::<T>::Routed { source, message });
+			Ok(())
+		}
+
+		/// Weight of routing `message`.
+		pub fn route_weight(message: &GovernanceMessage) -> Weight {
+			match message {
+				GovernanceMessage::SetUpdater { .. } => {
+					placeholder_weights::route_set_updater(T::DbWeight::get())
+				},
+				GovernanceMessage::SetLocalDomain { .. } => {
+					placeholder_weights::route_set_local_domain(T::DbWeight::get())
+				},
+				GovernanceMessage::EnrollRouter { .. } => {
+					placeholder_weights::route_enroll_router(T::DbWeight::get())
+				},
+			}
+		}
+	}
+
+	/// Handles the governance messages sent to `RouterAddress`, which must come from the router
+	/// of the governor domain.
+	impl<T: Config> HandleMessage for Pallet<T> {
+		fn handle(
+			origin: u32,
+			_nonce: u32,
+			sender: H256,
+			recipient: H256,
+			body: &[u8],
+		) -> Option<DispatchResult> {
+			(recipient == T::RouterAddress::get()).then(|| {
+				ensure!(
+					Self::governor_domain() == Some(origin) && Self::router(origin) == Some(sender),
+					Error::<T>::NotGovernorRouter
+				);
+				let message =
+					GovernanceMessage::from_bytes(body).ok_or(Error::<T>::InvalidMessage)?;
+				Self::do_route(Some(origin), message)
+			})
+		}
+	}
+}
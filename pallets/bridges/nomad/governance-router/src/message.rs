@@ -0,0 +1,106 @@
+use core::convert::TryInto;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::RuntimeDebug;
+use scale_info::TypeInfo;
+use sp_core::{H160, H256};
+use sp_std::vec::Vec;
+
+/// Type tag of a `SetUpdater` message.
+pub const SET_UPDATER_TAG: u8 = 1;
+/// Type tag of a `SetLocalDomain` message.
+pub const SET_LOCAL_DOMAIN_TAG: u8 = 2;
+/// Type tag of an `EnrollRouter` message.
+pub const ENROLL_ROUTER_TAG: u8 = 3;
+
+/// Governance message routed to the Nomad pallets.
+///
+/// As body of a Nomad message, it is formatted as a 1-byte type tag followed by its fields,
+/// packed and big-endian.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub enum GovernanceMessage {
+	/// Set the updater of the home and of the updater manager.
+	SetUpdater { updater: H160 },
+	/// Set the local domain of the home.
+	SetLocalDomain { domain: u32 },
+	/// Enroll `router` as the governance router of `domain`, or unenroll it if it is zero.
+	EnrollRouter { domain: u32, router: H256 },
+}
+
+impl GovernanceMessage {
+	/// Formats the message as the body of a Nomad message.
+	pub fn to_vec(&self) -> Vec<u8> {
+		let mut bytes = Vec::new();
+		match self {
+			Self::SetUpdater { updater } => {
+				bytes.push(SET_UPDATER_TAG);
+				bytes.extend_from_slice(updater.as_bytes());
+			},
+			Self::SetLocalDomain { domain } => {
+				bytes.push(SET_LOCAL_DOMAIN_TAG);
+				bytes.extend_from_slice(&domain.to_be_bytes());
+			},
+			Self::EnrollRouter { domain, router } => {
+				bytes.push(ENROLL_ROUTER_TAG);
+				bytes.extend_from_slice(&domain.to_be_bytes());
+				bytes.extend_from_slice(router.as_bytes());
+			},
+		}
+		bytes
+	}
+
+	/// Decodes a message formatted by `to_vec`.
+	///
+	/// Returns `None` if the type tag is unknown, or if the length does not match its type.
+	pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+		let (tag, fields) = bytes.split_first()?;
+		let u32_at = |at: usize| fields[at..at + 4].try_into().ok().map(u32::from_be_bytes);
+
+		match (*tag, fields.len()) {
+			(SET_UPDATER_TAG, 20) => Some(Self::SetUpdater {
+				updater: H160::from_slice(fields),
+			}),
+			(SET_LOCAL_DOMAIN_TAG, 4) => Some(Self::SetLocalDomain { domain: u32_at(0)? }),
+			(ENROLL_ROUTER_TAG, 36) => Some(Self::EnrollRouter {
+				domain: u32_at(0)?,
+				router: H256::from_slice(&fields[4..]),
+			}),
+			_ => None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn decodes_formatted_messages() {
+		let messages = [
+			GovernanceMessage::SetUpdater {
+				updater: H160::repeat_byte(1),
+			},
+			GovernanceMessage::SetLocalDomain { domain: 2000 },
+			GovernanceMessage::EnrollRouter {
+				domain: 1000,
+				router: H256::repeat_byte(2),
+			},
+		];
+		for message in messages {
+			let bytes = message.to_vec();
+			assert_eq!(GovernanceMessage::from_bytes(&bytes), Some(message));
+
+			// Truncated and extended messages.
+			assert!(GovernanceMessage::from_bytes(&bytes[..bytes.len() - 1]).is_none());
+			assert!(GovernanceMessage::from_bytes(&[&bytes[..], &[0u8]].concat()).is_none());
+		}
+
+		assert_eq!(
+			GovernanceMessage::SetLocalDomain { domain: 2000 }.to_vec(),
+			[2, 0, 0, 0x07, 0xd0]
+		);
+		// Unknown type tag, and empty message.
+		assert!(GovernanceMessage::from_bytes(&[4, 0, 0, 0x07, 0xd0]).is_none());
+		assert!(GovernanceMessage::from_bytes(&[]).is_none());
+	}
+}
@@ -0,0 +1,145 @@
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{ConstU32, ConstU64},
+	PalletId,
+};
+use frame_system::{self as system, header_builder::da, test_utils::TestRandomness, EnsureRoot};
+use nomad_base::testing::*;
+use sp_core::H256;
+use sp_runtime::{traits::IdentityLookup, AccountId32, BuildStorage, Perbill};
+
+use crate as governance_router;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockDaBlock<Test>;
+
+pub(crate) const GOVERNOR_DOMAIN: u32 = 1000;
+pub(crate) const GOVERNOR_ROUTER: H256 = H256::repeat_byte(1);
+pub(crate) const ROUTER_ADDRESS: H256 = H256::repeat_byte(2);
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub struct Test {
+		System: frame_system,
+		Balances: pallet_balances,
+		Home: nomad_home,
+		UpdaterManager: nomad_updater_manager,
+		GovernanceRouter: governance_router,
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u32 = 250;
+	pub const SlashRewardShare: Perbill = Perbill::from_percent(10);
+	pub const RouterAddress: H256 = ROUTER_ADDRESS;
+	pub const HomePalletId: PalletId = PalletId(*b"nmd/home");
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl system::Config for Test {
+	type AccountData = pallet_balances::AccountData<u64>;
+	type AccountId = AccountId32;
+	type BaseCallFilter = frame_support::traits::Everything;
+	type Block = Block;
+	type BlockHashCount = BlockHashCount;
+	type HeaderExtensionBuilder = da::HeaderExtensionBuilder<Test>;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type OnSetCode = ();
+	type PalletInfo = PalletInfo;
+	type Randomness = TestRandomness<Test>;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeOrigin = RuntimeOrigin;
+	type SubmittedDataExtractor = ();
+	type UncheckedExtrinsic = UncheckedExtrinsic;
+}
+
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
+	type Balance = u64;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type MaxHolds = ();
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeHoldReason = ();
+	type WeightInfo = ();
+}
+
+#[derive_impl(nomad_home::config_preludes::TestDefaultConfig as nomad_home::DefaultConfig)]
+impl nomad_home::Config for Test {
+	type PalletId = HomePalletId;
+	type RuntimeEvent = RuntimeEvent;
+}
+
+impl nomad_updater_manager::Config for Test {
+	type Currency = Balances;
+	type RuntimeEvent = RuntimeEvent;
+	type SlashRewardShare = SlashRewardShare;
+	type Slashed = ();
+	type UnbondingDelay = ConstU32<10>;
+	type UpdaterBond = ConstU64<1_000>;
+}
+
+#[derive_impl(governance_router::config_preludes::TestDefaultConfig as governance_router::DefaultConfig)]
+impl governance_router::Config for Test {
+	type GovernanceOrigin = EnsureRoot<AccountId32>;
+	type RouterAddress = RouterAddress;
+	type RuntimeEvent = RuntimeEvent;
+}
+
+#[derive(Default)]
+pub(crate) struct ExtBuilder;
+
+impl ExtBuilder {
+	pub(crate) fn build(self) -> sp_io::TestExternalities {
+		let mut t = RuntimeGenesisConfig::default()
+			.system
+			.build_storage()
+			.expect("Genesis build should work");
+
+		nomad_home::GenesisConfig::<Test> {
+			updater: TEST_UPDATER.address(),
+			local_domain: TEST_LOCAL_DOMAIN,
+			committed_root: H256::zero(),
+			..Default::default()
+		}
+		.assimilate_storage(&mut t)
+		.expect("Nomad base storage cannot be assimilated");
+		nomad_updater_manager::GenesisConfig::<Test> {
+			updater: TEST_UPDATER.address(),
+			_phantom: Default::default(),
+		}
+		.assimilate_storage(&mut t)
+		.expect("Updater manager storage cannot be assimilated");
+		governance_router::GenesisConfig::<Test> {
+			governor_domain: Some(GOVERNOR_DOMAIN),
+			routers: vec![(GOVERNOR_DOMAIN, GOVERNOR_ROUTER)],
+			_phantom: Default::default(),
+		}
+		.assimilate_storage(&mut t)
+		.expect("Governance router storage cannot be assimilated");
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
+
+pub(crate) fn events() -> Vec<super::Event<Test>> {
+	System::events()
+		.into_iter()
+		.map(|r| r.event)
+		.filter_map(|e| {
+			if let RuntimeEvent::GovernanceRouter(inner) = e {
+				Some(inner)
+			} else {
+				None
+			}
+		})
+		.collect::<Vec<_>>()
+}
@@ -0,0 +1,29 @@
+//! Placeholder weights for `nomad_governance_router`.
+//!
+//! NOT GENERATED: `benchmarking.rs` routes each kind of governance message, but it has not been
+//! run yet. Until then, each weight is a hand-written estimate of the execution time plus the
+//! storage listed above it, most of which belongs to the home and the updater manager that the
+//! message changes.
+
+use frame_support::weights::{RuntimeDbWeight, Weight};
+
+// NomadHome::Base (r:1 w:1), NomadUpdaterManager::Updater (r:1 w:1),
+// NomadUpdaterManager::Bonds (r:1)
+pub fn route_set_updater(db: RuntimeDbWeight) -> Weight {
+	Weight::from_parts(40_000_000, 3554).saturating_add(db.reads_writes(3, 2))
+}
+
+// NomadHome::Tree (r:1), NomadHome::Base (r:1 w:1)
+pub fn route_set_local_domain(db: RuntimeDbWeight) -> Weight {
+	Weight::from_parts(18_000_000, 3065).saturating_add(db.reads_writes(2, 1))
+}
+
+// Routers (w:1)
+pub fn route_enroll_router(db: RuntimeDbWeight) -> Weight {
+	Weight::from_parts(12_000_000, 0).saturating_add(db.writes(1))
+}
+
+// GovernorDomain (w:1)
+pub fn set_governor_domain(db: RuntimeDbWeight) -> Weight {
+	Weight::from_parts(10_000_000, 0).saturating_add(db.writes(1))
+}
@@ -0,0 +1,166 @@
+use frame_support::{assert_noop, assert_ok, traits::Currency};
+use nomad_base::testing::*;
+use nomad_replica::HandleMessage;
+use sp_core::{H160, H256};
+use sp_runtime::{AccountId32, DispatchError, DispatchResult};
+
+use crate::{mock::*, Error, GovernanceMessage};
+
+fn bond(updater: H160) {
+	let bonder = AccountId32::new([5u8; 32]);
+	let _ = Balances::deposit_creating(&bonder, 10_000);
+	assert_ok!(UpdaterManager::do_bond(bonder, updater));
+}
+
+/// Handles `message` as sent by `sender` of `origin` domain to the router.
+fn handle(origin: u32, sender: H256, message: &[u8]) -> Option<DispatchResult> {
+	GovernanceRouter::handle(origin, 0, sender, ROUTER_ADDRESS, message)
+}
+
+#[test]
+fn it_routes_local_governance_messages() {
+	ExtBuilder::default().build().execute_with(|| {
+		let set_updater = GovernanceMessage::SetUpdater {
+			updater: FAKE_UPDATER.address(),
+		};
+		assert_noop!(
+			GovernanceRouter::route(
+				RuntimeOrigin::signed(AccountId32::new([1u8; 32])),
+				set_updater.clone()
+			),
+			DispatchError::BadOrigin
+		);
+
+		// The new updater must be bonded.
+		assert_noop!(
+			GovernanceRouter::route(RuntimeOrigin::root(), set_updater.clone()),
+			nomad_updater_manager::Error::<Test>::UpdaterNotBonded
+		);
+		bond(FAKE_UPDATER.address());
+		assert_ok!(GovernanceRouter::route(
+			RuntimeOrigin::root(),
+			set_updater.clone()
+		));
+		assert_eq!(Home::base().updater, FAKE_UPDATER.address());
+		assert_eq!(UpdaterManager::get_updater(), FAKE_UPDATER.address());
+
+		let set_local_domain = GovernanceMessage::SetLocalDomain { domain: 3000 };
+		assert_ok!(GovernanceRouter::route(
+			RuntimeOrigin::root(),
+			set_local_domain.clone()
+		));
+		assert_eq!(Home::base().local_domain, 3000);
+
+		// Zero routers are unenrolled.
+		let enroll_router = GovernanceMessage::EnrollRouter {
+			domain: 4000,
+			router: H256::repeat_byte(4),
+		};
+		assert_ok!(GovernanceRouter::route(
+			RuntimeOrigin::root(),
+			enroll_router.clone()
+		));
+		assert_eq!(GovernanceRouter::router(4000), Some(H256::repeat_byte(4)));
+		assert_ok!(GovernanceRouter::route(
+			RuntimeOrigin::root(),
+			GovernanceMessage::EnrollRouter {
+				domain: 4000,
+				router: H256::zero(),
+			}
+		));
+		assert_eq!(GovernanceRouter::router(4000), None);
+
+		let expected = [set_updater, set_local_domain, enroll_router]
+			.into_iter()
+			.map(|message| crate::Event::Routed {
+				source: None,
+				message,
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(events()[..3], expected);
+
+		// The local domain cannot change once messages were dispatched.
+		assert_ok!(Home::enroll_destination(RuntimeOrigin::root(), 4000));
+		assert_ok!(Home::do_dispatch(
+			H256::zero(),
+			4000,
+			H256::zero(),
+			b"body".to_vec().try_into().unwrap()
+		));
+		assert_noop!(
+			GovernanceRouter::route(
+				RuntimeOrigin::root(),
+				GovernanceMessage::SetLocalDomain { domain: 5000 }
+			),
+			nomad_home::Error::<Test>::MessagesDispatched
+		);
+		assert_eq!(Home::base().local_domain, 3000);
+	})
+}
+
+#[test]
+fn it_handles_messages_of_governor_router() {
+	ExtBuilder::default().build().execute_with(|| {
+		let message = GovernanceMessage::SetLocalDomain { domain: 3000 };
+		let body = message.to_vec();
+
+		// Messages to other recipients are not handled.
+		assert_eq!(
+			GovernanceRouter::handle(GOVERNOR_DOMAIN, 0, GOVERNOR_ROUTER, H256::zero(), &body),
+			None
+		);
+
+		// Only the router of the governor domain is allowed.
+		let not_governor_router = Some(Err(Error::<Test>::NotGovernorRouter.into()));
+		assert_eq!(
+			handle(GOVERNOR_DOMAIN, H256::repeat_byte(4), &body),
+			not_governor_router
+		);
+		assert_ok!(GovernanceRouter::route(
+			RuntimeOrigin::root(),
+			GovernanceMessage::EnrollRouter {
+				domain: 4000,
+				router: GOVERNOR_ROUTER,
+			}
+		));
+		assert_eq!(handle(4000, GOVERNOR_ROUTER, &body), not_governor_router);
+		assert_eq!(
+			handle(GOVERNOR_DOMAIN, GOVERNOR_ROUTER, &body[1..]),
+			Some(Err(Error::<Test>::InvalidMessage.into()))
+		);
+
+		assert_eq!(
+			handle(GOVERNOR_DOMAIN, GOVERNOR_ROUTER, &body),
+			Some(Ok(()))
+		);
+		assert_eq!(Home::base().local_domain, 3000);
+		assert_eq!(
+			events().last(),
+			Some(&crate::Event::Routed {
+				source: Some(GOVERNOR_DOMAIN),
+				message,
+			})
+		);
+
+		// Remote governance is disallowed without governor domain.
+		assert_noop!(
+			GovernanceRouter::set_governor_domain(
+				RuntimeOrigin::signed(AccountId32::new([1u8; 32])),
+				None
+			),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(GovernanceRouter::set_governor_domain(
+			RuntimeOrigin::root(),
+			None
+		));
+		assert_eq!(
+			handle(GOVERNOR_DOMAIN, GOVERNOR_ROUTER, &body),
+			not_governor_router
+		);
+		assert_eq!(
+			events().last(),
+			Some(&crate::Event::GovernorDomainSet { domain: None })
+		);
+	})
+}
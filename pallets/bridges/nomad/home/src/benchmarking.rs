@@ -1,31 +1,42 @@
 #[cfg(feature = "runtime-benchmarks")]
 use frame_benchmarking::{benchmarks, whitelisted_caller};
 #[cfg(feature = "runtime-benchmarks")]
-use frame_support::{assert_ok, traits::Get as _, BoundedVec};
+use frame_support::{
+	assert_ok,
+	traits::{Currency, Get as _, Hooks},
+	weights::Weight,
+	BoundedVec,
+};
 #[cfg(feature = "runtime-benchmarks")]
 use frame_system::RawOrigin;
+#[cfg(feature = "runtime-benchmarks")]
 use hex_literal::hex;
 #[cfg(feature = "runtime-benchmarks")]
 use nomad_core::NomadState;
-use nomad_core::{SignedUpdate, Update};
 #[cfg(feature = "runtime-benchmarks")]
 use nomad_merkle::Merkle;
-use nomad_signature::Signature;
-use sp_core::{H160, H256, U256};
+#[cfg(feature = "runtime-benchmarks")]
+use sp_core::{H160, H256};
+#[cfg(feature = "runtime-benchmarks")]
+use sp_runtime::traits::{Bounded, Zero};
 #[cfg(feature = "runtime-benchmarks")]
 use sp_std::{iter::repeat, vec::Vec};
 
+use crate::common_tests_and_benches::{expected_signed_update, UPDATER_ADDRESS};
 #[cfg(feature = "runtime-benchmarks")]
 use crate::*;
 
 const ID: u32 = 1111;
-const UPDATER_ADDRESS: H160 = H160(hex!("19e7e376e7c213b7e7e7e46cc70a5dd086daff2a"));
 
 // Design of benchmark cases:
-// - `improper_update`. The worst case is when updater is slashed and the state change to
-// `NomdState::Failed`.
-// - `dispatch` cost is affected by the length of the message.
-// - `update`. The worst case is when `Tree` is full, having `TREE_DEPTH` as max index.
+// - `improper_update`. The worst case is when the bond of the updater is slashed and the state
+// change to `NomdState::Failed`.
+// - `dispatch` cost is affected by the length of the message. The worst case is when the fee
+// creates the relayer reward pot.
+// - `update` does not depend on the number of committed messages, whose mappings are pruned
+// later by `on_idle`.
+// - `prune_mappings`. The cost is linear with the number `n` of pruned mappings.
+// - `recover`. The worst case is when all the `n` messages are pending and pruned.
 
 #[cfg(feature = "runtime-benchmarks")]
 benchmarks! {
@@ -33,6 +44,7 @@ benchmarks! {
 
 	improper_update {
 		let _ = init_tree::<T>(0, 0);
+		bond_updater::<T>(UPDATER_ADDRESS);
 
 		let origin = RawOrigin::Signed(whitelisted_caller::<T::AccountId>());
 		let signed_update = expected_signed_update();
@@ -45,8 +57,11 @@ benchmarks! {
 		let b in 1.. T::MaxMessageBodyBytes::get();
 
 		let _ = init_tree::<T>(0, 0);
+		let caller = whitelisted_caller::<T::AccountId>();
+		fund::<T>(&caller);
+		ByteFee::<T>::put(CurrencyOf::<T>::minimum_balance());
 
-		let origin = RawOrigin::Signed(whitelisted_caller::<T::AccountId>());
+		let origin = RawOrigin::Signed(caller);
 		let recipient_address = H256::zero();
 		let body = random_message::<T>(b);
 
@@ -55,6 +70,7 @@ benchmarks! {
 	verify {
 		let new_nonce = Nonces::<T>::get(ID);
 		assert_eq!( prev_nonce +1, new_nonce);
+		assert!(!CurrencyOf::<T>::free_balance(&Pallet::<T>::relayer_pot()).is_zero());
 	}
 
 	update {
@@ -73,10 +89,24 @@ benchmarks! {
 		assert_eq!( Base::<T>::get().committed_root, new_root);
 	}
 
+	prune_mappings {
+		let n in 0 .. 1_000;
+
+		let _ = init_tree::<T>(n, 0);
+		CommittedCount::<T>::put(n);
+	}: {
+		Pallet::<T>::on_idle(Zero::zero(), Weight::MAX);
+	}
+	verify {
+		assert_eq!(PruneCursor::<T>::get(), n);
+		assert_eq!(IndexToRoot::<T>::iter().count(), 0);
+	}
+
 	set_updater {
 		let _ = init_tree::<T>(0, 0);
 
 		let new_updater: H160 = H160(hex!("39dD11C243Ac4Ac250980FA3AEa016f73C509f37"));
+		bond_updater::<T>(new_updater);
 		let origin = RawOrigin::Root;
 
 	}: _(origin, new_updater)
@@ -84,6 +114,45 @@ benchmarks! {
 		//  check new updater
 		assert_eq!(Base::<T>::get().updater, new_updater);
 	}
+	recover {
+		let n in 1 .. 1_000;
+
+		let committed_root = H256([0u8; 32]);
+		let _ = init_tree::<T>(n, 0);
+		Base::<T>::mutate(|base| base.state = NomadState::Failed);
+
+		let new_updater: H160 = H160(hex!("39dD11C243Ac4Ac250980FA3AEa016f73C509f37"));
+		bond_updater::<T>(new_updater);
+		let origin = RawOrigin::Root;
+
+	}: _(origin, new_updater, committed_root, PendingMessages::Prune, n)
+	verify {
+		assert!(Base::<T>::get().state == NomadState::Active);
+		assert_eq!(Tree::<T>::get().count(), 0);
+	}
+
+	enroll_destination {
+		let origin = RawOrigin::Root;
+	}: _(origin, ID + 1)
+	verify {
+		assert!(EnrolledDestinations::<T>::get(ID + 1));
+	}
+
+	unenroll_destination {
+		EnrolledDestinations::<T>::insert(ID, true);
+		let origin = RawOrigin::Root;
+	}: _(origin, ID)
+	verify {
+		assert!(!EnrolledDestinations::<T>::get(ID));
+	}
+
+	set_byte_fee {
+		let fee = CurrencyOf::<T>::minimum_balance();
+		let origin = RawOrigin::Root;
+	}: _(origin, fee)
+	verify {
+		assert_eq!(ByteFee::<T>::get(), fee);
+	}
 }
 
 #[cfg(test)]
@@ -110,27 +179,6 @@ mod tests {
 	}
 }
 
-fn expected_signed_update() -> SignedUpdate {
-	SignedUpdate {
-		update: Update {
-			home_domain: ID,
-			previous_root: H256([0u8; 32]),
-			new_root: H256([1u8; 32]),
-		},
-		signature: Signature {
-			r: U256::from_dec_str(
-				"108172166467498881923382587939104653020584515778413535909202974529351125581995",
-			)
-			.unwrap(),
-			s: U256::from_dec_str(
-				"24567415212865781861322727855523398821160274507883582359476963266144744361252",
-			)
-			.unwrap(),
-			v: 27,
-		},
-	}
-}
-
 #[cfg(feature = "runtime-benchmarks")]
 fn random_message<T: Config>(size: u32) -> BoundedVec<u8, T::MaxMessageBodyBytes> {
 	repeat(3u8)
@@ -140,6 +188,22 @@ fn random_message<T: Config>(size: u32) -> BoundedVec<u8, T::MaxMessageBodyBytes
 		.expect("`size` must be less than `T::MaxMessageBodyBytes`")
 }
 
+#[cfg(feature = "runtime-benchmarks")]
+type CurrencyOf<T> = <T as nomad_updater_manager::Config>::Currency;
+
+#[cfg(feature = "runtime-benchmarks")]
+fn fund<T: Config>(who: &T::AccountId) {
+	CurrencyOf::<T>::make_free_balance_be(who, BalanceOf::<T>::max_value() / 2u32.into());
+}
+
+/// Bonds `updater` with the funds of the whitelisted caller.
+#[cfg(feature = "runtime-benchmarks")]
+fn bond_updater<T: Config>(updater: H160) {
+	let owner = whitelisted_caller::<T::AccountId>();
+	fund::<T>(&owner);
+	assert_ok!(nomad_updater_manager::Pallet::<T>::do_bond(owner, updater));
+}
+
 #[cfg(feature = "runtime-benchmarks")]
 fn init_tree<T>(index: u32, message_size: u32) -> H256
 where
@@ -156,6 +220,7 @@ where
 		H256([0u8; 32]),
 		UPDATER_ADDRESS,
 	));
+	EnrolledDestinations::<T>::insert(ID, true);
 
 	for _ in 0..index {
 		assert_ok!(Pallet::<T>::dispatch(
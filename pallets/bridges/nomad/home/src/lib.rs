@@ -14,25 +14,40 @@ mod tests;
 #[cfg(any(test, feature = "runtime-benchmarks"))]
 mod benchmarking;
 
+pub mod migration;
+pub mod placeholder_weights;
+pub mod proof;
 pub mod weights;
 
+pub use proof::MessageProof;
 pub use weights::WeightInfo;
 
 #[frame_support::pallet]
 pub mod pallet {
 	use frame_support::{
 		pallet_prelude::{ValueQuery, *},
-		sp_runtime::ArithmeticError::Overflow,
-		transactional, DefaultNoBound,
+		sp_runtime::{
+			traits::{AccountIdConversion, Saturating, Zero},
+			ArithmeticError::Overflow,
+		},
+		traits::{Currency, ExistenceRequirement},
+		transactional, DefaultNoBound, PalletId,
 	};
 	use frame_system::pallet_prelude::{OriginFor, *};
 	use nomad_base::NomadBase;
 	use nomad_core::{destination_and_nonce, NomadMessage, NomadState, SignedUpdate};
-	use nomad_merkle::{Merkle, NomadLightMerkle};
+	use nomad_merkle::{Merkle, NomadLightMerkle, TREE_DEPTH};
 	use sp_core::{H160, H256};
 	use sp_std::vec::Vec;
 
-	use super::weights::WeightInfo;
+	use super::{
+		placeholder_weights,
+		proof::{hash_concat, zero_hash, MessageProof},
+		weights::WeightInfo,
+	};
+
+	type CurrencyOf<T> = <T as nomad_updater_manager::Config>::Currency;
+	pub type BalanceOf<T> = nomad_updater_manager::BalanceOf<T>;
 
 	/// Default implementations of [`DefaultConfig`], which can be used to implement [`Config`].
 	pub mod config_preludes {
@@ -46,6 +61,7 @@ pub mod pallet {
 		#[frame_support::register_default_impl(TestDefaultConfig)]
 		impl DefaultConfig for TestDefaultConfig {
 			type MaxMessageBodyBytes = frame_support::traits::ConstU32<2048>;
+			type OnPrunedMessages = ();
 			type WeightInfo = ();
 		}
 	}
@@ -58,13 +74,58 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxMessageBodyBytes: Get<u32>;
 
+		/// Identifier of the pot which collects the dispatch fees to reward the relayers.
+		#[pallet::constant]
+		#[pallet::no_default]
+		type PalletId: Get<PalletId>;
+
+		/// Handler of the messages pruned by `recover`.
+		type OnPrunedMessages: OnPrunedMessages;
+
 		/// Weights for this pallet.
 		type WeightInfo: WeightInfo;
 	}
 
+	/// Handler of the messages removed from the tree when the home recovers with
+	/// `PendingMessages::Prune`.
+	pub trait OnPrunedMessages {
+		/// Called with the leaf index of the first pruned message. All the messages from it
+		/// are pruned, and their leaf indices are reused by the next messages.
+		///
+		/// Returns the consumed weight.
+		fn on_pruned_messages(first_leaf_index: u32) -> Weight;
+	}
+
+	impl OnPrunedMessages for () {
+		fn on_pruned_messages(_first_leaf_index: u32) -> Weight {
+			Weight::zero()
+		}
+	}
+
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
+	/// What happens to the messages dispatched after the committed root when the home
+	/// recovers from the failed state.
+	#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+	pub enum PendingMessages {
+		/// Keep them in the tree, so the next update of the new updater commits them.
+		Replay,
+		/// Remove them from the tree. Their nonces are not reused.
+		Prune,
+	}
+
+	/// SCALE layout of `NomadLightMerkle`, whose branch is not exposed.
+	#[derive(Encode, Decode)]
+	pub(crate) struct TreeLayout {
+		/// Root of the last complete subtree of each level, where the bit of `count` is set.
+		pub branch: [H256; TREE_DEPTH],
+		pub count: u32,
+	}
+
 	// Nomad base
 	#[pallet::storage]
 	#[pallet::getter(fn base)]
@@ -90,6 +151,39 @@ pub mod pallet {
 	#[pallet::getter(fn root_to_index)]
 	pub type RootToIndex<T: Config> = StorageMap<_, Twox64Concat, H256, u32>;
 
+	// Number of messages committed by the committed root. Only the roots of the messages
+	// dispatched after them can be committed by an update.
+	#[pallet::storage]
+	#[pallet::getter(fn committed_count)]
+	pub type CommittedCount<T> = StorageValue<_, u32, ValueQuery>;
+
+	// Leaf index from which the mappings of the committed messages are not pruned yet
+	#[pallet::storage]
+	#[pallet::getter(fn prune_cursor)]
+	pub type PruneCursor<T> = StorageValue<_, u32, ValueQuery>;
+
+	// Level and index at that level to the root of each complete subtree of the tree. Level 0
+	// holds the message hashes. Nodes of the messages dispatched before v3 are missing, except
+	// the ones of the frontier of the tree at that time.
+	#[pallet::storage]
+	pub type Nodes<T: Config> = StorageDoubleMap<_, Twox64Concat, u32, Twox64Concat, u32, H256>;
+
+	// Destination domains which messages can be dispatched to
+	#[pallet::storage]
+	#[pallet::getter(fn is_enrolled_destination)]
+	pub type EnrolledDestinations<T> = StorageMap<_, Twox64Concat, u32, bool, ValueQuery>;
+
+	// Fee per byte of message body paid to dispatch a message
+	#[pallet::storage]
+	#[pallet::getter(fn byte_fee)]
+	pub type ByteFee<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	// Rewards of the relayers, which they withdraw from the relayer reward pot
+	#[pallet::storage]
+	#[pallet::getter(fn relayer_reward)]
+	pub type RelayerRewards<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
 	// Genesis config
 	#[pallet::genesis_config]
 	#[derive(DefaultNoBound)]
@@ -97,6 +191,8 @@ pub mod pallet {
 		pub local_domain: u32,
 		pub committed_root: H256,
 		pub updater: H160,
+		pub destinations: Vec<u32>,
+		pub byte_fee: BalanceOf<T>,
 		pub _phantom: PhantomData<T>,
 	}
 
@@ -109,6 +205,10 @@ pub mod pallet {
 				self.updater,
 			));
 			<Tree<T>>::put(NomadLightMerkle::default());
+			for domain in &self.destinations {
+				<EnrolledDestinations<T>>::insert(domain, true);
+			}
+			<ByteFee<T>>::put(self.byte_fee);
 		}
 	}
 
@@ -137,6 +237,33 @@ pub mod pallet {
 			updater: H160,
 			reporter: T::AccountId,
 		},
+		Recovered {
+			committed_root: H256,
+			updater: H160,
+			replayed_messages: u32,
+			pruned_messages: u32,
+		},
+		DestinationEnrolled {
+			domain: u32,
+		},
+		DestinationUnenrolled {
+			domain: u32,
+		},
+		ByteFeeSet {
+			fee: BalanceOf<T>,
+		},
+		RelayerPotFunded {
+			who: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		RelayerRewarded {
+			relayer: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		RewardWithdrawn {
+			relayer: T::AccountId,
+			amount: BalanceOf<T>,
+		},
 	}
 
 	#[pallet::error]
@@ -151,6 +278,26 @@ pub mod pallet {
 		IndexForRootNotFound,
 		FailedState,
 		MaxIndexWitnessExhausted,
+		NotFailedState,
+		UnknownRecoveryRoot,
+		FrontierNotFound,
+		DestinationNotEnrolled,
+		MessagesDispatched,
+		NoReward,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Prune the mappings of the committed messages with the remaining weight of the block.
+		fn on_idle(_now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::prune_committed_mappings(remaining_weight)
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			Self::migrate_to_v1()
+				.saturating_add(Self::migrate_to_v2())
+				.saturating_add(Self::migrate_to_v3())
+		}
 	}
 
 	#[pallet::call]
@@ -159,38 +306,56 @@ pub mod pallet {
 		[u8; 32]: From<T::AccountId>,
 	{
 		/// Dispatch a message to the destination domain and recipient address.
+		///
+		/// The destination domain must be enrolled. The sender pays `ByteFee` per byte of the
+		/// message body to the relayer reward pot.
 		#[pallet::call_index(0)]
-		#[pallet::weight(T::WeightInfo::dispatch(message_body.len() as u32))]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::dispatch(message_body.len() as u32)
+				.saturating_add(placeholder_weights::dispatch_extra(T::DbWeight::get()))
+		)]
 		pub fn dispatch(
 			origin: OriginFor<T>,
 			#[pallet::compact] destination_domain: u32,
 			recipient_address: H256,
 			message_body: BoundedVec<u8, T::MaxMessageBodyBytes>,
 		) -> DispatchResult {
-			let sender: [u8; 32] = ensure_signed(origin)?.into();
+			let who = ensure_signed(origin)?;
+			Self::charge_dispatch_fee(&who, message_body.len() as u32)?;
+
+			let sender: [u8; 32] = who.into();
 			Self::do_dispatch(
 				sender.into(),
 				destination_domain,
 				recipient_address,
 				message_body,
-			)
+			)?;
+			Ok(())
 		}
 
 		/// Verify/submit signed update.
+		///
+		/// Deprecated: `max_index` is ignored, use `submit_update` instead.
 		#[pallet::call_index(1)]
-		#[pallet::weight(T::WeightInfo::update())]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::update()
+				.saturating_add(placeholder_weights::slash_extra(T::DbWeight::get()))
+		)]
 		pub fn update(
 			origin: OriginFor<T>,
 			signed_update: SignedUpdate,
-			#[pallet::compact] max_index: u32,
+			#[pallet::compact] _max_index: u32,
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
-			Self::do_update(sender, signed_update, max_index)
+			Self::do_update(sender, signed_update)
 		}
 
 		/// Verify/slash updater for improper update.
 		#[pallet::call_index(2)]
-		#[pallet::weight(T::WeightInfo::improper_update())]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::improper_update()
+				.saturating_add(placeholder_weights::slash_extra(T::DbWeight::get()))
+		)]
 		pub fn improper_update(
 			origin: OriginFor<T>,
 			signed_update: SignedUpdate,
@@ -201,20 +366,147 @@ pub mod pallet {
 		}
 
 		/// Set new updater on self as well as updater manager.
-		/// Note: Not exposed as pallet call, will only be callable by the
-		/// GovernanceRouter pallet when implemented.
+		/// Note: Governance usually sets it through the governance router.
+		///
+		/// A failed home stays failed, see `recover`.
 		#[pallet::call_index(3)]
-		#[pallet::weight(T::WeightInfo::set_updater())]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::set_updater()
+				.saturating_add(placeholder_weights::set_updater_extra(T::DbWeight::get()))
+		)]
 		pub fn set_updater(origin: OriginFor<T>, new_updater: H160) -> DispatchResult {
 			ensure_root(origin)?;
+			Self::do_set_updater(new_updater)
+		}
 
-			// Modify NomadBase updater
-			Base::<T>::mutate(|base| base.updater = new_updater);
+		/// Recover from the failed state with a new updater.
+		///
+		/// `committed_root` becomes the committed root. It must be the current committed root
+		/// or the root of a message dispatched after it. The messages dispatched after
+		/// `committed_root` are replayed or pruned, depending on `pending`.
+		///
+		/// `max_index` bounds the number of pruned messages. The tree is rebuilt from the roots
+		/// of its complete subtrees at `committed_root`, which fails with `FrontierNotFound` if
+		/// they were dispatched before v3 and are not part of its frontier at that time.
+		#[pallet::call_index(4)]
+		#[pallet::weight(placeholder_weights::recover(T::DbWeight::get(), *max_index))]
+		pub fn recover(
+			origin: OriginFor<T>,
+			new_updater: H160,
+			committed_root: H256,
+			pending: PendingMessages,
+			#[pallet::compact] max_index: u32,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			Self::do_recover(new_updater, committed_root, pending, max_index)
+		}
 
-			// update nomad state to active
-			Base::<T>::mutate(|base| base.state = NomadState::Active);
-			// Rotate updater on updater manager
-			nomad_updater_manager::Pallet::<T>::set_updater(new_updater)
+		/// Verify/submit signed update.
+		///
+		/// The mappings of the committed messages are pruned later, in `on_idle`, so the weight
+		/// does not depend on the number of committed messages.
+		#[pallet::call_index(5)]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::update()
+				.saturating_add(placeholder_weights::slash_extra(T::DbWeight::get()))
+		)]
+		pub fn submit_update(origin: OriginFor<T>, signed_update: SignedUpdate) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::do_update(sender, signed_update)
+		}
+
+		/// Allow dispatching messages to the `domain`.
+		#[pallet::call_index(6)]
+		#[pallet::weight(placeholder_weights::enroll_destination(T::DbWeight::get()))]
+		pub fn enroll_destination(
+			origin: OriginFor<T>,
+			#[pallet::compact] domain: u32,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			EnrolledDestinations::<T>::insert(domain, true);
+			Self::deposit_event(Event::<T>::DestinationEnrolled { domain });
+			Ok(())
+		}
+
+		/// Stop dispatching messages to the `domain`. The messages already dispatched to it are
+		/// kept in the tree.
+		#[pallet::call_index(7)]
+		#[pallet::weight(placeholder_weights::unenroll_destination(T::DbWeight::get()))]
+		pub fn unenroll_destination(
+			origin: OriginFor<T>,
+			#[pallet::compact] domain: u32,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(
+				EnrolledDestinations::<T>::take(domain),
+				Error::<T>::DestinationNotEnrolled
+			);
+			Self::deposit_event(Event::<T>::DestinationUnenrolled { domain });
+			Ok(())
+		}
+
+		/// Set the fee per byte of message body paid by `dispatch`.
+		#[pallet::call_index(8)]
+		#[pallet::weight(placeholder_weights::set_byte_fee(T::DbWeight::get()))]
+		pub fn set_byte_fee(origin: OriginFor<T>, fee: BalanceOf<T>) -> DispatchResult {
+			ensure_root(origin)?;
+			ByteFee::<T>::put(fee);
+			Self::deposit_event(Event::<T>::ByteFeeSet { fee });
+			Ok(())
+		}
+
+		/// Transfer `amount` from the sender to the relayer reward pot.
+		///
+		/// The pot must hold the existential deposit before the dispatch fees below it can be
+		/// paid into it.
+		#[pallet::call_index(9)]
+		#[pallet::weight(placeholder_weights::fund_relayer_pot(T::DbWeight::get()))]
+		pub fn fund_relayer_pot(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			CurrencyOf::<T>::transfer(
+				&who,
+				&Self::relayer_pot(),
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+			Self::deposit_event(Event::<T>::RelayerPotFunded { who, amount });
+			Ok(())
+		}
+
+		/// Add `amount` to the reward of `relayer`, which it withdraws from the relayer reward
+		/// pot with `withdraw_reward`.
+		#[pallet::call_index(10)]
+		#[pallet::weight(placeholder_weights::reward_relayer(T::DbWeight::get()))]
+		pub fn reward_relayer(
+			origin: OriginFor<T>,
+			relayer: T::AccountId,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			RelayerRewards::<T>::mutate(&relayer, |reward| *reward = reward.saturating_add(amount));
+			Self::deposit_event(Event::<T>::RelayerRewarded { relayer, amount });
+			Ok(())
+		}
+
+		/// Transfer the reward of the sender from the relayer reward pot, which is kept alive.
+		#[pallet::call_index(11)]
+		#[pallet::weight(placeholder_weights::withdraw_reward(T::DbWeight::get()))]
+		pub fn withdraw_reward(origin: OriginFor<T>) -> DispatchResult {
+			let relayer = ensure_signed(origin)?;
+			let amount = RelayerRewards::<T>::take(&relayer);
+			ensure!(!amount.is_zero(), Error::<T>::NoReward);
+
+			CurrencyOf::<T>::transfer(
+				&Self::relayer_pot(),
+				&relayer,
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+			Self::deposit_event(Event::<T>::RewardWithdrawn { relayer, amount });
+			Ok(())
 		}
 	}
 
@@ -232,18 +524,24 @@ pub mod pallet {
 
 		/// Format message, insert hash into merkle tree, and update mappings
 		/// between tree roots and message indices.
+		///
+		/// Returns the leaf index of the message.
 		pub fn do_dispatch(
 			sender: H256,
 			destination_domain: u32,
 			recipient_address: H256,
 			message_body: BoundedVec<u8, T::MaxMessageBodyBytes>,
-		) -> DispatchResult {
+		) -> Result<u32, DispatchError> {
 			Self::ensure_not_failed()?;
+			ensure!(
+				Self::is_enrolled_destination(destination_domain),
+				Error::<T>::DestinationNotEnrolled
+			);
 			let base = Self::base();
 			let index_of = |tree: &NomadLightMerkle| tree.count() - 1;
 
 			// Get nonce and set new nonce
-			Nonces::<T>::try_mutate(destination_domain, |nonce| -> Result<(), DispatchError> {
+			Nonces::<T>::try_mutate(destination_domain, |nonce| -> Result<u32, DispatchError> {
 				let new_nonce = nonce
 					.checked_add(1)
 					.ok_or_else(|| DispatchError::from(Overflow))?;
@@ -270,59 +568,36 @@ pub mod pallet {
 						let index = index_of(tree);
 						RootToIndex::<T>::insert(root, index);
 						IndexToRoot::<T>::insert(index, root);
+						Self::insert_nodes(index, message_hash);
 
 						Ok(*tree)
 					})?;
 
+				let leaf_index = index_of(&tree);
 				Self::deposit_event(Event::<T>::Dispatch {
 					message_hash,
-					leaf_index: index_of(&tree),
+					leaf_index,
 					destination_and_nonce: destination_and_nonce(destination_domain, *nonce),
 					committed_root: base.committed_root,
 					message: message.to_vec(),
 				});
 
 				*nonce = new_nonce;
-				Ok(())
-			})?;
-
-			Ok(())
+				Ok(leaf_index)
+			})
 		}
 
-		/// Check for improper update, remove all previous root/index mappings,
-		/// and emit Update event if valid.
+		/// Check for improper update, commit the messages up to the new root, and emit Update
+		/// event if valid. Their mappings are pruned later, in `on_idle`.
 		#[transactional]
-		fn do_update(
-			sender: T::AccountId,
-			signed_update: SignedUpdate,
-			mut max_index_witness: u32,
-		) -> DispatchResult {
+		fn do_update(sender: T::AccountId, signed_update: SignedUpdate) -> DispatchResult {
 			if Self::do_improper_update(sender, &signed_update)? {
 				return Ok(());
 			}
 
-			let mut root = signed_update.new_root();
-			let previous_root = signed_update.previous_root();
-
-			// Clear previous mappings starting from new_root, going back and
-			// through previous_root. A new update's previous_root has always
-			// been cleared in the previous update, as the last update's
-			// new_root is always the next update's previous_root.
-			while root != previous_root {
-				// Ensure witness
-				ensure!(max_index_witness > 0, Error::<T>::MaxIndexWitnessExhausted);
-				max_index_witness -= 1;
-
-				// Remove `RootToIndex` & `IndexToRoot` items.
-				let index = RootToIndex::<T>::take(root).ok_or(Error::<T>::IndexForRootNotFound)?;
-				IndexToRoot::<T>::remove(index);
-
-				// Force an exit if `index ==0 ` or `index -1` is not found.
-				root = (index != 0)
-					.then(|| IndexToRoot::<T>::get(index - 1))
-					.flatten()
-					.unwrap_or(previous_root);
-			}
+			let index = Self::pending_index(signed_update.new_root())
+				.ok_or(Error::<T>::IndexForRootNotFound)?;
+			CommittedCount::<T>::put(index + 1);
 
 			Base::<T>::mutate(|base| {
 				base.set_committed_root(signed_update.new_root());
@@ -362,7 +637,7 @@ pub mod pallet {
 			);
 
 			// If new root not in history (invalid), slash updater and fail home
-			let root_not_found = RootToIndex::<T>::get(signed_update.new_root()).is_none();
+			let root_not_found = Self::pending_index(signed_update.new_root()).is_none();
 			if root_not_found {
 				Self::fail(sender);
 				Self::deposit_event(Event::<T>::ImproperUpdate {
@@ -375,6 +650,70 @@ pub mod pallet {
 			Ok(root_not_found)
 		}
 
+		/// Re-anchor the committed root, reconcile the mappings and the tree with it, and
+		/// activate the home with `new_updater`.
+		#[transactional]
+		fn do_recover(
+			new_updater: H160,
+			committed_root: H256,
+			pending: PendingMessages,
+			max_index_witness: u32,
+		) -> DispatchResult {
+			let base = Self::base();
+			ensure!(base.state == NomadState::Failed, Error::<T>::NotFailedState);
+
+			// Messages up to the new committed root are committed.
+			let count = Self::tree().count();
+			let committed = if committed_root == base.committed_root {
+				Self::committed_count()
+			} else {
+				Self::pending_index(committed_root).ok_or(Error::<T>::UnknownRecoveryRoot)? + 1
+			};
+
+			let (replayed_messages, pruned_messages) = match pending {
+				PendingMessages::Replay => (count - committed, 0),
+				PendingMessages::Prune => {
+					let pruned = count - committed;
+					ensure!(
+						pruned <= max_index_witness,
+						Error::<T>::MaxIndexWitnessExhausted
+					);
+
+					let tree =
+						Self::frontier_tree(committed).ok_or(Error::<T>::FrontierNotFound)?;
+					for index in committed..count {
+						Self::remove_mappings(index);
+						Self::remove_nodes(index);
+					}
+					Tree::<T>::put(tree);
+
+					// Their leaf indices are reused by the next messages.
+					let weight = T::OnPrunedMessages::on_pruned_messages(committed);
+					frame_system::Pallet::<T>::register_extra_weight_unchecked(
+						weight,
+						DispatchClass::Normal,
+					);
+					(0, pruned)
+				},
+			};
+
+			CommittedCount::<T>::put(committed);
+			Base::<T>::mutate(|base| {
+				base.set_committed_root(committed_root);
+				base.updater = new_updater;
+				base.state = NomadState::Active;
+			});
+			nomad_updater_manager::Pallet::<T>::set_updater(new_updater)?;
+
+			Self::deposit_event(Event::<T>::Recovered {
+				committed_root,
+				updater: new_updater,
+				replayed_messages,
+				pruned_messages,
+			});
+			Ok(())
+		}
+
 		/// Set self in failed state and slash updater.
 		fn fail(reporter: T::AccountId) {
 			Base::<T>::mutate(|base| base.state = NomadState::Failed);
@@ -384,6 +723,257 @@ pub mod pallet {
 			Self::deposit_event(Event::<T>::UpdaterSlashed { updater, reporter });
 		}
 	}
+
+	impl<T: Config> Pallet<T> {
+		/// Returns the leaf index of `root` if it is the root of a message dispatched after the
+		/// committed root.
+		fn pending_index(root: H256) -> Option<u32> {
+			Self::root_to_index(root).filter(|index| *index >= Self::committed_count())
+		}
+
+		/// Prune the mappings of the committed messages, as many as `remaining_weight` allows.
+		fn prune_committed_mappings(remaining_weight: Weight) -> Weight {
+			if !placeholder_weights::prune_mappings(T::DbWeight::get(), 1).all_lte(remaining_weight)
+			{
+				return Weight::zero();
+			}
+
+			let (mut cursor, committed) = (Self::prune_cursor(), Self::committed_count());
+			let mut pruned = 0;
+			while cursor < committed
+				&& placeholder_weights::prune_mappings(T::DbWeight::get(), pruned + 1)
+					.all_lte(remaining_weight)
+			{
+				Self::remove_mappings(cursor);
+				cursor += 1;
+				pruned += 1;
+			}
+			PruneCursor::<T>::put(cursor);
+
+			placeholder_weights::prune_mappings(T::DbWeight::get(), pruned)
+		}
+
+		/// Set `CommittedCount` and `PruneCursor`. Before v1, the mappings of the committed
+		/// messages were removed by the updates, so only the pending messages have them.
+		fn migrate_to_v1() -> Weight {
+			if StorageVersion::get::<Self>() >= 1 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let mut committed = Self::tree().count();
+			let mut reads = 2;
+			while let Some(index) = committed
+				.checked_sub(1)
+				.filter(|index| IndexToRoot::<T>::contains_key(index))
+			{
+				committed = index;
+				reads += 1;
+			}
+			CommittedCount::<T>::put(committed);
+			PruneCursor::<T>::put(committed);
+			StorageVersion::new(1).put::<Self>();
+
+			T::DbWeight::get().reads_writes(reads, 3)
+		}
+
+		/// Enroll the destination domains which messages were already dispatched to. The
+		/// relayer reward pot is funded by `migration::FundRelayerPot`.
+		fn migrate_to_v2() -> Weight {
+			if StorageVersion::get::<Self>() >= 2 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let mut domains = 0;
+			for domain in Nonces::<T>::iter_keys() {
+				EnrolledDestinations::<T>::insert(domain, true);
+				domains += 1;
+			}
+			StorageVersion::new(2).put::<Self>();
+
+			T::DbWeight::get().reads_writes(domains + 1, domains + 1)
+		}
+
+		/// Store the frontier of the tree in `Nodes`, i.e. the roots of the complete subtrees
+		/// which the next messages are hashed with, so their proofs can be built.
+		fn migrate_to_v3() -> Weight {
+			if StorageVersion::get::<Self>() >= 3 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let mut writes = 1;
+			if let Ok(tree) = TreeLayout::decode(&mut Self::tree().encode().as_slice()) {
+				for level in 0..TREE_DEPTH as u32 {
+					let position = tree.count >> level;
+					if position & 1 == 1 {
+						Nodes::<T>::insert(level, position - 1, tree.branch[level as usize]);
+						writes += 1;
+					}
+				}
+			}
+			STORAGE_VERSION.put::<Self>();
+
+			T::DbWeight::get().reads_writes(2, writes)
+		}
+
+		/// Account of the pot which collects the dispatch fees to reward the relayers.
+		pub fn relayer_pot() -> T::AccountId {
+			T::PalletId::get().into_account_truncating()
+		}
+
+		/// Transfer the fee of dispatching a message body of `len` bytes from `who` to the
+		/// relayer reward pot.
+		fn charge_dispatch_fee(who: &T::AccountId, len: u32) -> DispatchResult {
+			let fee = Self::byte_fee().saturating_mul(len.into());
+			if fee.is_zero() {
+				return Ok(());
+			}
+
+			CurrencyOf::<T>::transfer(
+				who,
+				&Self::relayer_pot(),
+				fee,
+				ExistenceRequirement::KeepAlive,
+			)
+		}
+
+		/// Remove the root/index mappings of the message at `index`.
+		fn remove_mappings(index: u32) {
+			if let Some(root) = IndexToRoot::<T>::take(index) {
+				RootToIndex::<T>::remove(root);
+			}
+		}
+
+		/// Set new updater on self as well as updater manager.
+		#[transactional]
+		pub fn do_set_updater(new_updater: H160) -> DispatchResult {
+			// Modify NomadBase updater
+			Base::<T>::mutate(|base| base.updater = new_updater);
+
+			// Rotate updater on updater manager
+			nomad_updater_manager::Pallet::<T>::set_updater(new_updater)
+		}
+
+		/// Set the domain of self, which is the origin of the dispatched messages.
+		///
+		/// The domain cannot change once messages were dispatched, as they were committed
+		/// with the previous domain as origin.
+		pub fn set_local_domain(local_domain: u32) -> DispatchResult {
+			ensure!(Self::tree_count() == 0, Error::<T>::MessagesDispatched);
+			Base::<T>::mutate(|base| base.local_domain = local_domain);
+			Ok(())
+		}
+
+		/// Current root of the home tree, which is not committed until signed by the updater.
+		pub fn tree_root() -> H256 {
+			Self::tree().root()
+		}
+
+		/// Number of messages of the home tree.
+		pub fn tree_count() -> u32 {
+			Self::tree().count()
+		}
+
+		/// Hash of the message at `leaf_index`.
+		pub fn leaf(leaf_index: u32) -> Option<H256> {
+			Nodes::<T>::get(0, leaf_index)
+		}
+
+		/// Builds the Merkle proof of the message at `leaf_index` against the committed root,
+		/// which is the root the replicas accept.
+		///
+		/// Returns `None` if the message is not committed yet, or if it was dispatched before
+		/// the nodes of the tree were recorded in `Nodes`.
+		pub fn message_proof(leaf_index: u32) -> Option<MessageProof> {
+			let count = Self::committed_count();
+			if leaf_index >= count {
+				return None;
+			}
+
+			let path = (0..TREE_DEPTH as u32)
+				.map(|level| Self::node(level, (leaf_index >> level) ^ 1, count))
+				.collect::<Option<Vec<_>>>()?;
+			let proof = MessageProof {
+				leaf: Self::leaf(leaf_index)?,
+				index: leaf_index,
+				root: Self::base().committed_root,
+				path,
+			};
+
+			proof.verify().then_some(proof)
+		}
+
+		/// Rebuilds the tree of the first `count` messages from its frontier, i.e. the roots of
+		/// its complete subtrees which the next messages are hashed with.
+		fn frontier_tree(count: u32) -> Option<NomadLightMerkle> {
+			let mut branch = [H256::zero(); TREE_DEPTH];
+			for level in 0..TREE_DEPTH as u32 {
+				let position = count >> level;
+				if position & 1 == 1 {
+					branch[level as usize] = Nodes::<T>::get(level, position - 1)?;
+				}
+			}
+
+			let layout = TreeLayout { branch, count };
+			NomadLightMerkle::decode(&mut layout.encode().as_slice()).ok()
+		}
+
+		/// Remove the message hash at `index` and the roots of the subtrees it completed.
+		fn remove_nodes(index: u32) {
+			Nodes::<T>::remove(0, index);
+			for level in 0..TREE_DEPTH as u32 - 1 {
+				let position = index >> level;
+				if position & 1 == 0 {
+					break;
+				}
+				Nodes::<T>::remove(level + 1, position >> 1);
+			}
+		}
+
+		/// Store the message hash at `index` and the roots of the subtrees it completes.
+		fn insert_nodes(index: u32, leaf: H256) {
+			let mut node = leaf;
+			Nodes::<T>::insert(0, index, node);
+			for level in 0..TREE_DEPTH as u32 - 1 {
+				let position = index >> level;
+				if position & 1 == 0 {
+					break;
+				}
+				let Some(left) = Nodes::<T>::get(level, position - 1) else {
+					break;
+				};
+				node = hash_concat(left, node);
+				Nodes::<T>::insert(level + 1, position >> 1, node);
+			}
+		}
+
+		/// Root of the subtree at `level` and `index` of the tree of the first `count` messages.
+		///
+		/// Complete subtrees are read from `Nodes` and empty ones are zero hashes. Only the
+		/// subtree containing the last message can be partial, so it is hashed from its
+		/// children, which takes at most one read per level.
+		fn node(level: u32, index: u32, count: u32) -> Option<H256> {
+			let start = u64::from(index) << level;
+			let end = (u64::from(index) + 1) << level;
+			if end <= u64::from(count) {
+				Nodes::<T>::get(level, index)
+			} else if start >= u64::from(count) {
+				Some(zero_hash(level))
+			} else {
+				let left = Self::node(level - 1, index * 2, count)?;
+				let right = Self::node(level - 1, index * 2 + 1, count)?;
+				Some(hash_concat(left, right))
+			}
+		}
+	}
+
+	/// Local domain of the home, which is the destination of the messages sent to this chain.
+	pub struct LocalDomain<T>(PhantomData<T>);
+
+	impl<T: Config> Get<u32> for LocalDomain<T> {
+		fn get() -> u32 {
+			Base::<T>::get().local_domain
+		}
+	}
 }
 
 #[cfg(any(test, feature = "runtime-benchmarks"))]
@@ -391,7 +981,10 @@ pub mod common_tests_and_benches {
 	use hex_literal::hex;
 	use nomad_core::{SignedUpdate, Update};
 	use nomad_signature::Signature;
-	use sp_core::{H256, U256};
+	use sp_core::{H160, H256, U256};
+
+	/// Address of the updater signing the expected updates.
+	pub const UPDATER_ADDRESS: H160 = H160(hex!("19e7e376e7c213b7e7e7e46cc70a5dd086daff2a"));
 
 	const EXPECTED_NEW_ROOT_LONGEST_TREE: H256 = H256(hex!(
 		"dd0a05d7b71c171d06b51f11d1191f2ce23dbe679ecabea374ee3a7909383fb6"
@@ -417,4 +1010,26 @@ pub mod common_tests_and_benches {
 			},
 		}
 	}
+
+	/// Update of the zero root to `H256::repeat_byte(1)` on domain `1111`.
+	pub fn expected_signed_update() -> SignedUpdate {
+		SignedUpdate {
+			update: Update {
+				home_domain: 1111,
+				previous_root: H256([0u8; 32]),
+				new_root: H256([1u8; 32]),
+			},
+			signature: Signature {
+				r: U256::from_dec_str(
+					"108172166467498881923382587939104653020584515778413535909202974529351125581995",
+				)
+				.unwrap(),
+				s: U256::from_dec_str(
+					"24567415212865781861322727855523398821160274507883582359476963266144744361252",
+				)
+				.unwrap(),
+				v: 27,
+			},
+		}
+	}
 }
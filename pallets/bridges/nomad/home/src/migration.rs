@@ -0,0 +1,38 @@
+use frame_support::{
+	pallet_prelude::*,
+	sp_runtime::traits::{Saturating, Zero},
+	traits::{Currency, ExistenceRequirement, OnRuntimeUpgrade},
+};
+
+use crate::{Config, Pallet};
+
+/// Fund the relayer reward pot up to the existential deposit with the funds of `Source`, so
+/// the dispatch fees below it can be paid into it.
+///
+/// It does nothing once the pot is funded. The pot stays unfunded if `Source` cannot transfer
+/// the existential deposit, and it can be funded with `fund_relayer_pot` instead.
+pub struct FundRelayerPot<T, Source>(PhantomData<(T, Source)>);
+
+impl<T: Config, Source: Get<T::AccountId>> OnRuntimeUpgrade for FundRelayerPot<T, Source>
+where
+	[u8; 32]: From<T::AccountId>,
+{
+	fn on_runtime_upgrade() -> Weight {
+		let pot = Pallet::<T>::relayer_pot();
+		let min_balance = T::Currency::minimum_balance();
+		let missing = min_balance.saturating_sub(T::Currency::free_balance(&pot));
+		if missing.is_zero() {
+			return T::DbWeight::get().reads(1);
+		}
+
+		let _ = T::Currency::transfer(
+			&Source::get(),
+			&pot,
+			missing,
+			ExistenceRequirement::KeepAlive,
+		);
+
+		// The accounts of the pot and the source.
+		T::DbWeight::get().reads_writes(2, 2)
+	}
+}
@@ -1,8 +1,12 @@
-use frame_support::{derive_impl, parameter_types};
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{ConstU32, ConstU64},
+	PalletId,
+};
 use frame_system::{self as system, header_builder::da, test_utils::TestRandomness};
 use nomad_base::NomadBase;
 use sp_core::{H160, H256};
-use sp_runtime::{traits::IdentityLookup, AccountId32, BuildStorage};
+use sp_runtime::{traits::IdentityLookup, AccountId32, BuildStorage, Perbill};
 
 use crate as home;
 
@@ -13,6 +17,7 @@ type Block = frame_system::mocking::MockDaBlock<Test>;
 frame_support::construct_runtime!(
 	pub struct Test {
 		System: frame_system,
+		Balances: pallet_balances,
 		Home: home,
 		UpdaterManager: nomad_updater_manager,
 	}
@@ -20,10 +25,13 @@ frame_support::construct_runtime!(
 
 parameter_types! {
 	pub const BlockHashCount: u32 = 250;
+	pub const SlashRewardShare: Perbill = Perbill::from_percent(10);
+	pub const HomePalletId: PalletId = PalletId(*b"nmd/home");
 }
 
 #[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
 impl system::Config for Test {
+	type AccountData = pallet_balances::AccountData<u64>;
 	type AccountId = AccountId32;
 	type BaseCallFilter = frame_support::traits::Everything;
 	type Block = Block;
@@ -42,18 +50,56 @@ impl system::Config for Test {
 
 #[derive_impl(home::config_preludes::TestDefaultConfig as home::DefaultConfig)]
 impl home::Config for Test {
+	type PalletId = HomePalletId;
 	type RuntimeEvent = RuntimeEvent;
 }
 
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
+	type Balance = u64;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type MaxHolds = ();
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeHoldReason = ();
+	type WeightInfo = ();
+}
+
 impl nomad_updater_manager::Config for Test {
+	type Currency = Balances;
 	type RuntimeEvent = RuntimeEvent;
+	type SlashRewardShare = SlashRewardShare;
+	type Slashed = ();
+	type UnbondingDelay = ConstU32<10>;
+	type UpdaterBond = ConstU64<1_000>;
 }
 
-#[derive(Default)]
+/// Destination domains enrolled by default.
+pub(crate) const TEST_DESTINATIONS: [u32; 2] = [1111, 2222];
+
 pub(crate) struct ExtBuilder {
 	updater: H160,
 	local_domain: u32,
 	committed_root: H256,
+	destinations: Vec<u32>,
+	byte_fee: u64,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self {
+			updater: H160::zero(),
+			local_domain: 0,
+			committed_root: H256::zero(),
+			destinations: TEST_DESTINATIONS.to_vec(),
+			byte_fee: 0,
+		}
+	}
 }
 
 impl ExtBuilder {
@@ -64,16 +110,29 @@ impl ExtBuilder {
 		self
 	}
 
+	pub(crate) fn with_byte_fee(mut self, byte_fee: u64) -> Self {
+		self.byte_fee = byte_fee;
+		self
+	}
+
 	pub(crate) fn build(self) -> sp_io::TestExternalities {
 		let mut t = RuntimeGenesisConfig::default()
 			.system
 			.build_storage()
 			.expect("Genesis build should work");
 
+		// The relayer reward pot holds the existential deposit, so fees below it can be paid.
+		pallet_balances::GenesisConfig::<Test> {
+			balances: vec![(Home::relayer_pot(), 1)],
+		}
+		.assimilate_storage(&mut t)
+		.expect("Balances storage cannot be assimilated");
 		home::GenesisConfig::<Test> {
 			updater: self.updater,
 			local_domain: self.local_domain,
 			committed_root: self.committed_root,
+			destinations: self.destinations,
+			byte_fee: self.byte_fee,
 			_phantom: Default::default(),
 		}
 		.assimilate_storage(&mut t)
@@ -0,0 +1,81 @@
+use codec::{Decode, Encode};
+use nomad_merkle::TREE_DEPTH;
+use scale_info::TypeInfo;
+use sp_core::{hashing::keccak_256, RuntimeDebug, H256};
+use sp_std::vec::Vec;
+
+/// Merkle proof of a message of the home tree, in the format expected by the Nomad replica
+/// contracts (`MerkleLib.branchRoot`).
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub struct MessageProof {
+	/// Hash of the proven message.
+	pub leaf: H256,
+	/// Leaf index of the message.
+	pub index: u32,
+	/// Root of the tree the message is proven against.
+	pub root: H256,
+	/// Sibling of each level of the tree, from the leaves to the root.
+	pub path: Vec<H256>,
+}
+
+impl MessageProof {
+	/// Builds the proof of the leaf at `index` of the tree of `leaves`.
+	///
+	/// The tree is the sparse Merkle tree of depth `TREE_DEPTH` used by `NomadLightMerkle`, so
+	/// empty leaves are zero hashes.
+	pub fn new(leaves: &[H256], index: u32) -> Option<Self> {
+		let leaf = *leaves.get(index as usize)?;
+
+		let mut level = leaves.to_vec();
+		let mut position = index as usize;
+		let mut zero = H256::zero();
+		let mut path = Vec::with_capacity(TREE_DEPTH);
+		for _ in 0..TREE_DEPTH {
+			path.push(level.get(position ^ 1).copied().unwrap_or(zero));
+			level = level
+				.chunks(2)
+				.map(|pair| hash_concat(pair[0], pair.get(1).copied().unwrap_or(zero)))
+				.collect();
+			position /= 2;
+			zero = hash_concat(zero, zero);
+		}
+		let root = level.first().copied().unwrap_or(zero);
+
+		Some(Self {
+			leaf,
+			index,
+			root,
+			path,
+		})
+	}
+
+	/// Returns `true` if `path` links `leaf` at `index` to `root`.
+	pub fn verify(&self) -> bool {
+		let branch_root =
+			self.path
+				.iter()
+				.enumerate()
+				.fold(self.leaf, |current, (level, sibling)| {
+					if (self.index >> level) & 1 == 1 {
+						hash_concat(*sibling, current)
+					} else {
+						hash_concat(current, *sibling)
+					}
+				});
+
+		self.path.len() == TREE_DEPTH && branch_root == self.root
+	}
+}
+
+/// Keccak hash of the concatenation of two nodes of the tree.
+pub(crate) fn hash_concat(left: H256, right: H256) -> H256 {
+	let mut buf = [0u8; 64];
+	buf[..32].copy_from_slice(left.as_bytes());
+	buf[32..].copy_from_slice(right.as_bytes());
+	H256(keccak_256(&buf))
+}
+
+/// Root of an empty subtree of height `level`.
+pub(crate) fn zero_hash(level: u32) -> H256 {
+	(0..level).fold(H256::zero(), |zero, _| hash_concat(zero, zero))
+}
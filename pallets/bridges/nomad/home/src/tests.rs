@@ -1,16 +1,26 @@
 use std::convert::TryInto;
 
+use codec::{Decode, Encode};
 use frame_benchmarking::whitelisted_caller;
-use frame_support::{assert_err, assert_ok, pallet_prelude::Get, BoundedVec};
+use frame_support::{
+	assert_err, assert_noop, assert_ok,
+	pallet_prelude::Get,
+	parameter_types,
+	traits::{Currency, Hooks, StorageVersion},
+	weights::Weight,
+	BoundedVec,
+};
 use nomad_base::testing::*;
 use nomad_core::{destination_and_nonce, NomadMessage, NomadState};
-use nomad_merkle::Merkle;
-use sp_core::H256;
-use sp_runtime::{AccountId32, DispatchResult};
+use nomad_merkle::{Merkle, NomadLightMerkle};
+use sp_core::{H160, H256};
+use sp_runtime::{AccountId32, DispatchError, DispatchResult};
 use test_case::test_case;
 
 use crate::{
-	common_tests_and_benches::expected_longest_tree_signed_update, mock::*, Config, Error,
+	common_tests_and_benches::expected_longest_tree_signed_update, migration::FundRelayerPot,
+	mock::*, placeholder_weights, proof::hash_concat, Config, Error, Nodes, Nonces,
+	PendingMessages, TreeLayout,
 };
 
 const TEST_REMOTE_DOMAIN: u32 = 2222;
@@ -18,6 +28,11 @@ const TEST_SENDER_VEC: [u8; 32] = [2u8; 32];
 const TEST_SENDER_BYTES: H256 = H256(TEST_SENDER_VEC);
 const TEST_SENDER_ACCOUNT: AccountId32 = AccountId32::new(TEST_SENDER_VEC);
 const TEST_RECIPIENT: H256 = H256::repeat_byte(3);
+const TEST_RELAYER: AccountId32 = AccountId32::new([4u8; 32]);
+
+parameter_types! {
+	pub const RelayerPotSource: AccountId32 = TEST_SENDER_ACCOUNT;
+}
 
 // TODO: test governance router can set updater for base and UM
 
@@ -96,6 +111,14 @@ fn it_catches_improper_update() {
 		.execute_with(|| {
 			let committed_root = Home::base().committed_root;
 
+			// Bond the updater, so it is slashed
+			let bonder = AccountId32::new([5u8; 32]);
+			let _ = Balances::deposit_creating(&bonder, 10_000);
+			assert_ok!(UpdaterManager::do_bond(
+				bonder.clone(),
+				TEST_UPDATER.address()
+			));
+
 			// Sign improper update
 			let fake_root = H256::repeat_byte(9);
 			let improper_signed = TEST_UPDATER.sign_update(committed_root, fake_root);
@@ -103,6 +126,9 @@ fn it_catches_improper_update() {
 			let origin = RuntimeOrigin::signed(TEST_SENDER_ACCOUNT);
 			assert_ok!(Home::improper_update(origin, improper_signed.clone()));
 			assert!(Home::base().state == NomadState::Failed);
+			assert_eq!(UpdaterManager::bond_of(TEST_UPDATER.address()), None);
+			assert_eq!(Balances::total_balance(&bonder), 9_000);
+			assert_eq!(Balances::free_balance(&TEST_SENDER_ACCOUNT), 100);
 
 			let expected = vec![
 				crate::Event::UpdaterSlashed {
@@ -132,10 +158,44 @@ fn dispatch_random_message(origin: RuntimeOrigin) -> H256 {
 	Home::tree().root()
 }
 
-#[test_case( 1, 0 => Err(Error::<Test>::MaxIndexWitnessExhausted.into()) ; "Invalid max index witness")]
-#[test_case( 2, 1 => Err(Error::<Test>::MaxIndexWitnessExhausted.into()) ; "Max index witness exhausted")]
+#[test_case(1; "Single message")]
+#[test_case(2; "Complete subtree")]
+#[test_case(5; "Incomplete subtree")]
+fn it_proves_committed_messages(dispatch_messages: u32) {
+	ExtBuilder::default()
+		.with_base(*TEST_NOMAD_BASE)
+		.build()
+		.execute_with(move || {
+			let committed_root = Home::base().committed_root;
+			let origin = RuntimeOrigin::signed(TEST_SENDER_ACCOUNT);
+			let root = (0..dispatch_messages)
+				.map(|_| dispatch_random_message(origin.clone()))
+				.last()
+				.unwrap();
+			assert_eq!(Home::tree_root(), root);
+			assert_eq!(Home::tree_count(), dispatch_messages);
+
+			// Pending messages are not proven until they are committed.
+			assert_eq!(Home::message_proof(0), None);
+			let signed_update = TEST_UPDATER.sign_update(committed_root, root);
+			assert_ok!(Home::submit_update(origin.clone(), signed_update));
+
+			// Later messages do not change the root the committed ones are proven against.
+			dispatch_random_message(origin);
+			for leaf_index in 0..dispatch_messages {
+				let proof = Home::message_proof(leaf_index).unwrap();
+				assert_eq!(proof.root, root);
+				assert_eq!(Some(proof.leaf), Home::leaf(leaf_index));
+				assert!(proof.verify());
+			}
+			assert_eq!(Home::message_proof(dispatch_messages), None);
+		})
+}
+
+#[test_case( 1, 0 => Ok(()) ; "Zero max index witness")]
+#[test_case( 2, 1 => Ok(()) ; "Short max index witness")]
 #[test_case( 2, 2 => Ok(()); "Valid update")]
-fn it_update_max_index_witness(dispatch_messages: usize, max_index: u32) -> DispatchResult {
+fn it_ignores_update_max_index_witness(dispatch_messages: usize, max_index: u32) -> DispatchResult {
 	ExtBuilder::default()
 		.with_base(*TEST_NOMAD_BASE)
 		.build()
@@ -185,8 +245,11 @@ fn it_dispatches_messages_and_accepts_updates() {
 			};
 			assert!(events().contains(&expected_update_event));
 
-			// Assert mappings are cleared out up to signed_update.new_root()
+			// Assert mappings are cleared out up to signed_update.new_root() when idle
 			assert_eq!(Home::base().committed_root, last_root);
+			assert_eq!(Home::committed_count(), 2);
+			assert_eq!(Home::root_to_index(last_root), Some(1));
+			Home::on_idle(1, Weight::MAX);
 			for (idx, root) in roots.into_iter().enumerate() {
 				assert_eq!(Home::index_to_root(idx as u32), None);
 				assert_eq!(Home::root_to_index(root), None);
@@ -200,7 +263,7 @@ fn it_dispatches_messages_and_accepts_updates() {
 			let signed_update = TEST_UPDATER.sign_update(committed_root, root_after_third_msg);
 
 			// Submit signed update
-			assert_ok!(Home::update(origin, signed_update.clone(), 3));
+			assert_ok!(Home::submit_update(origin, signed_update.clone()));
 
 			let expected_update_event = crate::Event::Update {
 				home_domain: TEST_LOCAL_DOMAIN,
@@ -210,7 +273,8 @@ fn it_dispatches_messages_and_accepts_updates() {
 			};
 			assert!(events().contains(&expected_update_event));
 
-			// Assert mappings are cleared out up to signed_update.new_root()
+			// Assert mappings are cleared out up to signed_update.new_root() when idle
+			Home::on_idle(2, Weight::MAX);
 			assert!(Home::index_to_root(2).is_none());
 			assert!(Home::root_to_index(root_after_third_msg).is_none());
 			assert!(Home::base().committed_root == root_after_third_msg);
@@ -284,3 +348,466 @@ fn it_longest_tree() {
 			assert_ok!(Home::update(origin, signed_update, TREE_DEPTH as u32));
 		})
 }
+
+/// Bonds `updater` with the funds of a new account.
+fn bond(updater: H160) {
+	let bonder = AccountId32::new([5u8; 32]);
+	let _ = Balances::deposit_creating(&bonder, 10_000);
+	assert_ok!(UpdaterManager::do_bond(bonder, updater));
+}
+
+/// Fails the home through an improper update of the test updater.
+fn fail_home() {
+	bond(TEST_UPDATER.address());
+	let improper_signed =
+		TEST_UPDATER.sign_update(Home::base().committed_root, H256::repeat_byte(9));
+	assert_ok!(Home::improper_update(
+		RuntimeOrigin::signed(TEST_SENDER_ACCOUNT),
+		improper_signed
+	));
+	assert!(Home::base().state == NomadState::Failed);
+}
+
+#[test]
+fn it_recovers_replaying_pending_messages() {
+	ExtBuilder::default()
+		.with_base(*TEST_NOMAD_BASE)
+		.build()
+		.execute_with(|| {
+			let origin = RuntimeOrigin::signed(TEST_SENDER_ACCOUNT);
+
+			// Commit the first message, the next ones are pending when the home fails.
+			let committed_root = Home::base().committed_root;
+			let first_root = dispatch_random_message(origin.clone());
+			let signed_update = TEST_UPDATER.sign_update(committed_root, first_root);
+			assert_ok!(Home::update(origin.clone(), signed_update, 1));
+			let roots = (0..2)
+				.map(|_| dispatch_random_message(origin.clone()))
+				.collect::<Vec<_>>();
+			fail_home();
+
+			// Messages cannot be dispatched, and a new updater does not recover the home.
+			assert_err!(
+				Home::dispatch(
+					origin.clone(),
+					TEST_REMOTE_DOMAIN,
+					TEST_RECIPIENT,
+					vec![1u8].try_into().unwrap()
+				),
+				Error::<Test>::FailedState
+			);
+			bond(FAKE_UPDATER.address());
+			assert_ok!(Home::set_updater(
+				RuntimeOrigin::root(),
+				FAKE_UPDATER.address()
+			));
+			assert!(Home::base().state == NomadState::Failed);
+
+			// Re-anchor on the second message and replay the third one.
+			assert_ok!(Home::recover(
+				RuntimeOrigin::root(),
+				FAKE_UPDATER.address(),
+				roots[0],
+				PendingMessages::Replay,
+				2
+			));
+			assert!(Home::base().state == NomadState::Active);
+			assert_eq!(Home::base().committed_root, roots[0]);
+			assert_eq!(Home::base().updater, FAKE_UPDATER.address());
+			assert_eq!(UpdaterManager::get_updater(), FAKE_UPDATER.address());
+			assert_eq!(Home::committed_count(), 2);
+			Home::on_idle(1, Weight::MAX);
+			assert_eq!(Home::root_to_index(roots[0]), None);
+			assert_eq!(Home::index_to_root(1), None);
+			assert_eq!(Home::index_to_root(2), Some(roots[1]));
+			assert_eq!(Home::tree().count(), 3);
+			assert_eq!(
+				events().last(),
+				Some(&crate::Event::Recovered {
+					committed_root: roots[0],
+					updater: FAKE_UPDATER.address(),
+					replayed_messages: 1,
+					pruned_messages: 0,
+				})
+			);
+
+			// Messages dispatched after the recovery are committed with the replayed one.
+			let last_root = dispatch_random_message(origin.clone());
+			assert_eq!(Home::root_to_index(last_root), Some(3));
+			let signed_update = FAKE_UPDATER.sign_update(roots[0], last_root);
+			assert_ok!(Home::submit_update(origin, signed_update));
+			assert_eq!(Home::base().committed_root, last_root);
+			Home::on_idle(2, Weight::MAX);
+			assert_eq!(Home::index_to_root(2), None);
+		})
+}
+
+#[test]
+fn it_recovers_pruning_pending_messages() {
+	ExtBuilder::default()
+		.with_base(*TEST_NOMAD_BASE)
+		.build()
+		.execute_with(|| {
+			let origin = RuntimeOrigin::signed(TEST_SENDER_ACCOUNT);
+			let roots = (0..3)
+				.map(|_| dispatch_random_message(origin.clone()))
+				.collect::<Vec<_>>();
+			fail_home();
+			bond(FAKE_UPDATER.address());
+
+			// The witness bounds the pruned messages.
+			assert_err!(
+				Home::recover(
+					RuntimeOrigin::root(),
+					FAKE_UPDATER.address(),
+					roots[0],
+					PendingMessages::Prune,
+					1
+				),
+				Error::<Test>::MaxIndexWitnessExhausted
+			);
+
+			// Re-anchor on the first message and prune the other ones.
+			assert_ok!(Home::recover(
+				RuntimeOrigin::root(),
+				FAKE_UPDATER.address(),
+				roots[0],
+				PendingMessages::Prune,
+				2
+			));
+			assert!(Home::base().state == NomadState::Active);
+			assert_eq!(Home::base().committed_root, roots[0]);
+			assert_eq!(Home::tree().count(), 1);
+			assert_eq!(Home::tree().root(), roots[0]);
+			Home::on_idle(1, Weight::MAX);
+			for (index, root) in roots.iter().enumerate() {
+				assert_eq!(Home::index_to_root(index as u32), None);
+				assert_eq!(Home::root_to_index(root), None);
+			}
+			assert_eq!(Home::leaf(1), None);
+			assert_eq!(Home::leaf(2), None);
+			assert_eq!(
+				events().last(),
+				Some(&crate::Event::Recovered {
+					committed_root: roots[0],
+					updater: FAKE_UPDATER.address(),
+					replayed_messages: 0,
+					pruned_messages: 2,
+				})
+			);
+
+			// Messages dispatched after the recovery take the leaves of the pruned ones.
+			let last_root = dispatch_random_message(origin.clone());
+			assert_eq!(Home::root_to_index(last_root), Some(1));
+			let signed_update = FAKE_UPDATER.sign_update(roots[0], last_root);
+			assert_ok!(Home::submit_update(origin, signed_update));
+			assert_eq!(Home::base().committed_root, last_root);
+			assert!(Home::message_proof(1).unwrap().verify());
+		})
+}
+
+#[test]
+fn it_rejects_invalid_recoveries() {
+	ExtBuilder::default()
+		.with_base(*TEST_NOMAD_BASE)
+		.build()
+		.execute_with(|| {
+			let origin = RuntimeOrigin::signed(TEST_SENDER_ACCOUNT);
+			let committed_root = Home::base().committed_root;
+			let _ = dispatch_random_message(origin);
+			bond(FAKE_UPDATER.address());
+			let recover = |root| {
+				Home::recover(
+					RuntimeOrigin::root(),
+					FAKE_UPDATER.address(),
+					root,
+					PendingMessages::Replay,
+					1,
+				)
+			};
+
+			assert_err!(recover(committed_root), Error::<Test>::NotFailedState);
+			fail_home();
+			assert_err!(
+				recover(H256::repeat_byte(9)),
+				Error::<Test>::UnknownRecoveryRoot
+			);
+			assert_ok!(recover(committed_root));
+		})
+}
+
+#[test]
+fn it_prunes_committed_mappings_within_idle_weight() {
+	ExtBuilder::default()
+		.with_base(*TEST_NOMAD_BASE)
+		.build()
+		.execute_with(|| {
+			let origin = RuntimeOrigin::signed(TEST_SENDER_ACCOUNT);
+			let committed_root = Home::base().committed_root;
+			let roots = (0..3)
+				.map(|_| dispatch_random_message(origin.clone()))
+				.collect::<Vec<_>>();
+			let signed_update = TEST_UPDATER.sign_update(committed_root, roots[2]);
+			assert_ok!(Home::submit_update(origin, signed_update));
+
+			let prune_weight = |n| {
+				let db_weight = <Test as frame_system::Config>::DbWeight::get();
+				placeholder_weights::prune_mappings(db_weight, n)
+			};
+
+			// Nothing is pruned without weight for a single mapping.
+			let weight = prune_weight(1);
+			assert_eq!(Home::on_idle(1, weight / 2), Weight::zero());
+			assert_eq!(Home::prune_cursor(), 0);
+
+			let weight = prune_weight(2);
+			assert_eq!(Home::on_idle(1, weight), weight);
+			assert_eq!(Home::prune_cursor(), 2);
+			assert_eq!(Home::root_to_index(roots[1]), None);
+			assert_eq!(Home::root_to_index(roots[2]), Some(2));
+
+			let weight = prune_weight(1);
+			assert_eq!(Home::on_idle(2, Weight::MAX), weight);
+			assert_eq!(Home::prune_cursor(), 3);
+			assert_eq!(Home::index_to_root(2), None);
+		})
+}
+
+#[test]
+fn it_fails_on_updates_to_committed_roots() {
+	ExtBuilder::default()
+		.with_base(*TEST_NOMAD_BASE)
+		.build()
+		.execute_with(|| {
+			let origin = RuntimeOrigin::signed(TEST_SENDER_ACCOUNT);
+			let committed_root = Home::base().committed_root;
+			let roots = (0..2)
+				.map(|_| dispatch_random_message(origin.clone()))
+				.collect::<Vec<_>>();
+			let signed_update = TEST_UPDATER.sign_update(committed_root, roots[1]);
+			assert_ok!(Home::submit_update(origin.clone(), signed_update));
+			bond(TEST_UPDATER.address());
+
+			// The mapping of the committed root is not pruned yet, but the update is improper.
+			assert_eq!(Home::root_to_index(roots[0]), Some(0));
+			let signed_update = TEST_UPDATER.sign_update(roots[1], roots[0]);
+			assert_ok!(Home::submit_update(origin, signed_update));
+			assert!(Home::base().state == NomadState::Failed);
+		})
+}
+
+#[test]
+fn it_dispatches_only_to_enrolled_destinations() {
+	ExtBuilder::default()
+		.with_base(*TEST_NOMAD_BASE)
+		.build()
+		.execute_with(|| {
+			let origin = RuntimeOrigin::signed(TEST_SENDER_ACCOUNT);
+			let domain = 3333;
+			let dispatch = || {
+				let body = [1u8; 8].to_vec().try_into().unwrap();
+				Home::dispatch(origin.clone(), domain, TEST_RECIPIENT, body)
+			};
+
+			assert_err!(dispatch(), Error::<Test>::DestinationNotEnrolled);
+			assert_err!(
+				Home::enroll_destination(origin.clone(), domain),
+				DispatchError::BadOrigin
+			);
+
+			assert_ok!(Home::enroll_destination(RuntimeOrigin::root(), domain));
+			assert!(events().contains(&crate::Event::DestinationEnrolled { domain }));
+			assert_ok!(dispatch());
+
+			assert_ok!(Home::unenroll_destination(RuntimeOrigin::root(), domain));
+			assert!(events().contains(&crate::Event::DestinationUnenrolled { domain }));
+			assert_err!(dispatch(), Error::<Test>::DestinationNotEnrolled);
+			assert_err!(
+				Home::unenroll_destination(RuntimeOrigin::root(), domain),
+				Error::<Test>::DestinationNotEnrolled
+			);
+			assert_eq!(Home::nonces(domain), 1);
+		})
+}
+
+#[test]
+fn it_charges_dispatch_fee_per_byte() {
+	ExtBuilder::default()
+		.with_base(*TEST_NOMAD_BASE)
+		.with_byte_fee(2)
+		.build()
+		.execute_with(|| {
+			let origin = RuntimeOrigin::signed(TEST_SENDER_ACCOUNT);
+			let pot = Home::relayer_pot();
+			assert_eq!(Balances::free_balance(&pot), 1);
+
+			// The fee of 8 bytes is 16, and the sender must be kept alive.
+			let _ = Balances::deposit_creating(&TEST_SENDER_ACCOUNT, 16);
+			let body: BoundedVec<u8, _> = [1u8; 8].to_vec().try_into().unwrap();
+			assert!(Home::dispatch(
+				origin.clone(),
+				TEST_REMOTE_DOMAIN,
+				TEST_RECIPIENT,
+				body.clone()
+			)
+			.is_err());
+			assert_eq!(Home::nonces(TEST_REMOTE_DOMAIN), 0);
+
+			let _ = Balances::deposit_creating(&TEST_SENDER_ACCOUNT, 1);
+			assert_ok!(Home::dispatch(
+				origin.clone(),
+				TEST_REMOTE_DOMAIN,
+				TEST_RECIPIENT,
+				body
+			));
+			assert_eq!(Balances::free_balance(&TEST_SENDER_ACCOUNT), 1);
+			assert_eq!(Balances::free_balance(&pot), 17);
+
+			assert_err!(
+				Home::set_byte_fee(origin.clone(), 0),
+				DispatchError::BadOrigin
+			);
+			assert_ok!(Home::set_byte_fee(RuntimeOrigin::root(), 0));
+			assert!(events().contains(&crate::Event::ByteFeeSet { fee: 0 }));
+			dispatch_random_message(origin);
+			assert_eq!(Balances::free_balance(&pot), 17);
+		})
+}
+
+#[test]
+fn it_pays_relayer_rewards_from_pot() {
+	ExtBuilder::default()
+		.with_base(*TEST_NOMAD_BASE)
+		.build()
+		.execute_with(|| {
+			let origin = RuntimeOrigin::signed(TEST_SENDER_ACCOUNT);
+			let relayer = RuntimeOrigin::signed(TEST_RELAYER);
+			let pot = Home::relayer_pot();
+			let _ = Balances::deposit_creating(&TEST_SENDER_ACCOUNT, 20);
+
+			assert_ok!(Home::fund_relayer_pot(origin.clone(), 11));
+			assert_eq!(Balances::free_balance(&pot), 12);
+			assert!(events().contains(&crate::Event::RelayerPotFunded {
+				who: TEST_SENDER_ACCOUNT,
+				amount: 11,
+			}));
+
+			assert_noop!(
+				Home::withdraw_reward(relayer.clone()),
+				Error::<Test>::NoReward
+			);
+			assert_noop!(
+				Home::reward_relayer(origin.clone(), TEST_RELAYER, 12),
+				DispatchError::BadOrigin
+			);
+			assert_ok!(Home::reward_relayer(
+				RuntimeOrigin::root(),
+				TEST_RELAYER,
+				12
+			));
+			assert_eq!(Home::relayer_reward(TEST_RELAYER), 12);
+
+			// The pot is kept alive, and the reward is kept until it can be paid.
+			assert!(Home::withdraw_reward(relayer.clone()).is_err());
+			assert_eq!(Home::relayer_reward(TEST_RELAYER), 12);
+
+			assert_ok!(Home::fund_relayer_pot(origin, 5));
+			assert_ok!(Home::withdraw_reward(relayer.clone()));
+			assert_eq!(Balances::free_balance(&TEST_RELAYER), 12);
+			assert_eq!(Balances::free_balance(&pot), 5);
+			assert_eq!(Home::relayer_reward(TEST_RELAYER), 0);
+			assert!(events().contains(&crate::Event::RewardWithdrawn {
+				relayer: TEST_RELAYER,
+				amount: 12,
+			}));
+			assert_noop!(Home::withdraw_reward(relayer), Error::<Test>::NoReward);
+		})
+}
+
+#[test]
+fn it_funds_relayer_pot_on_upgrade() {
+	use frame_support::traits::OnRuntimeUpgrade;
+
+	ExtBuilder::default()
+		.with_base(*TEST_NOMAD_BASE)
+		.build()
+		.execute_with(|| {
+			let pot = Home::relayer_pot();
+			let _ = Balances::make_free_balance_be(&pot, 0);
+			let _ = Balances::deposit_creating(&TEST_SENDER_ACCOUNT, 10);
+
+			FundRelayerPot::<Test, RelayerPotSource>::on_runtime_upgrade();
+			assert_eq!(Balances::free_balance(&pot), 1);
+			assert_eq!(Balances::free_balance(&TEST_SENDER_ACCOUNT), 9);
+
+			// The pot is funded once.
+			FundRelayerPot::<Test, RelayerPotSource>::on_runtime_upgrade();
+			assert_eq!(Balances::free_balance(&TEST_SENDER_ACCOUNT), 9);
+		})
+}
+
+#[test]
+fn it_migrates_to_v2() {
+	ExtBuilder::default()
+		.with_base(*TEST_NOMAD_BASE)
+		.build()
+		.execute_with(|| {
+			let domain = 3333;
+			Nonces::<Test>::insert(domain, 5);
+			StorageVersion::new(1).put::<Home>();
+
+			Home::on_runtime_upgrade();
+			assert!(Home::is_enrolled_destination(domain));
+			assert_eq!(StorageVersion::get::<Home>(), 3);
+		})
+}
+
+#[test]
+fn tree_layout_matches_light_merkle() {
+	let mut tree = NomadLightMerkle::default();
+	for byte in 1..=5 {
+		tree.ingest(H256::repeat_byte(byte)).unwrap();
+	}
+
+	let layout = TreeLayout::decode(&mut tree.encode().as_slice()).unwrap();
+	assert_eq!(layout.count, 5);
+	// The leaf 4 and the subtree of the leaves 0 to 3 are complete.
+	assert_eq!(layout.branch[0], H256::repeat_byte(5));
+	let pair = |left: u8, right: u8| hash_concat(H256::repeat_byte(left), H256::repeat_byte(right));
+	assert_eq!(layout.branch[2], hash_concat(pair(1, 2), pair(3, 4)));
+}
+
+#[test_case(3; "Partial frontier")]
+#[test_case(4; "Complete subtree frontier")]
+fn it_migrates_to_v3(pre_upgrade_messages: u32) {
+	ExtBuilder::default()
+		.with_base(*TEST_NOMAD_BASE)
+		.build()
+		.execute_with(move || {
+			let committed_root = Home::base().committed_root;
+			let origin = RuntimeOrigin::signed(TEST_SENDER_ACCOUNT);
+			for _ in 0..pre_upgrade_messages {
+				dispatch_random_message(origin.clone());
+			}
+			// Before v3, only the tree was stored.
+			let _ = Nodes::<Test>::clear(u32::MAX, None);
+			StorageVersion::new(2).put::<Home>();
+
+			Home::on_runtime_upgrade();
+			assert_eq!(StorageVersion::get::<Home>(), 3);
+
+			let root = (0..3)
+				.map(|_| dispatch_random_message(origin.clone()))
+				.last()
+				.unwrap();
+			let signed_update = TEST_UPDATER.sign_update(committed_root, root);
+			assert_ok!(Home::submit_update(origin, signed_update));
+
+			// The hashes of the messages dispatched before the upgrade are not known, unless
+			// they are part of the frontier.
+			assert_eq!(Home::message_proof(0), None);
+			for leaf_index in pre_upgrade_messages..pre_upgrade_messages + 3 {
+				assert!(Home::message_proof(leaf_index).unwrap().verify());
+			}
+		})
+}
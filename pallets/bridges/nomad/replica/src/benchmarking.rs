@@ -0,0 +1,117 @@
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::{assert_ok, traits::Get};
+use frame_system::RawOrigin;
+use nomad_core::{NomadMessage, NomadState};
+use nomad_home::{
+	common_tests_and_benches::{
+		expected_longest_tree_signed_update, expected_signed_update, UPDATER_ADDRESS,
+	},
+	MessageProof,
+};
+use sp_core::{H160, H256};
+use sp_runtime::traits::Zero;
+use sp_std::{boxed::Box, iter::repeat, vec::Vec};
+
+use crate::{
+	message::{decode_message, MESSAGE_HEADER_LEN},
+	*,
+};
+
+const REMOTE_DOMAIN: u32 = 1111;
+
+// Design of benchmark cases:
+// - `update` and `double_update` are dominated by the recovery of the updater signatures.
+// - `prove` verifies a proof of `TREE_DEPTH` hashes.
+// - `process` cost is affected by the length of the message, but not by its handling, which
+// is charged separately.
+
+fn enroll<T: Config>(committed_root: H256) {
+	assert_ok!(Pallet::<T>::enroll_replica(
+		RawOrigin::Root.into(),
+		REMOTE_DOMAIN,
+		UPDATER_ADDRESS,
+		committed_root
+	));
+}
+
+/// Formats a message of `len` bytes to the local domain.
+fn message<T: Config>(len: u32) -> Vec<u8> {
+	let body_len = (len as usize).saturating_sub(MESSAGE_HEADER_LEN);
+	NomadMessage::<T::MaxMessageBytes> {
+		origin: REMOTE_DOMAIN,
+		sender: H256::repeat_byte(2),
+		nonce: 0,
+		destination: T::LocalDomain::get(),
+		recipient: H256::repeat_byte(3),
+		body: repeat(3u8)
+			.take(body_len)
+			.collect::<Vec<_>>()
+			.try_into()
+			.expect("`len` must be less than `T::MaxMessageBytes`"),
+	}
+	.to_vec()
+}
+
+benchmarks! {
+	update {
+		enroll::<T>(H256::zero());
+		let signed_update = expected_longest_tree_signed_update();
+		let new_root = signed_update.new_root();
+	}: _(RawOrigin::Signed(whitelisted_caller()), signed_update)
+	verify {
+		assert_eq!(Replicas::<T>::get(REMOTE_DOMAIN).unwrap().committed_root, new_root);
+	}
+
+	double_update {
+		enroll::<T>(H256::zero());
+		let first = Box::new(expected_longest_tree_signed_update());
+		let second = Box::new(expected_signed_update());
+	}: _(RawOrigin::Signed(whitelisted_caller()), first, second)
+	verify {
+		assert!(Replicas::<T>::get(REMOTE_DOMAIN).unwrap().state == NomadState::Failed);
+	}
+
+	prove {
+		let message = message::<T>(T::MaxMessageBytes::get());
+		let leaf = decode_message::<T::MaxMessageBytes>(&message).unwrap().hash();
+		let proof = MessageProof::new(&[leaf], 0).unwrap();
+		enroll::<T>(proof.root);
+	}: _(RawOrigin::Signed(whitelisted_caller()), REMOTE_DOMAIN, Box::new(proof))
+	verify {
+		assert_eq!(
+			Messages::<T>::get(leaf),
+			Some(MessageStatus::Proven { remote_domain: REMOTE_DOMAIN })
+		);
+	}
+
+	process {
+		let b in (MESSAGE_HEADER_LEN as u32) .. T::MaxMessageBytes::get();
+
+		enroll::<T>(H256::zero());
+		let message = message::<T>(b);
+		let leaf = decode_message::<T::MaxMessageBytes>(&message).unwrap().hash();
+		Messages::<T>::insert(leaf, MessageStatus::Proven { remote_domain: REMOTE_DOMAIN });
+		let message = message.try_into().unwrap();
+	}: _(RawOrigin::Signed(whitelisted_caller()), message)
+	verify {
+		assert_eq!(Messages::<T>::get(leaf), Some(MessageStatus::Processed));
+	}
+
+	enroll_replica {
+		let committed_root = H256::repeat_byte(1);
+	}: _(RawOrigin::Root, REMOTE_DOMAIN, UPDATER_ADDRESS, committed_root)
+	verify {
+		assert_eq!(
+			ConfirmAt::<T>::get((REMOTE_DOMAIN, 1), committed_root),
+			Some(Zero::zero())
+		);
+	}
+
+	set_updater {
+		enroll::<T>(H256::zero());
+		let new_updater = H160::repeat_byte(1);
+	}: _(RawOrigin::Root, REMOTE_DOMAIN, new_updater)
+	verify {
+		assert_eq!(Replicas::<T>::get(REMOTE_DOMAIN).unwrap().updater, new_updater);
+	}
+}
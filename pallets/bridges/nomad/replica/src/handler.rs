@@ -0,0 +1,37 @@
+use sp_core::H256;
+use sp_runtime::DispatchResult;
+
+/// Handles the messages processed by the replica.
+///
+/// Handlers are registered in the runtime as a tuple, and the message goes to the first
+/// handler of `recipient`.
+pub trait HandleMessage {
+	/// Handles a message of `origin` domain sent by `sender` to `recipient`.
+	///
+	/// Returns `None` if `recipient` is not handled.
+	fn handle(
+		origin: u32,
+		nonce: u32,
+		sender: H256,
+		recipient: H256,
+		body: &[u8],
+	) -> Option<DispatchResult>;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(8)]
+impl HandleMessage for Tuple {
+	fn handle(
+		origin: u32,
+		nonce: u32,
+		sender: H256,
+		recipient: H256,
+		body: &[u8],
+	) -> Option<DispatchResult> {
+		for_tuples!( #(
+			if let Some(result) = Tuple::handle(origin, nonce, sender, recipient, body) {
+				return Some(result);
+			}
+		)* );
+		None
+	}
+}
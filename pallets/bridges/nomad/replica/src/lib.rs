@@ -0,0 +1,414 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Replica side of the Nomad bridge, which receives the messages of remote Nomad homes.
+//!
+//! Each remote home has a replica, which follows the updates signed by the updater of the
+//! home. The root of an update is accepted after `OptimisticPeriod` blocks, so a double update
+//! of the updater can be proven in the meantime, failing the replica. Messages are proven
+//! against accepted roots, and then processed by the `MessageHandler` of their recipient.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod handler;
+pub mod message;
+pub mod placeholder_weights;
+
+pub use handler::HandleMessage;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{pallet_prelude::*, storage::with_storage_layer};
+	use frame_system::pallet_prelude::*;
+	use nomad_base::NomadBase;
+	use nomad_core::{NomadState, SignedUpdate};
+	use nomad_home::MessageProof;
+	use sp_core::{H160, H256};
+	use sp_runtime::traits::{Saturating, Zero};
+	use sp_std::{boxed::Box, mem, vec::Vec};
+
+	use super::{handler::HandleMessage, message::decode_message, placeholder_weights};
+
+	/// Default implementations of [`DefaultConfig`], which can be used to implement [`Config`].
+	pub mod config_preludes {
+		use super::DefaultConfig;
+
+		/// Provides a viable default config that can be used with
+		/// [`derive_impl`](`frame_support::derive_impl`) to derive a testing pallet config
+		/// based on this one.
+		pub struct TestDefaultConfig;
+
+		#[frame_support::register_default_impl(TestDefaultConfig)]
+		impl DefaultConfig for TestDefaultConfig {
+			type MaxHandlerWeight = ();
+			type MaxMessageBytes = frame_support::traits::ConstU32<2124>;
+			type MessageHandler = ();
+		}
+	}
+
+	#[pallet::config(with_default)]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Local domain, to which processed messages must be sent. It is the local domain of the
+		/// home, e.g. `nomad_home::LocalDomain`, so it follows its `SetLocalDomain` changes.
+		#[pallet::no_default]
+		type LocalDomain: Get<u32>;
+
+		/// Number of blocks after an update before its new root is accepted.
+		#[pallet::constant]
+		#[pallet::no_default]
+		type OptimisticPeriod: Get<BlockNumberFor<Self>>;
+
+		/// Max allowed message size, including its header.
+		#[pallet::constant]
+		type MaxMessageBytes: Get<u32>;
+
+		/// Handlers of the processed messages.
+		type MessageHandler: HandleMessage;
+
+		/// Max weight of the handling of a message, which is charged by `process`.
+		#[pallet::constant]
+		type MaxHandlerWeight: Get<Weight>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Status of a message received by the replicas.
+	#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+	pub enum MessageStatus {
+		/// Proven against an accepted root of the replica of `remote_domain`, and waiting to be
+		/// processed. Only a message of `remote_domain` can be processed.
+		Proven {
+			remote_domain: u32,
+		},
+		Processed,
+	}
+
+	// Nomad base of the replica of each remote domain, whose local domain is the remote one.
+	#[pallet::storage]
+	#[pallet::getter(fn replica)]
+	pub type Replicas<T> = StorageMap<_, Twox64Concat, u32, NomadBase>;
+
+	// Number of enrollments of the replica of each remote domain.
+	#[pallet::storage]
+	#[pallet::getter(fn enrollments)]
+	pub type Enrollments<T> = StorageMap<_, Twox64Concat, u32, u32, ValueQuery>;
+
+	// Block from which each root is accepted, by remote domain and enrollment.
+	#[pallet::storage]
+	#[pallet::getter(fn confirm_at)]
+	pub type ConfirmAt<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, (u32, u32), Twox64Concat, H256, BlockNumberFor<T>>;
+
+	// Message hash to message status
+	#[pallet::storage]
+	#[pallet::getter(fn message_status)]
+	pub type Messages<T> = StorageMap<_, Twox64Concat, H256, MessageStatus>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub (super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		ReplicaEnrolled {
+			remote_domain: u32,
+			updater: H160,
+			committed_root: H256,
+		},
+		NewUpdater {
+			remote_domain: u32,
+			old_updater: H160,
+			new_updater: H160,
+		},
+		Update {
+			home_domain: u32,
+			previous_root: H256,
+			new_root: H256,
+			signature: Vec<u8>,
+		},
+		DoubleUpdate {
+			home_domain: u32,
+			previous_root: H256,
+			first_root: H256,
+			second_root: H256,
+		},
+		Proven {
+			remote_domain: u32,
+			message_hash: H256,
+			leaf_index: u32,
+		},
+		Processed {
+			message_hash: H256,
+			result: DispatchResult,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		ReplicaNotEnrolled,
+		FailedState,
+		SignatureRecoveryError,
+		InvalidUpdaterSignature,
+		CommittedRootNotMatchUpdatePrevious,
+		NotDoubleUpdate,
+		RootNotAcceptable,
+		InvalidProof,
+		MessageAlreadyProven,
+		InvalidMessage,
+		WrongDestination,
+		MessageNotProven,
+		/// The message was proven against a root of the replica of another domain than its
+		/// origin.
+		WrongOrigin,
+		/// No handler of the recipient of the message.
+		NoHandler,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Submit a signed update of a remote home. Its new root is accepted after
+		/// `OptimisticPeriod` blocks.
+		#[pallet::call_index(0)]
+		#[pallet::weight(placeholder_weights::update(T::DbWeight::get()))]
+		pub fn update(origin: OriginFor<T>, signed_update: SignedUpdate) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let home_domain = signed_update.update.home_domain;
+			let mut replica = Self::active_replica(home_domain)?;
+			ensure!(
+				replica.committed_root == signed_update.previous_root(),
+				Error::<T>::CommittedRootNotMatchUpdatePrevious,
+			);
+			Self::ensure_updater_signature(&replica, &signed_update)?;
+
+			let confirm_at = <frame_system::Pallet<T>>::block_number()
+				.saturating_add(T::OptimisticPeriod::get());
+			ConfirmAt::<T>::insert(
+				(home_domain, Self::enrollments(home_domain)),
+				signed_update.new_root(),
+				confirm_at,
+			);
+			replica.set_committed_root(signed_update.new_root());
+			Replicas::<T>::insert(home_domain, replica);
+
+			Self::deposit_event(Event::<T>::Update {
+				home_domain,
+				previous_root: signed_update.previous_root(),
+				new_root: signed_update.new_root(),
+				signature: signed_update.signature.to_vec(),
+			});
+			Ok(())
+		}
+
+		/// Prove that the updater of a remote home signed two different updates of the same
+		/// root, which fails its replica.
+		#[pallet::call_index(1)]
+		#[pallet::weight(placeholder_weights::double_update(T::DbWeight::get()))]
+		pub fn double_update(
+			origin: OriginFor<T>,
+			first: Box<SignedUpdate>,
+			second: Box<SignedUpdate>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let home_domain = first.update.home_domain;
+			ensure!(
+				home_domain == second.update.home_domain
+					&& first.previous_root() == second.previous_root()
+					&& first.new_root() != second.new_root(),
+				Error::<T>::NotDoubleUpdate
+			);
+			let mut replica = Self::active_replica(home_domain)?;
+			Self::ensure_updater_signature(&replica, &first)?;
+			Self::ensure_updater_signature(&replica, &second)?;
+
+			replica.state = NomadState::Failed;
+			Replicas::<T>::insert(home_domain, replica);
+
+			Self::deposit_event(Event::<T>::DoubleUpdate {
+				home_domain,
+				previous_root: first.previous_root(),
+				first_root: first.new_root(),
+				second_root: second.new_root(),
+			});
+			Ok(())
+		}
+
+		/// Prove a message of `remote_domain` against an accepted root of its replica.
+		#[pallet::call_index(2)]
+		#[pallet::weight(placeholder_weights::prove(T::DbWeight::get()))]
+		pub fn prove(
+			origin: OriginFor<T>,
+			#[pallet::compact] remote_domain: u32,
+			proof: Box<MessageProof>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			Self::active_replica(remote_domain)?;
+			ensure!(
+				Self::is_acceptable_root(remote_domain, proof.root),
+				Error::<T>::RootNotAcceptable
+			);
+			ensure!(proof.verify(), Error::<T>::InvalidProof);
+			ensure!(
+				!Messages::<T>::contains_key(proof.leaf),
+				Error::<T>::MessageAlreadyProven
+			);
+
+			Messages::<T>::insert(proof.leaf, MessageStatus::Proven { remote_domain });
+			Self::deposit_event(Event::<T>::Proven {
+				remote_domain,
+				message_hash: proof.leaf,
+				leaf_index: proof.index,
+			});
+			Ok(())
+		}
+
+		/// Process a proven message, handing it to the `MessageHandler` of its recipient.
+		///
+		/// The message is processed even if its handling fails, and the changes of the handler
+		/// are reverted.
+		#[pallet::call_index(3)]
+		#[pallet::weight(
+			placeholder_weights::process(T::DbWeight::get(), message.len() as u32)
+				.saturating_add(T::MaxHandlerWeight::get())
+		)]
+		pub fn process(
+			origin: OriginFor<T>,
+			message: BoundedVec<u8, T::MaxMessageBytes>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let message =
+				decode_message::<T::MaxMessageBytes>(&message).ok_or(Error::<T>::InvalidMessage)?;
+			ensure!(
+				message.destination == T::LocalDomain::get(),
+				Error::<T>::WrongDestination
+			);
+			Self::active_replica(message.origin)?;
+
+			// The message must be proven against a root of the replica of its origin, or any
+			// replica could forge the messages of another domain.
+			let message_hash = message.hash();
+			let Some(MessageStatus::Proven { remote_domain }) = Self::message_status(message_hash)
+			else {
+				return Err(Error::<T>::MessageNotProven.into());
+			};
+			ensure!(remote_domain == message.origin, Error::<T>::WrongOrigin);
+			Messages::<T>::insert(message_hash, MessageStatus::Processed);
+
+			let result = with_storage_layer(|| {
+				T::MessageHandler::handle(
+					message.origin,
+					message.nonce,
+					message.sender,
+					message.recipient,
+					&message.body,
+				)
+				.unwrap_or_else(|| Err(Error::<T>::NoHandler.into()))
+			});
+			Self::deposit_event(Event::<T>::Processed {
+				message_hash,
+				result,
+			});
+			Ok(())
+		}
+
+		/// Enroll the replica of the home of `remote_domain`, accepting `committed_root`.
+		///
+		/// Enrolling an enrolled replica resets it, and the roots accepted until then are no
+		/// longer accepted. It is the way to recover a failed replica.
+		#[pallet::call_index(4)]
+		#[pallet::weight(placeholder_weights::enroll_replica(T::DbWeight::get()))]
+		pub fn enroll_replica(
+			origin: OriginFor<T>,
+			#[pallet::compact] remote_domain: u32,
+			updater: H160,
+			committed_root: H256,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			let enrollment = Enrollments::<T>::mutate(remote_domain, |enrollments| {
+				*enrollments = enrollments.saturating_add(1);
+				*enrollments
+			});
+			ConfirmAt::<T>::insert(
+				(remote_domain, enrollment),
+				committed_root,
+				BlockNumberFor::<T>::zero(),
+			);
+			Replicas::<T>::insert(
+				remote_domain,
+				NomadBase::new(remote_domain, committed_root, updater),
+			);
+
+			Self::deposit_event(Event::<T>::ReplicaEnrolled {
+				remote_domain,
+				updater,
+				committed_root,
+			});
+			Ok(())
+		}
+
+		/// Set the updater of the replica of `remote_domain`.
+		#[pallet::call_index(5)]
+		#[pallet::weight(placeholder_weights::set_updater(T::DbWeight::get()))]
+		pub fn set_updater(
+			origin: OriginFor<T>,
+			#[pallet::compact] remote_domain: u32,
+			new_updater: H160,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			let old_updater = Replicas::<T>::try_mutate(remote_domain, |replica| {
+				let replica = replica.as_mut().ok_or(Error::<T>::ReplicaNotEnrolled)?;
+				Ok::<_, Error<T>>(mem::replace(&mut replica.updater, new_updater))
+			})?;
+
+			Self::deposit_event(Event::<T>::NewUpdater {
+				remote_domain,
+				old_updater,
+				new_updater,
+			});
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Returns the replica of `remote_domain`, if it is enrolled and not failed.
+		fn active_replica(remote_domain: u32) -> Result<NomadBase, Error<T>> {
+			let replica = Self::replica(remote_domain).ok_or(Error::<T>::ReplicaNotEnrolled)?;
+			ensure!(replica.state != NomadState::Failed, Error::<T>::FailedState);
+			Ok(replica)
+		}
+
+		fn ensure_updater_signature(
+			replica: &NomadBase,
+			signed_update: &SignedUpdate,
+		) -> Result<(), Error<T>> {
+			ensure!(
+				replica
+					.is_updater_signature(signed_update)
+					.map_err(|_| Error::<T>::SignatureRecoveryError)?,
+				Error::<T>::InvalidUpdaterSignature,
+			);
+			Ok(())
+		}
+
+		/// Returns `true` if messages of `remote_domain` can be proven against `root`, which
+		/// is when its optimistic period is over.
+		pub fn is_acceptable_root(remote_domain: u32, root: H256) -> bool {
+			let enrollment = (remote_domain, Self::enrollments(remote_domain));
+			Self::confirm_at(enrollment, root).map_or(false, |confirm_at| {
+				<frame_system::Pallet<T>>::block_number() >= confirm_at
+			})
+		}
+	}
+}
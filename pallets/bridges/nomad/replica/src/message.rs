@@ -0,0 +1,56 @@
+use core::convert::TryInto;
+
+use nomad_core::NomadMessage;
+use sp_core::{Get, H256};
+
+/// Length of the header of a Nomad message: origin, sender, nonce, destination and recipient.
+pub const MESSAGE_HEADER_LEN: usize = 76;
+
+/// Decodes a message formatted by a Nomad home, as `NomadMessage::to_vec` does.
+///
+/// Returns `None` if `bytes` is shorter than the header, or if the body is longer than `S`.
+pub fn decode_message<S: Get<u32>>(bytes: &[u8]) -> Option<NomadMessage<S>> {
+	if bytes.len() < MESSAGE_HEADER_LEN {
+		return None;
+	}
+	let (header, body) = bytes.split_at(MESSAGE_HEADER_LEN);
+	let u32_at = |at: usize| header[at..at + 4].try_into().ok().map(u32::from_be_bytes);
+	let h256_at = |at: usize| H256::from_slice(&header[at..at + 32]);
+
+	Some(NomadMessage {
+		origin: u32_at(0)?,
+		sender: h256_at(4),
+		nonce: u32_at(36)?,
+		destination: u32_at(40)?,
+		recipient: h256_at(44),
+		body: body.to_vec().try_into().ok()?,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use frame_support::traits::ConstU32;
+
+	use super::*;
+
+	#[test]
+	fn decodes_formatted_messages() {
+		let message = NomadMessage::<ConstU32<8>> {
+			origin: 1000,
+			sender: H256::repeat_byte(1),
+			nonce: 7,
+			destination: 2000,
+			recipient: H256::repeat_byte(2),
+			body: [3u8; 8].to_vec().try_into().unwrap(),
+		};
+		let bytes = message.to_vec();
+
+		let decoded = decode_message::<ConstU32<8>>(&bytes).unwrap();
+		assert_eq!(decoded.to_vec(), bytes);
+		assert_eq!(decoded.hash(), message.hash());
+
+		// Body too long, and truncated header.
+		assert!(decode_message::<ConstU32<7>>(&bytes).is_none());
+		assert!(decode_message::<ConstU32<8>>(&bytes[..MESSAGE_HEADER_LEN - 1]).is_none());
+	}
+}
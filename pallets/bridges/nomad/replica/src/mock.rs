@@ -0,0 +1,111 @@
+use frame_support::{derive_impl, ensure, parameter_types, traits::ConstU32};
+use frame_system::{self as system, header_builder::da, test_utils::TestRandomness};
+use sp_core::H256;
+use sp_runtime::{
+	traits::IdentityLookup, AccountId32, BuildStorage, DispatchError, DispatchResult,
+};
+
+use crate::{self as replica, HandleMessage};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockDaBlock<Test>;
+
+pub(crate) const LOCAL_DOMAIN: u32 = 2000;
+pub(crate) const OPTIMISTIC_PERIOD: u32 = 10;
+
+/// Recipient handled by `TestHandler`.
+pub(crate) const HANDLED_RECIPIENT: H256 = H256::repeat_byte(3);
+/// Storage key where `TestHandler` writes the last handled body.
+pub(crate) const HANDLED_BODY_KEY: &[u8] = b":handled_body";
+/// Body whose handling fails.
+pub(crate) const FAILING_BODY: &[u8] = b"fail";
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub struct Test {
+		System: frame_system,
+		Replica: replica,
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u32 = 250;
+	/// Local domain of the home, which governance can change.
+	pub static LocalDomain: u32 = LOCAL_DOMAIN;
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl system::Config for Test {
+	type AccountId = AccountId32;
+	type BaseCallFilter = frame_support::traits::Everything;
+	type Block = Block;
+	type BlockHashCount = BlockHashCount;
+	type HeaderExtensionBuilder = da::HeaderExtensionBuilder<Test>;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type OnSetCode = ();
+	type PalletInfo = PalletInfo;
+	type Randomness = TestRandomness<Test>;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeOrigin = RuntimeOrigin;
+	type SubmittedDataExtractor = ();
+	type UncheckedExtrinsic = UncheckedExtrinsic;
+}
+
+/// Writes the body of the messages to `HANDLED_RECIPIENT` into `HANDLED_BODY_KEY`, and fails
+/// if it is `FAILING_BODY`.
+pub(crate) struct TestHandler;
+
+impl HandleMessage for TestHandler {
+	fn handle(
+		_origin: u32,
+		_nonce: u32,
+		_sender: H256,
+		recipient: H256,
+		body: &[u8],
+	) -> Option<DispatchResult> {
+		(recipient == HANDLED_RECIPIENT).then(|| {
+			sp_io::storage::set(HANDLED_BODY_KEY, body);
+			ensure!(body != FAILING_BODY, DispatchError::Unavailable);
+			Ok(())
+		})
+	}
+}
+
+#[derive_impl(replica::config_preludes::TestDefaultConfig as replica::DefaultConfig)]
+impl replica::Config for Test {
+	type LocalDomain = LocalDomain;
+	type MessageHandler = TestHandler;
+	type OptimisticPeriod = ConstU32<OPTIMISTIC_PERIOD>;
+	type RuntimeEvent = RuntimeEvent;
+}
+
+#[derive(Default)]
+pub(crate) struct ExtBuilder;
+
+impl ExtBuilder {
+	pub(crate) fn build(self) -> sp_io::TestExternalities {
+		let t = RuntimeGenesisConfig::default()
+			.system
+			.build_storage()
+			.expect("Genesis build should work");
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
+
+pub(crate) fn events() -> Vec<super::Event<Test>> {
+	System::events()
+		.into_iter()
+		.map(|r| r.event)
+		.filter_map(|e| {
+			if let RuntimeEvent::Replica(inner) = e {
+				Some(inner)
+			} else {
+				None
+			}
+		})
+		.collect::<Vec<_>>()
+}
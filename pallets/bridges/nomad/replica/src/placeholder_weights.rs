@@ -0,0 +1,41 @@
+//! Placeholder weights for `nomad_replica`.
+//!
+//! NOT GENERATED: the benchmarks of `benchmarking.rs` have not been run on reference hardware,
+//! so the estimates below stand in for them. Recovering the updater of a signed update is the
+//! main cost of `update` and `double_update`, which recovers two. The weight of `process`
+//! excludes the handler of the message, whose `MaxHandlerWeight` the call adds.
+
+use frame_support::weights::{RuntimeDbWeight, Weight};
+
+// Replicas (r:1 w:1), Enrollments (r:1), ConfirmAt (w:1)
+pub fn update(db: RuntimeDbWeight) -> Weight {
+	Weight::from_parts(520_000_000, 3534).saturating_add(db.reads_writes(2, 2))
+}
+
+// Replicas (r:1 w:1)
+pub fn double_update(db: RuntimeDbWeight) -> Weight {
+	Weight::from_parts(1_030_000_000, 3534).saturating_add(db.reads_writes(1, 1))
+}
+
+// Replicas (r:1), Enrollments (r:1), ConfirmAt (r:1), Messages (r:1 w:1)
+pub fn prove(db: RuntimeDbWeight) -> Weight {
+	Weight::from_parts(120_000_000, 3534).saturating_add(db.reads_writes(4, 1))
+}
+
+// NomadHome::Base (r:1), Replicas (r:1), Messages (r:1 w:1), plus hashing and decoding the
+// `b` bytes of the message
+pub fn process(db: RuntimeDbWeight, b: u32) -> Weight {
+	Weight::from_parts(40_000_000, 3534)
+		.saturating_add(Weight::from_parts(8_000, 0).saturating_mul(b.into()))
+		.saturating_add(db.reads_writes(3, 1))
+}
+
+// Enrollments (r:1 w:1), Replicas (w:1), ConfirmAt (w:1)
+pub fn enroll_replica(db: RuntimeDbWeight) -> Weight {
+	Weight::from_parts(20_000_000, 3481).saturating_add(db.reads_writes(1, 3))
+}
+
+// Replicas (r:1 w:1)
+pub fn set_updater(db: RuntimeDbWeight) -> Weight {
+	Weight::from_parts(18_000_000, 3534).saturating_add(db.reads_writes(1, 1))
+}
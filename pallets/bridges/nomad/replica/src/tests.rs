@@ -0,0 +1,402 @@
+use frame_support::{assert_err, assert_noop, assert_ok};
+use nomad_base::testing::{TEST_LOCAL_DOMAIN as REMOTE_DOMAIN, *};
+use nomad_core::{NomadMessage, NomadState};
+use nomad_home::MessageProof;
+use sp_core::{ConstU32, H256};
+use sp_runtime::{AccountId32, DispatchError};
+
+use crate::{mock::*, Error, MessageStatus};
+
+const TEST_SENDER: H256 = H256::repeat_byte(2);
+const RELAYER: AccountId32 = AccountId32::new([1u8; 32]);
+
+fn relayer() -> RuntimeOrigin {
+	RuntimeOrigin::signed(RELAYER)
+}
+
+/// Formats a message of the remote domain to `destination`.
+fn message(nonce: u32, destination: u32, recipient: H256, body: &[u8]) -> Vec<u8> {
+	NomadMessage::<ConstU32<64>> {
+		origin: REMOTE_DOMAIN,
+		sender: TEST_SENDER,
+		nonce,
+		destination,
+		recipient,
+		body: body.to_vec().try_into().unwrap(),
+	}
+	.to_vec()
+}
+
+fn message_hash(message: &[u8]) -> H256 {
+	crate::message::decode_message::<ConstU32<64>>(message)
+		.unwrap()
+		.hash()
+}
+
+fn enroll(committed_root: H256) {
+	assert_ok!(Replica::enroll_replica(
+		RuntimeOrigin::root(),
+		REMOTE_DOMAIN,
+		TEST_UPDATER.address(),
+		committed_root
+	));
+}
+
+/// Submits an update of the committed root to the root of the tree of `messages`, waits for
+/// the optimistic period, and returns the proofs of `messages`.
+fn accept(messages: &[Vec<u8>]) -> Vec<MessageProof> {
+	let leaves = messages.iter().map(|m| message_hash(m)).collect::<Vec<_>>();
+	let proofs = (0..leaves.len() as u32)
+		.map(|index| MessageProof::new(&leaves, index).unwrap())
+		.collect::<Vec<_>>();
+
+	let committed_root = Replica::replica(REMOTE_DOMAIN).unwrap().committed_root;
+	let signed_update = TEST_UPDATER.sign_update(committed_root, proofs[0].root);
+	assert_ok!(Replica::update(relayer(), signed_update));
+	System::set_block_number(System::block_number() + OPTIMISTIC_PERIOD);
+
+	proofs
+}
+
+fn prove_and_process(proof: MessageProof, message: Vec<u8>) {
+	assert_ok!(Replica::prove(relayer(), REMOTE_DOMAIN, Box::new(proof)));
+	assert_ok!(Replica::process(relayer(), message.try_into().unwrap()));
+}
+
+#[test]
+fn it_enrolls_replicas() {
+	ExtBuilder::default().build().execute_with(|| {
+		let committed_root = H256::repeat_byte(1);
+		assert_noop!(
+			Replica::enroll_replica(
+				relayer(),
+				REMOTE_DOMAIN,
+				TEST_UPDATER.address(),
+				committed_root
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Replica::set_updater(RuntimeOrigin::root(), REMOTE_DOMAIN, FAKE_UPDATER.address()),
+			Error::<Test>::ReplicaNotEnrolled
+		);
+
+		enroll(committed_root);
+		let replica = Replica::replica(REMOTE_DOMAIN).unwrap();
+		assert!(replica.state == NomadState::Active);
+		assert_eq!(replica.committed_root, committed_root);
+		assert_eq!(replica.updater, TEST_UPDATER.address());
+		assert!(Replica::is_acceptable_root(REMOTE_DOMAIN, committed_root));
+
+		assert_ok!(Replica::set_updater(
+			RuntimeOrigin::root(),
+			REMOTE_DOMAIN,
+			FAKE_UPDATER.address()
+		));
+		assert_eq!(
+			Replica::replica(REMOTE_DOMAIN).unwrap().updater,
+			FAKE_UPDATER.address()
+		);
+
+		let expected = vec![
+			crate::Event::ReplicaEnrolled {
+				remote_domain: REMOTE_DOMAIN,
+				updater: TEST_UPDATER.address(),
+				committed_root,
+			},
+			crate::Event::NewUpdater {
+				remote_domain: REMOTE_DOMAIN,
+				old_updater: TEST_UPDATER.address(),
+				new_updater: FAKE_UPDATER.address(),
+			},
+		];
+		assert_eq!(events(), expected);
+	})
+}
+
+#[test]
+fn it_accepts_roots_after_optimistic_period() {
+	ExtBuilder::default().build().execute_with(|| {
+		let committed_root = H256::zero();
+		enroll(committed_root);
+
+		let message = message(0, LOCAL_DOMAIN, HANDLED_RECIPIENT, b"body");
+		let proof = MessageProof::new(&[message_hash(&message)], 0).unwrap();
+		let signed_update = TEST_UPDATER.sign_update(committed_root, proof.root);
+		assert_ok!(Replica::update(relayer(), signed_update.clone()));
+		assert_eq!(
+			Replica::replica(REMOTE_DOMAIN).unwrap().committed_root,
+			proof.root
+		);
+
+		// The root is accepted once the optimistic period is over.
+		System::set_block_number(OPTIMISTIC_PERIOD);
+		assert!(!Replica::is_acceptable_root(REMOTE_DOMAIN, proof.root));
+		assert_noop!(
+			Replica::prove(relayer(), REMOTE_DOMAIN, Box::new(proof.clone())),
+			Error::<Test>::RootNotAcceptable
+		);
+		System::set_block_number(1 + OPTIMISTIC_PERIOD);
+		assert_ok!(Replica::prove(
+			relayer(),
+			REMOTE_DOMAIN,
+			Box::new(proof.clone())
+		));
+		assert_eq!(
+			Replica::message_status(proof.leaf),
+			Some(MessageStatus::Proven {
+				remote_domain: REMOTE_DOMAIN
+			})
+		);
+		assert_noop!(
+			Replica::prove(relayer(), REMOTE_DOMAIN, Box::new(proof.clone())),
+			Error::<Test>::MessageAlreadyProven
+		);
+
+		assert_eq!(
+			events()[1..],
+			[
+				crate::Event::Update {
+					home_domain: REMOTE_DOMAIN,
+					previous_root: committed_root,
+					new_root: proof.root,
+					signature: signed_update.signature.to_vec(),
+				},
+				crate::Event::Proven {
+					remote_domain: REMOTE_DOMAIN,
+					message_hash: proof.leaf,
+					leaf_index: 0,
+				},
+			]
+		);
+	})
+}
+
+#[test]
+fn it_rejects_invalid_updates_and_proofs() {
+	ExtBuilder::default().build().execute_with(|| {
+		let committed_root = H256::zero();
+		let new_root = H256::repeat_byte(1);
+		let signed_update = TEST_UPDATER.sign_update(committed_root, new_root);
+		assert_noop!(
+			Replica::update(relayer(), signed_update.clone()),
+			Error::<Test>::ReplicaNotEnrolled
+		);
+
+		enroll(committed_root);
+		assert_noop!(
+			Replica::update(
+				relayer(),
+				FAKE_UPDATER.sign_update(committed_root, new_root)
+			),
+			Error::<Test>::InvalidUpdaterSignature
+		);
+		assert_noop!(
+			Replica::update(relayer(), TEST_UPDATER.sign_update(new_root, new_root)),
+			Error::<Test>::CommittedRootNotMatchUpdatePrevious
+		);
+
+		// Proofs must link the message to the root.
+		let message = message(0, LOCAL_DOMAIN, HANDLED_RECIPIENT, b"body");
+		let mut proof = accept(&[message]).remove(0);
+		proof.leaf = H256::repeat_byte(4);
+		assert_noop!(
+			Replica::prove(relayer(), REMOTE_DOMAIN, Box::new(proof)),
+			Error::<Test>::InvalidProof
+		);
+	})
+}
+
+#[test]
+fn it_processes_proven_messages() {
+	ExtBuilder::default().build().execute_with(|| {
+		enroll(H256::zero());
+		let messages = vec![
+			message(0, LOCAL_DOMAIN, HANDLED_RECIPIENT, b"body"),
+			message(1, LOCAL_DOMAIN, HANDLED_RECIPIENT, FAILING_BODY),
+			message(2, LOCAL_DOMAIN, H256::repeat_byte(4), b"body"),
+			message(3, LOCAL_DOMAIN + 1, HANDLED_RECIPIENT, b"body"),
+		];
+		let mut proofs = accept(&messages);
+
+		// Messages must be proven before being processed, and only once.
+		assert_noop!(
+			Replica::process(relayer(), messages[0].clone().try_into().unwrap()),
+			Error::<Test>::MessageNotProven
+		);
+		prove_and_process(proofs.remove(0), messages[0].clone());
+		assert_eq!(
+			sp_io::storage::get(HANDLED_BODY_KEY).as_deref(),
+			Some(&b"body"[..])
+		);
+		assert_eq!(
+			Replica::message_status(message_hash(&messages[0])),
+			Some(MessageStatus::Processed)
+		);
+		assert_noop!(
+			Replica::process(relayer(), messages[0].clone().try_into().unwrap()),
+			Error::<Test>::MessageNotProven
+		);
+
+		// Failed handlings are reverted, and messages without handler are processed as well.
+		prove_and_process(proofs.remove(0), messages[1].clone());
+		assert_eq!(
+			sp_io::storage::get(HANDLED_BODY_KEY).as_deref(),
+			Some(&b"body"[..])
+		);
+		prove_and_process(proofs.remove(0), messages[2].clone());
+
+		// Messages to other domains cannot be processed.
+		assert_ok!(Replica::prove(
+			relayer(),
+			REMOTE_DOMAIN,
+			Box::new(proofs.remove(0))
+		));
+		assert_noop!(
+			Replica::process(relayer(), messages[3].clone().try_into().unwrap()),
+			Error::<Test>::WrongDestination
+		);
+		assert_noop!(
+			Replica::process(relayer(), vec![0u8; 75].try_into().unwrap()),
+			Error::<Test>::InvalidMessage
+		);
+
+		let processed = events()
+			.into_iter()
+			.filter_map(|event| match event {
+				crate::Event::Processed {
+					message_hash,
+					result,
+				} => Some((message_hash, result)),
+				_ => None,
+			})
+			.collect::<Vec<_>>();
+		let expected = vec![
+			(message_hash(&messages[0]), Ok(())),
+			(message_hash(&messages[1]), Err(DispatchError::Unavailable)),
+			(
+				message_hash(&messages[2]),
+				Err(Error::<Test>::NoHandler.into()),
+			),
+		];
+		assert_eq!(processed, expected);
+	})
+}
+
+#[test]
+fn it_processes_messages_to_the_local_domain_of_the_home() {
+	// No genesis, as when the replica is added by a runtime upgrade.
+	sp_io::TestExternalities::default().execute_with(|| {
+		System::set_block_number(1);
+		enroll(H256::zero());
+		let messages = vec![
+			message(0, LOCAL_DOMAIN, HANDLED_RECIPIENT, b"body"),
+			message(1, LOCAL_DOMAIN + 1, HANDLED_RECIPIENT, b"moved"),
+		];
+		let mut proofs = accept(&messages);
+		prove_and_process(proofs.remove(0), messages[0].clone());
+		assert_eq!(
+			sp_io::storage::get(HANDLED_BODY_KEY).as_deref(),
+			Some(&b"body"[..])
+		);
+
+		// The home moves to another domain, e.g. through a `SetLocalDomain` governance message.
+		LocalDomain::set(LOCAL_DOMAIN + 1);
+		prove_and_process(proofs.remove(0), messages[1].clone());
+		assert_eq!(
+			sp_io::storage::get(HANDLED_BODY_KEY).as_deref(),
+			Some(&b"moved"[..])
+		);
+	})
+}
+
+#[test]
+fn it_processes_messages_only_from_the_domain_they_were_proven_for() {
+	ExtBuilder::default().build().execute_with(|| {
+		// The home of another domain commits a message forged as one of `REMOTE_DOMAIN`, e.g. a
+		// governance message.
+		let forged = message(0, LOCAL_DOMAIN, HANDLED_RECIPIENT, b"forged");
+		let proof = MessageProof::new(&[message_hash(&forged)], 0).unwrap();
+		assert_ok!(Replica::enroll_replica(
+			RuntimeOrigin::root(),
+			REMOTE_DOMAIN + 1,
+			TEST_UPDATER.address(),
+			proof.root
+		));
+
+		assert_ok!(Replica::prove(
+			relayer(),
+			REMOTE_DOMAIN + 1,
+			Box::new(proof)
+		));
+		assert_eq!(
+			Replica::message_status(message_hash(&forged)),
+			Some(MessageStatus::Proven {
+				remote_domain: REMOTE_DOMAIN + 1
+			})
+		);
+		assert_noop!(
+			Replica::process(relayer(), forged.try_into().unwrap()),
+			Error::<Test>::WrongOrigin
+		);
+		assert_eq!(sp_io::storage::get(HANDLED_BODY_KEY), None);
+	})
+}
+
+#[test]
+fn it_fails_replica_on_double_update() {
+	ExtBuilder::default().build().execute_with(|| {
+		let committed_root = H256::zero();
+		enroll(committed_root);
+		let message = message(0, LOCAL_DOMAIN, HANDLED_RECIPIENT, b"body");
+		let proof = accept(&[message.clone()]).remove(0);
+
+		// Updates of different roots are required.
+		let first = TEST_UPDATER.sign_update(committed_root, proof.root);
+		let second = TEST_UPDATER.sign_update(committed_root, H256::repeat_byte(1));
+		assert_noop!(
+			Replica::double_update(relayer(), Box::new(first.clone()), Box::new(first.clone())),
+			Error::<Test>::NotDoubleUpdate
+		);
+		assert_noop!(
+			Replica::double_update(
+				relayer(),
+				Box::new(first.clone()),
+				Box::new(FAKE_UPDATER.sign_update(committed_root, H256::repeat_byte(1)))
+			),
+			Error::<Test>::InvalidUpdaterSignature
+		);
+
+		assert_ok!(Replica::double_update(
+			relayer(),
+			Box::new(first),
+			Box::new(second)
+		));
+		assert!(Replica::replica(REMOTE_DOMAIN).unwrap().state == NomadState::Failed);
+		assert_eq!(
+			events().last(),
+			Some(&crate::Event::DoubleUpdate {
+				home_domain: REMOTE_DOMAIN,
+				previous_root: committed_root,
+				first_root: proof.root,
+				second_root: H256::repeat_byte(1),
+			})
+		);
+
+		// A failed replica neither proves nor processes messages.
+		assert_noop!(
+			Replica::prove(relayer(), REMOTE_DOMAIN, Box::new(proof.clone())),
+			Error::<Test>::FailedState
+		);
+
+		// Enrolling it again recovers it, without the roots accepted until then.
+		enroll(committed_root);
+		assert!(Replica::replica(REMOTE_DOMAIN).unwrap().state == NomadState::Active);
+		assert!(!Replica::is_acceptable_root(REMOTE_DOMAIN, proof.root));
+		assert_err!(
+			Replica::prove(relayer(), REMOTE_DOMAIN, Box::new(proof)),
+			Error::<Test>::RootNotAcceptable
+		);
+		prove_and_process(accept(&[message.clone()]).remove(0), message);
+	})
+}
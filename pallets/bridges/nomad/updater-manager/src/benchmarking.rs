@@ -1,20 +1,57 @@
-// //! Benchmarking setup for pallet-template
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::traits::{Currency, Get};
+use frame_system::RawOrigin;
+use sp_core::{crypto::KeyTypeId, ecdsa, H160};
+use sp_runtime::traits::{Bounded, Saturating};
 
-// use super::*;
+use crate::*;
 
-// #[allow(unused)]
-// use crate::Pallet as Template;
-// use frame_benchmarking::{benchmarks, whitelisted_caller};
-// use frame_system::RawOrigin;
+const UPDATER: H160 = H160::repeat_byte(1);
+const UPDATER_KEY_TYPE: KeyTypeId = KeyTypeId(*b"nmdu");
 
-// benchmarks! {
-// 	do_something {
-// 		let s in 0 .. 100;
-// 		let caller: T::AccountId = whitelisted_caller();
-// 	}: _(RawOrigin::Signed(caller), s)
-// 	verify {
-// 		assert_eq!(Something::<T>::get(), Some(s));
-// 	}
+/// Funds the whitelisted caller and bonds `UPDATER` with it.
+fn bonded_caller<T: Config>() -> T::AccountId {
+	let caller = whitelisted_caller::<T::AccountId>();
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+	Pallet::<T>::do_bond(caller.clone(), UPDATER).expect("Caller is funded .qed");
+	caller
+}
 
-// 	impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
-// }
+/// Generates an updater key and signs the bond of `owner` with it.
+fn signed_bond<T: Config>(owner: &T::AccountId) -> (H160, ecdsa::Signature) {
+	let public = sp_io::crypto::ecdsa_generate(UPDATER_KEY_TYPE, None);
+	let hash = Pallet::<T>::bond_message_hash(owner);
+	let signature = sp_io::crypto::ecdsa_sign_prehashed(UPDATER_KEY_TYPE, &public, &hash)
+		.expect("Key was generated .qed");
+	let updater = Pallet::<T>::recover_signer(&signature, &hash).expect("Signature is valid .qed");
+	(updater, signature)
+}
+
+benchmarks! {
+	bond {
+		let caller = whitelisted_caller::<T::AccountId>();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+		let (updater, signature) = signed_bond::<T>(&caller);
+	}: _(RawOrigin::Signed(caller), updater, signature)
+	verify {
+		assert!(Bonds::<T>::contains_key(updater));
+	}
+
+	unbond {
+		let caller = bonded_caller::<T>();
+	}: _(RawOrigin::Signed(caller), UPDATER)
+	verify {
+		assert!(Bonds::<T>::get(UPDATER).and_then(|bond| bond.unlock_at).is_some());
+	}
+
+	withdraw_unbonded {
+		let caller = bonded_caller::<T>();
+		Pallet::<T>::unbond(RawOrigin::Signed(caller.clone()).into(), UPDATER)?;
+		let unlock_at = frame_system::Pallet::<T>::block_number()
+			.saturating_add(T::UnbondingDelay::get());
+		frame_system::Pallet::<T>::set_block_number(unlock_at);
+	}: _(RawOrigin::Signed(caller), UPDATER)
+	verify {
+		assert!(!Bonds::<T>::contains_key(UPDATER));
+	}
+}
@@ -13,26 +13,84 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod placeholder_weights;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use frame_support::{
 		pallet_prelude::{ValueQuery, *},
+		sp_std::vec::Vec,
+		traits::{Currency, Imbalance, OnUnbalanced, ReservableCurrency},
 		DefaultNoBound,
 	};
-	use sp_core::H160;
+	use frame_system::pallet_prelude::*;
+	use sp_core::{ecdsa, H160};
+	use sp_io::hashing::keccak_256;
+	use sp_runtime::{
+		traits::{Saturating, Zero},
+		Perbill,
+	};
+
+	use super::placeholder_weights;
+
+	/// Prefix of the message signed by an updater key to be bonded, see `bond_message_hash`.
+	pub const BOND_MESSAGE_PREFIX: &[u8] = b"nomad-updater-bond";
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::NegativeImbalance;
+
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Currency of the updater bonds.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// Amount reserved by a bond, which is required to be set as the updater.
+		#[pallet::constant]
+		type UpdaterBond: Get<BalanceOf<Self>>;
+
+		/// Share of a slashed bond rewarded to the reporter of the fraud.
+		#[pallet::constant]
+		type SlashRewardShare: Get<Perbill>;
+
+		/// Handler for the rest of the slashed bonds.
+		type Slashed: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Number of blocks between the unbonding of a bond and its withdrawal.
+		#[pallet::constant]
+		type UnbondingDelay: Get<BlockNumberFor<Self>>;
 	}
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
+	/// Funds reserved for an updater.
+	#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+	pub struct Bond<AccountId, Balance, BlockNumber> {
+		/// Account whose funds are reserved.
+		pub owner: AccountId,
+		pub amount: Balance,
+		/// Block from which the bond can be withdrawn, once it is unbonding.
+		pub unlock_at: Option<BlockNumber>,
+	}
+
+	pub type BondOf<T> =
+		Bond<<T as frame_system::Config>::AccountId, BalanceOf<T>, BlockNumberFor<T>>;
+
 	// Updater
 	#[pallet::storage]
 	#[pallet::getter(fn updater)]
 	pub type Updater<T> = StorageValue<_, H160, ValueQuery>;
 
+	// Updater to its bond
+	#[pallet::storage]
+	#[pallet::getter(fn bond_of)]
+	pub type Bonds<T: Config> = StorageMap<_, Blake2_128Concat, H160, BondOf<T>>;
+
 	// Genesis config
 	#[pallet::genesis_config]
 	#[derive(DefaultNoBound)]
@@ -55,27 +113,172 @@ pub mod pallet {
 			old_updater: H160,
 			new_updater: H160,
 		},
-		FakeSlashed {
+		Bonded {
+			updater: H160,
+			owner: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		Unbonding {
+			updater: H160,
+			unlock_at: BlockNumberFor<T>,
+		},
+		Withdrawn {
+			updater: H160,
+			owner: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		Slashed {
+			updater: H160,
 			reporter: T::AccountId,
+			amount: BalanceOf<T>,
+			reward: BalanceOf<T>,
 		},
 	}
 
 	#[pallet::error]
 	pub enum Error<T> {
 		InitializationError,
+		AlreadyBonded,
+		NotBonded,
+		NotBondOwner,
+		UpdaterNotBonded,
+		ActiveUpdater,
+		AlreadyUnbonding,
+		NotUnbonding,
+		BondStillLocked,
+		InvalidBondSignature,
 	}
 
-	// No exposed methods. The updater manager can only be called through the
-	// Home pallet.
+	// Bonds are managed by their owners. The updater itself can only be set or slashed through
+	// the Home pallet.
 	#[pallet::call]
-	impl<T: Config> Pallet<T> {}
+	impl<T: Config> Pallet<T> {
+		/// Reserve `UpdaterBond` from the caller for `updater`, so it can be set as the updater.
+		///
+		/// `signature` is the signature of the `bond_message_hash` of the caller by the
+		/// `updater` key, so only the accounts the updater chose can bond it.
+		#[pallet::call_index(0)]
+		#[pallet::weight(placeholder_weights::bond(T::DbWeight::get()))]
+		pub fn bond(
+			origin: OriginFor<T>,
+			updater: H160,
+			signature: ecdsa::Signature,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			let signer = Self::recover_signer(&signature, &Self::bond_message_hash(&owner));
+			ensure!(signer == Some(updater), Error::<T>::InvalidBondSignature);
+			Self::do_bond(owner, updater)
+		}
+
+		/// Start unbonding the bond of `updater`, which can be withdrawn after `UnbondingDelay`
+		/// blocks. The current updater cannot be unbonded.
+		#[pallet::call_index(1)]
+		#[pallet::weight(placeholder_weights::unbond(T::DbWeight::get()))]
+		pub fn unbond(origin: OriginFor<T>, updater: H160) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			ensure!(Updater::<T>::get() != updater, Error::<T>::ActiveUpdater);
+
+			let unlock_at = Bonds::<T>::try_mutate(updater, |bond| {
+				let bond = bond.as_mut().ok_or(Error::<T>::NotBonded)?;
+				ensure!(bond.owner == owner, Error::<T>::NotBondOwner);
+				ensure!(bond.unlock_at.is_none(), Error::<T>::AlreadyUnbonding);
+
+				let unlock_at = frame_system::Pallet::<T>::block_number()
+					.saturating_add(T::UnbondingDelay::get());
+				bond.unlock_at = Some(unlock_at);
+				Ok::<_, Error<T>>(unlock_at)
+			})?;
+
+			Self::deposit_event(Event::<T>::Unbonding { updater, unlock_at });
+
+			Ok(())
+		}
+
+		/// Release the funds of the bond of `updater` once its unbonding delay is over.
+		#[pallet::call_index(2)]
+		#[pallet::weight(placeholder_weights::withdraw_unbonded(T::DbWeight::get()))]
+		pub fn withdraw_unbonded(origin: OriginFor<T>, updater: H160) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			let bond = Bonds::<T>::get(updater).ok_or(Error::<T>::NotBonded)?;
+			ensure!(bond.owner == owner, Error::<T>::NotBondOwner);
+
+			let unlock_at = bond.unlock_at.ok_or(Error::<T>::NotUnbonding)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >= unlock_at,
+				Error::<T>::BondStillLocked
+			);
+
+			T::Currency::unreserve(&owner, bond.amount);
+			Bonds::<T>::remove(updater);
+
+			Self::deposit_event(Event::<T>::Withdrawn {
+				updater,
+				owner,
+				amount: bond.amount,
+			});
+
+			Ok(())
+		}
+	}
 
 	impl<T: Config> Pallet<T> {
 		pub fn get_updater() -> H160 {
 			Updater::<T>::get()
 		}
 
+		/// Reserve `UpdaterBond` from `owner` for `updater`, without checking that the updater
+		/// chose `owner`.
+		pub fn do_bond(owner: T::AccountId, updater: H160) -> DispatchResult {
+			ensure!(
+				!Bonds::<T>::contains_key(updater),
+				Error::<T>::AlreadyBonded
+			);
+
+			let amount = T::UpdaterBond::get();
+			T::Currency::reserve(&owner, amount)?;
+			Bonds::<T>::insert(
+				updater,
+				Bond {
+					owner: owner.clone(),
+					amount,
+					unlock_at: None,
+				},
+			);
+
+			Self::deposit_event(Event::<T>::Bonded {
+				updater,
+				owner,
+				amount,
+			});
+
+			Ok(())
+		}
+
+		/// Hash signed by an updater key to let `owner` bond it.
+		///
+		/// It is the hash of the Ethereum signed message of the 32 bytes
+		/// `keccak256(BOND_MESSAGE_PREFIX ++ owner)`, like the updates, so the updater signs
+		/// it with the same tools.
+		pub fn bond_message_hash(owner: &T::AccountId) -> [u8; 32] {
+			let mut message = Vec::from(BOND_MESSAGE_PREFIX);
+			owner.encode_to(&mut message);
+			let mut signed_message = Vec::from(&b"\x19Ethereum Signed Message:\n32"[..]);
+			signed_message.extend_from_slice(&keccak_256(&message));
+			keccak_256(&signed_message)
+		}
+
+		/// Address of the key which signed `hash`, if the signature is valid.
+		pub fn recover_signer(signature: &ecdsa::Signature, hash: &[u8; 32]) -> Option<H160> {
+			let public = sp_io::crypto::secp256k1_ecdsa_recover(&signature.0, hash).ok()?;
+			Some(H160::from_slice(&keccak_256(&public)[12..]))
+		}
+
+		/// Set the updater, which must have a bond that is not unbonding.
 		pub fn set_updater(new_updater: H160) -> DispatchResult {
+			let bonded =
+				Bonds::<T>::get(new_updater).map_or(false, |bond| bond.unlock_at.is_none());
+			ensure!(bonded, Error::<T>::UpdaterNotBonded);
+
 			let old_updater = Updater::<T>::get();
 			Updater::<T>::put(new_updater);
 
@@ -87,8 +290,30 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Slash the bond of the current updater, rewarding `SlashRewardShare` of it to
+		/// `reporter`.
 		pub fn slash_updater(reporter: T::AccountId) {
-			Self::deposit_event(Event::<T>::FakeSlashed { reporter });
+			let updater = Updater::<T>::get();
+			let (amount, reward) = match Bonds::<T>::take(updater) {
+				Some(bond) => {
+					let (slashed, _) = T::Currency::slash_reserved(&bond.owner, bond.amount);
+					let amount = slashed.peek();
+					let (reward, rest) = slashed.split(T::SlashRewardShare::get() * amount);
+					let reward_amount = reward.peek();
+
+					T::Currency::resolve_creating(&reporter, reward);
+					T::Slashed::on_unbalanced(rest);
+					(amount, reward_amount)
+				},
+				None => (Zero::zero(), Zero::zero()),
+			};
+
+			Self::deposit_event(Event::<T>::Slashed {
+				updater,
+				reporter,
+				amount,
+				reward,
+			});
 		}
 	}
 }
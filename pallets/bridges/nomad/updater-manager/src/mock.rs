@@ -1,6 +1,6 @@
-use frame_support::{derive_impl, parameter_types};
+use frame_support::{derive_impl, parameter_types, traits::ConstU32};
 use frame_system::{self as system, mocking::MockUncheckedExtrinsic, test_utils::TestRandomness};
-use sp_runtime::BuildStorage;
+use sp_runtime::{BuildStorage, Perbill};
 
 use crate as updater_manager;
 
@@ -11,16 +11,22 @@ type Block = frame_system::mocking::MockDaBlock<Test>;
 frame_support::construct_runtime!(
 	pub struct Test {
 		System: frame_system,
+		Balances: pallet_balances,
 		UpdaterManager: updater_manager,
 	}
 );
 
 parameter_types! {
 	pub const BlockHashCount: u32 = 250;
+	pub const UpdaterBond: u64 = 1_000;
+	pub const SlashRewardShare: Perbill = Perbill::from_percent(10);
 }
 
+pub(crate) const UNBONDING_DELAY: u32 = 10;
+
 #[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
 impl system::Config for Test {
+	type AccountData = pallet_balances::AccountData<u64>;
 	type BaseCallFilter = frame_support::traits::Everything;
 	type Block = Block;
 	type BlockHashCount = BlockHashCount;
@@ -35,16 +41,47 @@ impl system::Config for Test {
 	type UncheckedExtrinsic = UncheckedExtrinsic;
 }
 
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
+	type Balance = u64;
+	type DustRemoval = ();
+	type ExistentialDeposit = frame_support::traits::ConstU64<1>;
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type MaxHolds = ();
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeHoldReason = ();
+	type WeightInfo = ();
+}
+
 impl updater_manager::Config for Test {
+	type Currency = Balances;
 	type RuntimeEvent = RuntimeEvent;
+	type SlashRewardShare = SlashRewardShare;
+	type Slashed = ();
+	type UnbondingDelay = ConstU32<UNBONDING_DELAY>;
+	type UpdaterBond = UpdaterBond;
 }
 
+/// Accounts funded at genesis.
+pub(crate) const BONDER: u64 = 1;
+pub(crate) const REPORTER: u64 = 2;
+pub(crate) const INITIAL_BALANCE: u64 = 10_000;
+
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	let t = RuntimeGenesisConfig::default()
+	let mut t = RuntimeGenesisConfig::default()
 		.system
 		.build_storage()
 		.expect("Genesis build should work");
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(BONDER, INITIAL_BALANCE), (REPORTER, INITIAL_BALANCE)],
+	}
+	.assimilate_storage(&mut t)
+	.expect("Balances storage can be assimilated");
 	let mut ext = sp_io::TestExternalities::new(t);
 	ext.execute_with(|| System::set_block_number(1));
 	ext
@@ -0,0 +1,23 @@
+//! Placeholder weights for `nomad_updater_manager`.
+//!
+//! NOT GENERATED: the benchmarks of `benchmarking.rs` have not been run yet, so this pallet has
+//! no generated `weights.rs` nor `WeightInfo`. Each weight is a hand-written estimate of the
+//! execution time plus the storage listed above it. `bond` dominates, as it recovers the
+//! updater key from its signature.
+
+use frame_support::weights::{RuntimeDbWeight, Weight};
+
+// Bonds (r:1 w:1), System::Account (r:1 w:1), plus recovering the updater of the signature
+pub fn bond(db: RuntimeDbWeight) -> Weight {
+	Weight::from_parts(100_000_000, 3593).saturating_add(db.reads_writes(2, 2))
+}
+
+// Updater (r:1), Bonds (r:1 w:1)
+pub fn unbond(db: RuntimeDbWeight) -> Weight {
+	Weight::from_parts(22_000_000, 3554).saturating_add(db.reads_writes(2, 1))
+}
+
+// Bonds (r:1 w:1), System::Account (r:1 w:1)
+pub fn withdraw_unbonded(db: RuntimeDbWeight) -> Weight {
+	Weight::from_parts(42_000_000, 3593).saturating_add(db.reads_writes(2, 2))
+}
@@ -1,7 +1,25 @@
-use frame_support::assert_ok;
-use sp_core::H160;
+use frame_support::{assert_noop, assert_ok};
+use hex_literal::hex;
+use sp_core::{ecdsa, Pair as _, H160};
 
-use crate::mock::*;
+use crate::{mock::*, Bond, Error, Event};
+
+const UPDATER: H160 = H160::repeat_byte(1);
+
+/// Ethereum key of `SIGNING_UPDATER`.
+const SIGNING_UPDATER_KEY: [u8; 32] =
+	hex!("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318");
+const SIGNING_UPDATER: H160 = H160(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+
+fn bond(updater: H160) {
+	assert_ok!(UpdaterManager::do_bond(BONDER, updater));
+}
+
+/// Signature of the bond of `SIGNING_UPDATER` by `owner`.
+fn bond_signature(owner: u64) -> ecdsa::Signature {
+	ecdsa::Pair::from_seed(&SIGNING_UPDATER_KEY)
+		.sign_prehashed(&UpdaterManager::bond_message_hash(&owner))
+}
 
 #[test]
 fn it_sets_updater() {
@@ -9,27 +27,193 @@ fn it_sets_updater() {
 		// Default updater is zero bytes
 		assert_eq!(UpdaterManager::get_updater(), H160::zero());
 
+		// Only bonded updaters can be set
+		assert_noop!(
+			UpdaterManager::set_updater(UPDATER),
+			Error::<Test>::UpdaterNotBonded
+		);
+
 		// Set to 1 and check new state
-		let new_updater = H160::repeat_byte(1);
-		assert_ok!(UpdaterManager::set_updater(new_updater));
-		assert_eq!(UpdaterManager::get_updater(), new_updater);
+		bond(UPDATER);
+		assert_ok!(UpdaterManager::set_updater(UPDATER));
+		assert_eq!(UpdaterManager::get_updater(), UPDATER);
 
-		let expected = vec![crate::Event::NewUpdater {
-			old_updater: H160::zero(),
-			new_updater,
-		}];
+		let expected = vec![
+			Event::Bonded {
+				updater: UPDATER,
+				owner: BONDER,
+				amount: UpdaterBond::get(),
+			},
+			Event::NewUpdater {
+				old_updater: H160::zero(),
+				new_updater: UPDATER,
+			},
+		];
 		assert_eq!(events(), expected);
 	});
 }
 
+#[test]
+fn it_bonds_updater() {
+	new_test_ext().execute_with(|| {
+		bond(UPDATER);
+		assert_eq!(Balances::reserved_balance(BONDER), UpdaterBond::get());
+		assert_eq!(
+			UpdaterManager::bond_of(UPDATER),
+			Some(Bond {
+				owner: BONDER,
+				amount: UpdaterBond::get(),
+				unlock_at: None,
+			})
+		);
+
+		assert_noop!(
+			UpdaterManager::do_bond(REPORTER, UPDATER),
+			Error::<Test>::AlreadyBonded
+		);
+	});
+}
+
+#[test]
+fn it_requires_the_updater_signature_to_bond() {
+	new_test_ext().execute_with(|| {
+		// The updater signs the account allowed to bond it.
+		assert_noop!(
+			UpdaterManager::bond(
+				RuntimeOrigin::signed(REPORTER),
+				SIGNING_UPDATER,
+				bond_signature(BONDER)
+			),
+			Error::<Test>::InvalidBondSignature
+		);
+		assert_noop!(
+			UpdaterManager::bond(
+				RuntimeOrigin::signed(BONDER),
+				UPDATER,
+				bond_signature(BONDER)
+			),
+			Error::<Test>::InvalidBondSignature
+		);
+
+		assert_ok!(UpdaterManager::bond(
+			RuntimeOrigin::signed(BONDER),
+			SIGNING_UPDATER,
+			bond_signature(BONDER)
+		));
+		assert_eq!(Balances::reserved_balance(BONDER), UpdaterBond::get());
+		assert_eq!(
+			UpdaterManager::bond_of(SIGNING_UPDATER).map(|bond| bond.owner),
+			Some(BONDER)
+		);
+	});
+}
+
 #[test]
 fn it_slashes_updater() {
 	new_test_ext().execute_with(|| {
-		// Slash updater
-		let reporter = 1u64;
-		UpdaterManager::slash_updater(reporter);
+		bond(UPDATER);
+		assert_ok!(UpdaterManager::set_updater(UPDATER));
+		let issuance = Balances::total_issuance();
 
-		let expected = vec![crate::Event::FakeSlashed { reporter }];
+		UpdaterManager::slash_updater(REPORTER);
+
+		// The reporter is rewarded, and the rest of the bond is burnt
+		let reward = SlashRewardShare::get() * UpdaterBond::get();
+		assert_eq!(Balances::reserved_balance(BONDER), 0);
+		assert_eq!(
+			Balances::free_balance(BONDER),
+			INITIAL_BALANCE - UpdaterBond::get()
+		);
+		assert_eq!(Balances::free_balance(REPORTER), INITIAL_BALANCE + reward);
+		assert_eq!(
+			Balances::total_issuance(),
+			issuance - UpdaterBond::get() + reward
+		);
+		assert_eq!(UpdaterManager::bond_of(UPDATER), None);
+
+		let expected = Event::Slashed {
+			updater: UPDATER,
+			reporter: REPORTER,
+			amount: UpdaterBond::get(),
+			reward,
+		};
+		assert_eq!(events().last(), Some(&expected));
+	});
+}
+
+#[test]
+fn it_slashes_nothing_without_bond() {
+	new_test_ext().execute_with(|| {
+		UpdaterManager::slash_updater(REPORTER);
+
+		assert_eq!(Balances::free_balance(REPORTER), INITIAL_BALANCE);
+		let expected = vec![Event::Slashed {
+			updater: H160::zero(),
+			reporter: REPORTER,
+			amount: 0,
+			reward: 0,
+		}];
 		assert_eq!(events(), expected);
 	});
 }
+
+#[test]
+fn it_unbonds_after_delay() {
+	new_test_ext().execute_with(|| {
+		let previous = H160::repeat_byte(2);
+		bond(previous);
+		bond(UPDATER);
+		assert_ok!(UpdaterManager::set_updater(UPDATER));
+
+		// The current updater cannot be unbonded
+		assert_noop!(
+			UpdaterManager::unbond(RuntimeOrigin::signed(BONDER), UPDATER),
+			Error::<Test>::ActiveUpdater
+		);
+		assert_noop!(
+			UpdaterManager::unbond(RuntimeOrigin::signed(REPORTER), previous),
+			Error::<Test>::NotBondOwner
+		);
+		assert_noop!(
+			UpdaterManager::withdraw_unbonded(RuntimeOrigin::signed(BONDER), previous),
+			Error::<Test>::NotUnbonding
+		);
+
+		assert_ok!(UpdaterManager::unbond(
+			RuntimeOrigin::signed(BONDER),
+			previous
+		));
+		let unlock_at = 1 + UNBONDING_DELAY;
+		assert_eq!(
+			events().last(),
+			Some(&Event::Unbonding {
+				updater: previous,
+				unlock_at
+			})
+		);
+
+		// Unbonding updaters cannot be set
+		assert_noop!(
+			UpdaterManager::set_updater(previous),
+			Error::<Test>::UpdaterNotBonded
+		);
+		assert_noop!(
+			UpdaterManager::unbond(RuntimeOrigin::signed(BONDER), previous),
+			Error::<Test>::AlreadyUnbonding
+		);
+
+		System::set_block_number(unlock_at - 1);
+		assert_noop!(
+			UpdaterManager::withdraw_unbonded(RuntimeOrigin::signed(BONDER), previous),
+			Error::<Test>::BondStillLocked
+		);
+
+		System::set_block_number(unlock_at);
+		assert_ok!(UpdaterManager::withdraw_unbonded(
+			RuntimeOrigin::signed(BONDER),
+			previous
+		));
+		assert_eq!(Balances::reserved_balance(BONDER), UpdaterBond::get());
+		assert_eq!(UpdaterManager::bond_of(previous), None);
+	});
+}
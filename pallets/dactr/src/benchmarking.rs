@@ -12,7 +12,9 @@ use frame_benchmarking::{
 };
 use frame_support::{log::info, traits::Get};
 use frame_system::{
-	header_builder::hosted_header_builder, limits::BlockLength, submitted_data, RawOrigin,
+	header_builder::{hosted_header_builder, HeaderExtensionVersion},
+	limits::BlockLength,
+	submitted_data, RawOrigin,
 };
 use scale_info::{StaticTypeInfo, TypeInfo};
 use sp_core::H256;
@@ -208,6 +210,19 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn set_header_extension_version() -> Result<(), BenchmarkError> {
+		let origin = RawOrigin::Root;
+		let version = ExtensionVersion::V2;
+		let at = frame_system::Pallet::<T>::block_number() + 1u32.into();
+
+		#[extrinsic_call]
+		_(origin, version, at);
+
+		assert_last_event::<T>(Event::HeaderExtensionVersionScheduled { version, at }.into());
+		Ok(())
+	}
+
 	#[benchmark]
 	fn submit_data(i: Linear<1, { T::MaxAppDataLength::get() }>) -> Result<(), BenchmarkError> {
 		let caller = whitelisted_caller::<T::AccountId>();
@@ -235,7 +250,10 @@ mod benchmarks {
 
 		#[block]
 		{
-			submitted_data::extrinsics_root::<T::SubmittedDataExtractor, _>(once(&opaque));
+			submitted_data::extrinsics_root::<T::SubmittedDataExtractor, _>(
+				once(&opaque),
+				T::DataRootLeafVersion::get(),
+			);
 		}
 
 		Ok(())
@@ -266,7 +284,10 @@ mod benchmarks {
 
 		#[block]
 		{
-			submitted_data::extrinsics_root::<T::SubmittedDataExtractor, _>(calls.iter());
+			submitted_data::extrinsics_root::<T::SubmittedDataExtractor, _>(
+				calls.iter(),
+				T::DataRootLeafVersion::get(),
+			);
 		}
 
 		Ok(())
@@ -280,7 +301,14 @@ mod benchmarks {
 
 		#[block]
 		{
-			hosted_header_builder::build(txs, root, block_length, block_number, seed);
+			hosted_header_builder::build(
+				txs,
+				root,
+				block_length,
+				block_number,
+				seed,
+				HeaderExtensionVersion::V1,
+			);
 		}
 
 		Ok(())
@@ -294,7 +322,14 @@ mod benchmarks {
 
 		#[block]
 		{
-			hosted_header_builder::build(txs, root, block_length, block_number, seed);
+			hosted_header_builder::build(
+				txs,
+				root,
+				block_length,
+				block_number,
+				seed,
+				HeaderExtensionVersion::V1,
+			);
 		}
 
 		Ok(())
@@ -308,7 +343,14 @@ mod benchmarks {
 
 		#[block]
 		{
-			hosted_header_builder::build(txs, root, block_length, block_number, seed);
+			hosted_header_builder::build(
+				txs,
+				root,
+				block_length,
+				block_number,
+				seed,
+				HeaderExtensionVersion::V1,
+			);
 		}
 
 		Ok(())
@@ -322,7 +364,14 @@ mod benchmarks {
 
 		#[block]
 		{
-			hosted_header_builder::build(txs, root, block_length, block_number, seed);
+			hosted_header_builder::build(
+				txs,
+				root,
+				block_length,
+				block_number,
+				seed,
+				HeaderExtensionVersion::V1,
+			);
 		}
 
 		Ok(())
@@ -1,11 +1,11 @@
 use avail_core::{traits::GetAppId, AppId, InvalidTransactionCustomId};
 use codec::{Decode, Encode};
-use frame_support::{
-	ensure,
-	traits::{IsSubType, IsType},
+use frame_support::ensure;
+use frame_system::{
+	submitted_data::{inner_calls, DataCall},
+	Config as SystemConfig,
 };
-use frame_system::Config as SystemConfig;
-use pallet_utility::{Call as UtilityCall, Config as UtilityConfig};
+use pallet_utility::Config as UtilityConfig;
 use scale_info::TypeInfo;
 use sp_runtime::{
 	traits::{DispatchInfoOf, SignedExtension},
@@ -17,12 +17,9 @@ use sp_std::{
 	default::Default,
 	fmt::{self, Debug, Formatter},
 	marker::PhantomData,
-	vec::Vec,
 };
 
-use crate::{Call as DACall, Config as DAConfig, Pallet};
-
-const MAX_ITERATIONS: usize = 2;
+use crate::{Config as DAConfig, Pallet};
 
 /// Check for Application Id.
 ///
@@ -40,7 +37,7 @@ pub struct CheckAppId<T: DAConfig + UtilityConfig + Send + Sync>(
 impl<T> CheckAppId<T>
 where
 	T: DAConfig + UtilityConfig + Send + Sync,
-	<T as SystemConfig>::RuntimeCall: IsSubType<DACall<T>> + IsSubType<UtilityCall<T>>,
+	<T as SystemConfig>::RuntimeCall: DataCall,
 {
 	/// utility constructor. Used only in client/factory code.
 	pub fn from(app_id: AppId) -> Self {
@@ -49,52 +46,35 @@ where
 
 	/// It validates that `AppId` is correct and already registered for the call and potential nested calls.
 	/// Transaction validation:
-	///  - `DataAvailability::submit_data(..)` extrinsic can use `AppId != 0`.
-	///  - `Utility::batch/batch_all/force_batch(..)` extrinsic can use `AppId != 0` If the wrapped calls are ALL `DataAvailability::submit_data(..)`.
+	///  - Data calls, like `DataAvailability::submit_data(..)`, can use `AppId != 0`.
+	///  - Wrapper calls, like `Utility::batch/batch_all/force_batch(..)`, can use `AppId != 0` If the wrapped calls are ALL data calls.
 	///  - Any other call must use `AppId == 0`.
+	///  - Calls nested deeper than `MAX_WRAPPER_DEPTH` wrappers are rejected for `AppId != 0`.
+	///    With `AppId == 0` they stay valid, but their data is not part of the data root.
+	///
+	/// Data and wrapper calls are the ones marked by the `DataCall` implementation of the runtime
+	/// call, and they are walked by `inner_calls`, as when the data root is built.
 	pub fn do_validate(&self, call: &<T as SystemConfig>::RuntimeCall) -> TransactionValidity {
 		if self.app_id() == AppId(0) {
 			return Ok(ValidTransaction::default());
 		}
 
-		let mut stack = Vec::new();
-		stack.push(call);
+		let inner = inner_calls(call).map_err(|_| {
+			InvalidTransaction::Custom(InvalidTransactionCustomId::MaxRecursionExceeded as u8)
+		})?;
 
 		let mut maybe_next_app_id: Option<AppId> = None;
-		let mut iterations = 0;
-
-		while let Some(call) = stack.pop() {
-			if let Some(DACall::<T>::submit_data { .. }) = call.is_sub_type() {
-				let next_app_id =
-					maybe_next_app_id.get_or_insert_with(<Pallet<T>>::peek_next_application_id);
-				ensure!(
-					self.app_id() < *next_app_id,
-					InvalidTransaction::Custom(InvalidTransactionCustomId::InvalidAppId as u8)
-				);
-			} else {
-				match call.is_sub_type() {
-					Some(UtilityCall::<T>::batch { calls })
-					| Some(UtilityCall::<T>::batch_all { calls })
-					| Some(UtilityCall::<T>::force_batch { calls }) => {
-						iterations += 1;
-						ensure!(
-							iterations < MAX_ITERATIONS,
-							InvalidTransaction::Custom(
-								InvalidTransactionCustomId::MaxRecursionExceeded as u8
-							)
-						);
-						for call in calls.iter() {
-							stack.push(call.into_ref());
-						}
-						Ok(())
-					},
-					_ => Err(TransactionValidityError::Invalid(
-						InvalidTransaction::Custom(
-							InvalidTransactionCustomId::ForbiddenAppId as u8,
-						),
-					)),
-				}?;
-			}
+		for call in inner {
+			ensure!(
+				call.is_some(),
+				InvalidTransaction::Custom(InvalidTransactionCustomId::ForbiddenAppId as u8)
+			);
+			let next_app_id =
+				maybe_next_app_id.get_or_insert_with(<Pallet<T>>::peek_next_application_id);
+			ensure!(
+				self.app_id() < *next_app_id,
+				InvalidTransaction::Custom(InvalidTransactionCustomId::InvalidAppId as u8)
+			);
 		}
 
 		Ok(ValidTransaction::default())
@@ -125,8 +105,7 @@ where
 impl<T> SignedExtension for CheckAppId<T>
 where
 	T: DAConfig + UtilityConfig + Send + Sync,
-	<T as frame_system::Config>::RuntimeCall:
-		IsSubType<DACall<T>> + IsSubType<pallet_utility::Call<T>>,
+	<T as frame_system::Config>::RuntimeCall: DataCall,
 {
 	type AccountId = T::AccountId;
 	type AdditionalSigned = ();
@@ -173,8 +152,11 @@ where
 
 #[cfg(test)]
 mod tests {
-	use avail_core::InvalidTransactionCustomId::{ForbiddenAppId, InvalidAppId};
+	use avail_core::InvalidTransactionCustomId::{
+		ForbiddenAppId, InvalidAppId, MaxRecursionExceeded,
+	};
 	use frame_system::pallet::Call as SysCall;
+	use frame_system::submitted_data::MAX_WRAPPER_DEPTH;
 	use pallet_utility::pallet::Call as UtilityCall;
 	use sp_runtime::transaction_validity::InvalidTransaction;
 	use test_case::test_case;
@@ -210,6 +192,12 @@ mod tests {
 		})
 	}
 
+	fn nested_batch_call(depth: usize) -> RuntimeCall {
+		(0..depth).fold(submit_data_call(), |call, _| {
+			RuntimeCall::Utility(UtilityCall::batch { calls: vec![call] })
+		})
+	}
+
 	fn to_invalid_tx(custom_id: InvalidTransactionCustomId) -> TransactionValidity {
 		Err(TransactionValidityError::Invalid(
 			InvalidTransaction::Custom(custom_id as u8),
@@ -223,6 +211,9 @@ mod tests {
 	#[test_case(1, batch_submit_call() => Ok(ValidTransaction::default()); "utility batch filled with submit_data can be called with any valid AppId" )]
 	#[test_case(1, batch_mixed_call() => to_invalid_tx(ForbiddenAppId); "utility batch filled with submit_data and remark cannot be called if AppId != 0" )]
 	#[test_case(0, batch_mixed_call() => Ok(ValidTransaction::default()); "utility batch filled with submit_data and remark can be called if AppId == 0" )]
+	#[test_case(1, nested_batch_call(MAX_WRAPPER_DEPTH) => Ok(ValidTransaction::default()); "submit_data nested up to the max depth can be called with any valid AppId" )]
+	#[test_case(1, nested_batch_call(MAX_WRAPPER_DEPTH + 1) => to_invalid_tx(MaxRecursionExceeded); "submit_data nested deeper than the max depth cannot be called if AppId != 0" )]
+	#[test_case(0, nested_batch_call(MAX_WRAPPER_DEPTH + 1) => to_invalid_tx(MaxRecursionExceeded); "submit_data nested deeper than the max depth cannot be called if AppId == 0" )]
 	fn do_validate_test(id: u32, call: RuntimeCall) -> TransactionValidity {
 		new_test_ext().execute_with(|| CheckAppId::<Test>::from(AppId(id)).do_validate(&call))
 	}
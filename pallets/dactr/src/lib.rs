@@ -6,7 +6,11 @@ use avail_core::{
 	AppId, BlockLengthColumns, BlockLengthRows, BLOCK_CHUNK_SIZE, NORMAL_DISPATCH_RATIO,
 };
 use frame_support::{dispatch::DispatchClass, weights::Weight};
-use frame_system::{limits::BlockLength, pallet::DynamicBlockLength};
+use frame_system::{
+	limits::BlockLength,
+	pallet::{DynamicBlockLength, PendingHeaderExtensionVersion},
+	ExtensionVersion,
+};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_arithmetic::traits::{CheckedAdd, One, SaturatedConversion};
@@ -21,6 +25,7 @@ mod benchmarking;
 mod extensions;
 #[cfg(feature = "std")]
 pub mod mock;
+pub mod placeholder_weights;
 #[cfg(test)]
 mod tests;
 pub use extensions::check_app_id::CheckAppId;
@@ -210,6 +215,29 @@ pub mod pallet {
 
 			Ok(().into())
 		}
+
+		/// Schedules the header extension `version` to be used from block number `at` onwards.
+		///
+		/// Any previously scheduled version which is not enabled yet is replaced.
+		#[pallet::call_index(3)]
+		#[pallet::weight(placeholder_weights::set_header_extension_version(T::DbWeight::get()))]
+		pub fn set_header_extension_version(
+			origin: OriginFor<T>,
+			version: ExtensionVersion,
+			at: BlockNumberFor<T>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			ensure!(
+				at > <frame_system::Pallet<T>>::block_number(),
+				Error::<T>::InvalidActivationBlock
+			);
+
+			PendingHeaderExtensionVersion::<T>::put((at, version));
+
+			Self::deposit_event(Event::HeaderExtensionVersionScheduled { version, at });
+
+			Ok(().into())
+		}
 	}
 
 	/// Event for the pallet.
@@ -230,6 +258,11 @@ pub mod pallet {
 			rows: BlockLengthRows,
 			cols: BlockLengthColumns,
 		},
+		/// A new header extension version was scheduled.
+		HeaderExtensionVersionScheduled {
+			version: ExtensionVersion,
+			at: BlockNumberFor<T>,
+		},
 	}
 
 	/// Error for the System pallet
@@ -253,6 +286,8 @@ pub mod pallet {
 		InvalidBlockWeightReduction,
 		/// Submit data call outside of block execution context.
 		BadContext,
+		/// The activation block of a header extension version is not in the future.
+		InvalidActivationBlock,
 	}
 
 	#[pallet::genesis_config]
@@ -309,6 +344,18 @@ impl<T: Config> Pallet<T> {
 	}
 }
 
+impl<T: Config> Call<T> {
+	/// Returns the data of this call if it is a `submit_data` call.
+	///
+	/// It is used by the runtime to implement `frame_system::submitted_data::DataCall`.
+	pub fn submitted_data(&self) -> Option<&[u8]> {
+		match self {
+			Self::submit_data { data } => Some(data.as_slice()),
+			_ => None,
+		}
+	}
+}
+
 mod weight_helper {
 
 	use super::*;
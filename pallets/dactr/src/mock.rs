@@ -6,7 +6,9 @@ use frame_support::{
 	weights::{ConstantMultiplier, IdentityFee},
 };
 use frame_system::{
-	header_builder::da::HeaderExtensionBuilder, mocking::MockUncheckedExtrinsic,
+	header_builder::da::HeaderExtensionBuilder,
+	mocking::MockUncheckedExtrinsic,
+	submitted_data::{DataCall, DataCallKind},
 	test_utils::TestRandomness,
 };
 use pallet_transaction_payment::CurrencyAdapter;
@@ -94,6 +96,22 @@ impl pallet_utility::Config for Test {
 	type WeightInfo = ();
 }
 
+impl DataCall for RuntimeCall {
+	fn data_call_kind(&self) -> DataCallKind<'_, Self> {
+		match self {
+			RuntimeCall::DataAvailability(call) => call
+				.submitted_data()
+				.map_or(DataCallKind::Other, DataCallKind::Data),
+			RuntimeCall::Utility(
+				pallet_utility::Call::batch { calls }
+				| pallet_utility::Call::batch_all { calls }
+				| pallet_utility::Call::force_batch { calls },
+			) => DataCallKind::Wrapper(calls.iter().collect()),
+			_ => DataCallKind::Other,
+		}
+	}
+}
+
 parameter_types! {
 	pub const MaxAppKeyLength: u32 = 32;
 	pub const MaxAppDataLength: u32 = 512 * 1024; // 512 Kb
@@ -234,3 +234,56 @@ mod submit_block_length_proposal {
 		})
 	}
 }
+
+mod set_header_extension_version {
+	use frame_system::ExtensionVersion;
+
+	use super::*;
+
+	#[test]
+	fn set_header_extension_version() {
+		new_test_ext().execute_with(|| {
+			let root: RuntimeOrigin = RawOrigin::Root.into();
+			let version = ExtensionVersion::V2;
+			let at = 10;
+
+			assert_ok!(DataAvailability::set_header_extension_version(
+				root, version, at
+			));
+			assert_eq!(
+				System::pending_header_extension_version(),
+				Some((at, version))
+			);
+			assert_eq!(System::header_extension_version(), ExtensionVersion::V1);
+
+			let event = RuntimeEvent::DataAvailability(Event::HeaderExtensionVersionScheduled {
+				version,
+				at,
+			});
+			System::assert_last_event(event);
+		})
+	}
+
+	#[test]
+	fn bad_origin() {
+		new_test_ext().execute_with(|| {
+			let alice: RuntimeOrigin = RawOrigin::Signed(ALICE).into();
+
+			let err =
+				DataAvailability::set_header_extension_version(alice, ExtensionVersion::V2, 10);
+			assert_noop!(err, BadOrigin);
+		})
+	}
+
+	#[test]
+	fn invalid_activation_block() {
+		new_test_ext().execute_with(|| {
+			let root: RuntimeOrigin = RawOrigin::Root.into();
+			let current = System::block_number();
+
+			let err =
+				DataAvailability::set_header_extension_version(root, ExtensionVersion::V2, current);
+			assert_noop!(err, Error::InvalidActivationBlock);
+		})
+	}
+}
@@ -134,6 +134,10 @@ impl frame_system::Config for Runtime {
 	type BaseCallFilter = frame_support::traits::Everything;
 	type BlockHashCount = BlockHashCount;
 	type BlockLength = ();
+	type CommitmentScheme = frame_system::header_builder::commitment::Kate;
+	type PaddingSeed = frame_support::traits::GetDefault;
+	type DataRootLeafVersion = frame_support::traits::GetDefault;
+	type DataRootMode = frame_support::traits::GetDefault;
 	type Block = Block;
 	type BlockWeights = BlockWeights;
 	type DbWeight = ();
@@ -178,6 +182,19 @@ impl pallet_balances::Config for Runtime {
 	type WeightInfo = ();
 }
 
+impl frame_system::submitted_data::DataCall for RuntimeCall {
+	fn data_call_kind(&self) -> frame_system::submitted_data::DataCallKind<'_, Self> {
+		use frame_system::submitted_data::DataCallKind;
+
+		match self {
+			RuntimeCall::DataAvailability(call) => call
+				.submitted_data()
+				.map_or(DataCallKind::Other, DataCallKind::Data),
+			_ => DataCallKind::Other,
+		}
+	}
+}
+
 impl da_control::Config for Runtime {
 	type BlockLenProposalId = u32;
 	type MaxAppDataLength = MaxAppDataLength;
@@ -76,6 +76,10 @@ impl frame_system::Config for Runtime {
 	type Block = Block;
 	type BlockHashCount = ConstU32<250>;
 	type BlockLength = BlockLength;
+	type CommitmentScheme = frame_system::header_builder::commitment::Kate;
+	type PaddingSeed = frame_support::traits::GetDefault;
+	type DataRootLeafVersion = frame_support::traits::GetDefault;
+	type DataRootMode = frame_support::traits::GetDefault;
 	type BlockWeights = ();
 	type DbWeight = ();
 	type Hash = H256;
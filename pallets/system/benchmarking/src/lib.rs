@@ -147,7 +147,8 @@ benchmarks! {
 		let block_length = Default::default();
 
 	}: {
-		let _header = T::HeaderExtensionBuilder::build(app_extrinsics, data_root, block_length, 0);
+		let version = System::<T>::header_extension_version();
+		let _header = T::HeaderExtensionBuilder::build(app_extrinsics, data_root, block_length, 0, version);
 	}
 
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
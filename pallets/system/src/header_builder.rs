@@ -1,18 +1,291 @@
 use avail_core::{header::HeaderExtension, traits::ExtendedHeader, AppExtrinsic};
-use frame_support::traits::Randomness;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::traits::{Get, Randomness};
 pub use kate::{
 	metrics::{IgnoreMetrics, Metrics},
 	Seed,
 };
+use scale_info::TypeInfo;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use sp_core::H256;
 #[cfg(feature = "std")]
 use sp_runtime::SaturatedConversion;
-use sp_runtime::{generic::Digest, traits::Hash};
-use sp_runtime_interface::runtime_interface;
+use sp_runtime::{
+	generic::{Digest, DigestItem},
+	traits::Hash,
+	ConsensusEngineId, RuntimeDebug,
+};
+use sp_runtime_interface::{pass_by::PassByCodec, runtime_interface};
 use sp_std::vec::Vec;
 
 use crate::{limits::BlockLength, Config, LOG_TARGET};
 
+/// Version of the header extension built for each block.
+///
+/// It is stored on-chain, so the whole network can switch to a new version at a given block.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(
+	Clone,
+	Copy,
+	Default,
+	PartialEq,
+	Eq,
+	Encode,
+	Decode,
+	TypeInfo,
+	MaxEncodedLen,
+	PassByCodec,
+	RuntimeDebug,
+)]
+pub enum HeaderExtensionVersion {
+	#[default]
+	V1,
+	V2,
+}
+
+impl From<&HeaderExtension> for HeaderExtensionVersion {
+	fn from(extension: &HeaderExtension) -> Self {
+		match extension {
+			HeaderExtension::V1(_) => Self::V1,
+			HeaderExtension::V2(_) => Self::V2,
+		}
+	}
+}
+
+/// Engine id of the header digest item which tags the headers built by a commitment scheme other
+/// than `Kate`, see [`CommitmentSchemeId::digest_item`].
+pub const COMMITMENT_SCHEME_ENGINE_ID: ConsensusEngineId = *b"AVCS";
+
+/// Identifier of the commitment scheme used to commit to the data grid of each block.
+///
+/// Headers built by any scheme other than `Kate` carry it in their digest, so light clients can
+/// tell how the commitments of their extension were built.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(
+	Clone,
+	Copy,
+	Default,
+	PartialEq,
+	Eq,
+	Encode,
+	Decode,
+	TypeInfo,
+	MaxEncodedLen,
+	PassByCodec,
+	RuntimeDebug,
+)]
+pub enum CommitmentSchemeId {
+	/// KZG commitments of the column-extended polynomial grid.
+	#[default]
+	Kate,
+	/// One Keccak-256 Merkle root per row of the data grid.
+	MerkleRows,
+	/// Zeroed commitments, without any cryptography. Only for local testing.
+	Mock,
+}
+
+impl CommitmentSchemeId {
+	/// Returns the digest item which tags the headers built with this scheme.
+	///
+	/// `Kate` headers are not tagged, so they keep the format they had before the scheme was
+	/// selectable.
+	pub fn digest_item(self) -> Option<DigestItem> {
+		(self != Self::Kate)
+			.then(|| DigestItem::Consensus(COMMITMENT_SCHEME_ENGINE_ID, self.encode()))
+	}
+
+	/// Returns the scheme which built the header with the given `digest`.
+	pub fn from_digest(digest: &Digest) -> Self {
+		digest
+			.convert_first(|item| item.consensus_try_to(&COMMITMENT_SCHEME_ENGINE_ID))
+			.unwrap_or_default()
+	}
+}
+
+/// Source of the seed used to fill the padding cells of the data grid.
+///
+/// Light clients and RPC nodes must use the same seed to rebuild the grid of a block.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(
+	Clone, Copy, Default, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug,
+)]
+pub enum PaddingSeedPolicy {
+	/// The zero seed.
+	#[default]
+	Zero,
+	/// A seed derived from `T::Randomness`, see [`randomness_seed`].
+	Randomness,
+}
+
+/// Minimum width of the data grid.
+pub const MIN_GRID_WIDTH: usize = 4;
+
+/// Generates a seed using the _epoch seed_ and the _current block_ returned by `T::Randomness`.
+pub fn randomness_seed<T: Config>() -> Seed {
+	let (epoch_seed, block_number) = <T as Config>::Randomness::random_seed();
+	let seed = <T as Config>::Hashing::hash_of(&(&epoch_seed, &block_number));
+	log::trace!(
+		target: LOG_TARGET,
+		"Header builder seed {seed:?} from epoch seed {epoch_seed:?} and block {block_number:?}"
+	);
+
+	seed.into()
+}
+
+/// Commitment scheme used to build the header extension, selected on the runtime `Config`.
+///
+/// Each scheme builds the extension through its own host function, which commits to the data
+/// grid using the `commit` of the same scheme.
+pub trait CommitmentScheme {
+	/// Identifier of this scheme, which tags the headers it builds.
+	const ID: CommitmentSchemeId;
+
+	/// Creates the header extension of the given `version`, committing to the data grid.
+	fn build(
+		app_extrinsics: Vec<AppExtrinsic>,
+		data_root: H256,
+		block_length: BlockLength,
+		block_number: u32,
+		seed: Seed,
+		version: HeaderExtensionVersion,
+	) -> HeaderExtension;
+
+	/// Returns the serialized commitments of `grid`.
+	#[cfg(feature = "std")]
+	fn commit(grid: &kate::gridgen::EvaluationGrid) -> Vec<u8>;
+}
+
+pub mod commitment {
+	#[cfg(feature = "std")]
+	use kate::gridgen::{AsBytes, EvaluationGrid};
+
+	use super::*;
+
+	/// KZG commitments using the `couscous` public parameters.
+	pub struct Kate;
+
+	impl CommitmentScheme for Kate {
+		const ID: CommitmentSchemeId = CommitmentSchemeId::Kate;
+
+		fn build(
+			app_extrinsics: Vec<AppExtrinsic>,
+			data_root: H256,
+			block_length: BlockLength,
+			block_number: u32,
+			seed: Seed,
+			version: HeaderExtensionVersion,
+		) -> HeaderExtension {
+			hosted_header_builder::build(
+				app_extrinsics,
+				data_root,
+				block_length,
+				block_number,
+				seed,
+				version,
+			)
+		}
+
+		#[cfg(feature = "std")]
+		fn commit(grid: &EvaluationGrid) -> Vec<u8> {
+			use once_cell::sync::Lazy;
+
+			// couscous has pp for degree upto 1024
+			static PMP: Lazy<kate::pmp::m1_blst::M1NoPrecomp> =
+				Lazy::new(kate::couscous::multiproof_params);
+
+			grid.make_polynomial_grid()
+				.expect("Make polynomials cannot fail")
+				.extended_commitments(&*PMP, 2)
+				.expect("Extended commitments cannot fail")
+				.iter()
+				.flat_map(|c| c.to_bytes().expect("Commitment serialization cannot fail"))
+				.collect()
+		}
+	}
+
+	/// Keccak-256 Merkle root of the cells of each row, without erasure extension.
+	pub struct MerkleRows;
+
+	impl CommitmentScheme for MerkleRows {
+		const ID: CommitmentSchemeId = CommitmentSchemeId::MerkleRows;
+
+		fn build(
+			app_extrinsics: Vec<AppExtrinsic>,
+			data_root: H256,
+			block_length: BlockLength,
+			block_number: u32,
+			seed: Seed,
+			version: HeaderExtensionVersion,
+		) -> HeaderExtension {
+			hosted_header_builder::build_merkle_rows(
+				app_extrinsics,
+				data_root,
+				block_length,
+				block_number,
+				seed,
+				version,
+			)
+		}
+
+		#[cfg(feature = "std")]
+		fn commit(grid: &EvaluationGrid) -> Vec<u8> {
+			use binary_merkle_tree::merkle_root;
+			use sp_runtime::traits::Keccak256;
+
+			let dims = grid.dims();
+			let (rows, cols) = (
+				usize::from(dims.rows().get()),
+				usize::from(dims.cols().get()),
+			);
+
+			(0..rows)
+				.flat_map(|row| {
+					let cells = (0..cols).map(|col| {
+						grid.get::<usize, usize>(row, col)
+							.expect("Cell is inside the grid .qed")
+							.to_bytes()
+							.expect("Cell serialization cannot fail")
+					});
+					merkle_root::<Keccak256, _>(cells).0
+				})
+				.collect()
+		}
+	}
+
+	/// Zeroed commitments with the same size as `Kate` ones. Only for local testing.
+	pub struct Mock;
+
+	impl CommitmentScheme for Mock {
+		const ID: CommitmentSchemeId = CommitmentSchemeId::Mock;
+
+		fn build(
+			app_extrinsics: Vec<AppExtrinsic>,
+			data_root: H256,
+			block_length: BlockLength,
+			block_number: u32,
+			seed: Seed,
+			version: HeaderExtensionVersion,
+		) -> HeaderExtension {
+			hosted_header_builder::build_mock(
+				app_extrinsics,
+				data_root,
+				block_length,
+				block_number,
+				seed,
+				version,
+			)
+		}
+
+		#[cfg(feature = "std")]
+		fn commit(grid: &EvaluationGrid) -> Vec<u8> {
+			const COMMITMENT_SIZE: usize = 48;
+			let extended_rows = usize::from(grid.dims().rows().get()) * 2;
+			vec![0u8; extended_rows * COMMITMENT_SIZE]
+		}
+	}
+}
+
 pub mod da {
 	use core::marker::PhantomData;
 
@@ -36,15 +309,17 @@ pub mod da {
 			data_root: H256,
 			block_length: BlockLength,
 			block_number: u32,
+			version: HeaderExtensionVersion,
 		) -> HeaderExtension {
 			let seed = Self::random_seed::<T>();
 
-			super::hosted_header_builder::build(
+			<T::CommitmentScheme as CommitmentScheme>::build(
 				app_extrinsics,
 				data_root,
 				block_length,
 				block_number,
 				seed,
+				version,
 			)
 		}
 	}
@@ -54,29 +329,21 @@ pub mod da {
 pub trait HeaderExtensionBuilder {
 	type Header: sp_runtime::traits::Header + ExtendedHeader<u32, H256, Digest, HeaderExtension>;
 
-	/// Creates the header using the given parameters.
+	/// Creates the header extension of the given `version` using the given parameters.
 	fn build(
 		app_extrinsics: Vec<AppExtrinsic>,
 		data_root: H256,
 		block_length: BlockLength,
 		block_number: u32,
+		version: HeaderExtensionVersion,
 	) -> HeaderExtension;
 
-	/// Generates a random seed using the _epoch seed_ and the _current block_ returned by
-	/// `T::Randomness` type.
+	/// Returns the padding seed following the `T::PaddingSeed` policy.
 	fn random_seed<T: Config>() -> Seed {
-		let seed = if cfg!(feature = "secure_padding_fill") {
-			let (epoch_seed, block_number) = <T as Config>::Randomness::random_seed();
-			let seed = <T as Config>::Hashing::hash_of(&(&epoch_seed, &block_number));
-			log::trace!(
-				target: LOG_TARGET,
-				"Header builder seed {seed:?} from epoch seed {epoch_seed:?} and block {block_number:?}");
-			seed
-		} else {
-			<T as Config>::Hash::default()
-		};
-
-		seed.into()
+		match T::PaddingSeed::get() {
+			PaddingSeedPolicy::Zero => Seed::default(),
+			PaddingSeedPolicy::Randomness => randomness_seed::<T>(),
+		}
 	}
 }
 
@@ -99,30 +366,26 @@ fn corrupt_commitment(block_number: u32, commitment: &mut Vec<u8>) {
 	}
 }
 
+/// Creates the header extension of the given `version`, committing to the data grid with the
+/// commitment scheme `S`.
 #[cfg(feature = "std")]
-pub fn build_extension(
+pub fn build_extension<S: CommitmentScheme>(
 	app_extrinsics: &[AppExtrinsic],
 	data_root: H256,
 	block_length: BlockLength,
 	_block_number: u32,
 	seed: Seed,
+	version: HeaderExtensionVersion,
 ) -> HeaderExtension {
 	use avail_base::metrics::avail::HeaderExtensionBuilderMetrics;
 	use avail_core::header::extension::{v1, v2};
-	use kate::gridgen::AsBytes;
-	use once_cell::sync::Lazy;
 
 	let build_extension_start = std::time::Instant::now();
 
-	// couscous has pp for degree upto 1024
-	static PMP: Lazy<kate::pmp::m1_blst::M1NoPrecomp> =
-		Lazy::new(kate::couscous::multiproof_params);
-
-	const MIN_WIDTH: usize = 4;
 	let timer = std::time::Instant::now();
 	let grid = kate::gridgen::EvaluationGrid::from_extrinsics(
 		app_extrinsics.to_vec(),
-		MIN_WIDTH,
+		MIN_GRID_WIDTH,
 		block_length.cols.0.saturated_into(), // even if we run on a u16 target this is fine
 		block_length.rows.0.saturated_into(),
 		seed,
@@ -133,14 +396,7 @@ pub fn build_extension(
 	HeaderExtensionBuilderMetrics::observe_evaluation_grid_build_time(timer.elapsed());
 
 	let timer = std::time::Instant::now();
-	let commitment = grid
-		.make_polynomial_grid()
-		.expect("Make polynomials cannot fail")
-		.extended_commitments(&*PMP, 2)
-		.expect("Extended commitments cannot fail")
-		.iter()
-		.flat_map(|c| c.to_bytes().expect("Commitment serialization cannot fail"))
-		.collect::<Vec<u8>>();
+	let commitment = S::commit(&grid);
 
 	// Commitment Build Time Metrics
 	HeaderExtensionBuilderMetrics::observe_commitment_build_time(timer.elapsed());
@@ -154,51 +410,53 @@ pub fn build_extension(
 	HeaderExtensionBuilderMetrics::observe_grid_cols(cols as f64);
 
 	let app_lookup = grid.lookup().clone();
-	// **NOTE:** Header extension V2 is not yet enable by default.
-	if cfg!(feature = "header_extension_v2") {
-		use avail_core::kate_commitment::v2::KateCommitment;
-		#[allow(unused_mut)]
-		let mut kate = KateCommitment::new(rows, cols, data_root, commitment);
-
-		#[cfg(feature = "header_commitment_corruption")]
-		if _block_number > 20 {
-			corrupt_commitment(_block_number, &mut kate.commitment);
-		}
-
-		// Total Execution Time Metrics
-		HeaderExtensionBuilderMetrics::observe_total_execution_time(
-			build_extension_start.elapsed(),
-		);
-
-		v2::HeaderExtension {
-			app_lookup,
-			commitment: kate,
-		}
-		.into()
-	} else {
-		#[allow(unused_mut)]
-		let mut kate = avail_core::kate_commitment::v1::KateCommitment {
-			rows,
-			cols,
-			commitment,
-			data_root,
-		};
-
-		#[cfg(feature = "header_commitment_corruption")]
-		if _block_number > 20 {
-			corrupt_commitment(_block_number, &mut kate.commitment);
-		}
-
-		// Total Execution Time Metrics
-		HeaderExtensionBuilderMetrics::observe_total_execution_time(
-			build_extension_start.elapsed(),
-		);
-
-		v1::HeaderExtension {
-			app_lookup,
-			commitment: kate,
-		}
-		.into()
+	match version {
+		HeaderExtensionVersion::V1 => {
+			#[allow(unused_mut)]
+			let mut kate = avail_core::kate_commitment::v1::KateCommitment {
+				rows,
+				cols,
+				commitment,
+				data_root,
+			};
+
+			#[cfg(feature = "header_commitment_corruption")]
+			if _block_number > 20 {
+				corrupt_commitment(_block_number, &mut kate.commitment);
+			}
+
+			// Total Execution Time Metrics
+			HeaderExtensionBuilderMetrics::observe_total_execution_time(
+				build_extension_start.elapsed(),
+			);
+
+			v1::HeaderExtension {
+				app_lookup,
+				commitment: kate,
+			}
+			.into()
+		},
+		HeaderExtensionVersion::V2 => {
+			use avail_core::kate_commitment::v2::KateCommitment;
+			#[allow(unused_mut)]
+			let mut kate = KateCommitment::new(rows, cols, data_root, commitment);
+
+			#[cfg(feature = "header_commitment_corruption")]
+			if _block_number > 20 {
+				corrupt_commitment(_block_number, &mut kate.commitment);
+			}
+
+			// Total Execution Time Metrics
+			HeaderExtensionBuilderMetrics::observe_total_execution_time(
+				build_extension_start.elapsed(),
+			);
+
+			v2::HeaderExtension {
+				app_lookup,
+				commitment: kate,
+			}
+			.into()
+		},
 	}
 }
 
@@ -215,6 +473,73 @@ pub trait HostedHeaderBuilder {
 		block_number: u32,
 		seed: Seed,
 	) -> HeaderExtension {
-		build_extension(&app_extrinsics, data_root, block_length, block_number, seed)
+		build_extension::<commitment::Kate>(
+			&app_extrinsics,
+			data_root,
+			block_length,
+			block_number,
+			seed,
+			HeaderExtensionVersion::V1,
+		)
+	}
+
+	/// Creates the header extension of the given `version` using the given parameters.
+	#[version(2)]
+	fn build(
+		app_extrinsics: Vec<AppExtrinsic>,
+		data_root: H256,
+		block_length: BlockLength,
+		block_number: u32,
+		seed: Seed,
+		version: HeaderExtensionVersion,
+	) -> HeaderExtension {
+		build_extension::<commitment::Kate>(
+			&app_extrinsics,
+			data_root,
+			block_length,
+			block_number,
+			seed,
+			version,
+		)
+	}
+
+	/// Creates the header extension of the given `version`, committing to the data grid with
+	/// a Merkle root per row.
+	fn build_merkle_rows(
+		app_extrinsics: Vec<AppExtrinsic>,
+		data_root: H256,
+		block_length: BlockLength,
+		block_number: u32,
+		seed: Seed,
+		version: HeaderExtensionVersion,
+	) -> HeaderExtension {
+		build_extension::<commitment::MerkleRows>(
+			&app_extrinsics,
+			data_root,
+			block_length,
+			block_number,
+			seed,
+			version,
+		)
+	}
+
+	/// Creates the header extension of the given `version` with zeroed commitments. Only for
+	/// local testing.
+	fn build_mock(
+		app_extrinsics: Vec<AppExtrinsic>,
+		data_root: H256,
+		block_length: BlockLength,
+		block_number: u32,
+		seed: Seed,
+		version: HeaderExtensionVersion,
+	) -> HeaderExtension {
+		build_extension::<commitment::Mock>(
+			&app_extrinsics,
+			data_root,
+			block_length,
+			block_number,
+			seed,
+			version,
+		)
 	}
 }
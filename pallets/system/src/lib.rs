@@ -89,7 +89,7 @@ use pallet_prelude::{BlockNumberFor, DaHeaderFor};
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::Serialize;
-use sp_core::storage::well_known_keys;
+use sp_core::{storage::well_known_keys, H256};
 use sp_io::hashing::blake2_256;
 #[cfg(any(feature = "std", test))]
 use sp_io::TestExternalities;
@@ -112,7 +112,10 @@ use sp_weights::{RuntimeDbWeight, Weight};
 
 pub mod header_builder;
 pub mod submitted_data;
-pub use header_builder::HeaderExtensionBuilder;
+pub use header_builder::{
+	CommitmentScheme, CommitmentSchemeId, HeaderExtensionBuilder,
+	HeaderExtensionVersion as ExtensionVersion, PaddingSeedPolicy,
+};
 
 pub mod limits;
 #[cfg(any(feature = "std", test))]
@@ -259,6 +262,10 @@ pub mod pallet {
 			type BlockWeights = ();
 			type BlockLength = ();
 			type DbWeight = ();
+			type CommitmentScheme = crate::header_builder::commitment::Kate;
+			type PaddingSeed = frame_support::traits::GetDefault;
+			type DataRootLeafVersion = frame_support::traits::GetDefault;
+			type DataRootMode = frame_support::traits::GetDefault;
 		}
 	}
 
@@ -345,6 +352,12 @@ pub mod pallet {
 		#[pallet::no_default]
 		type HeaderExtensionBuilder: header_builder::HeaderExtensionBuilder;
 
+		/// Commitment scheme used by the header extension builder.
+		type CommitmentScheme: header_builder::CommitmentScheme;
+
+		/// Source of the seed used to fill the padding cells of the data grid.
+		type PaddingSeed: Get<header_builder::PaddingSeedPolicy>;
+
 		/// Source of random seeds.
 		#[pallet::no_default]
 		type Randomness: Randomness<Self::Hash, BlockNumberFor<Self>>;
@@ -423,6 +436,12 @@ pub mod pallet {
 		type SubmittedDataExtractor: submitted_data::Extractor
 			+ submitted_data::Filter<Self::RuntimeCall>;
 
+		/// Format of the leaves of the data root.
+		type DataRootLeafVersion: Get<submitted_data::LeafVersion>;
+
+		/// Structure of the data root tree.
+		type DataRootMode: Get<submitted_data::DataRootMode>;
+
 		/// UncheckedExtrinsic Type used on Kate commitment & Data root calculation.
 		#[pallet::no_default]
 		type UncheckedExtrinsic: Into<AppExtrinsic>
@@ -722,6 +741,33 @@ pub mod pallet {
 	#[pallet::getter(fn block_length)]
 	pub type DynamicBlockLength<T: Config> = StorageValue<_, limits::BlockLength, ValueQuery>;
 
+	/// The version of the header extension built for the current block.
+	#[pallet::storage]
+	#[pallet::getter(fn header_extension_version)]
+	pub type HeaderExtensionVersion<T: Config> = StorageValue<_, ExtensionVersion, ValueQuery>;
+
+	/// The header extension version which will be enabled at the given block number.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_header_extension_version)]
+	pub type PendingHeaderExtensionVersion<T: Config> =
+		StorageValue<_, (BlockNumberFor<T>, ExtensionVersion)>;
+
+	/// The number of submitted data leaves of the data root of the last finalized block.
+	#[pallet::storage]
+	#[pallet::getter(fn data_root_leaves)]
+	pub type DataRootLeaves<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The data root of the last finalized block.
+	#[pallet::storage]
+	#[pallet::getter(fn data_root)]
+	pub type DataRoot<T: Config> = StorageValue<_, H256>;
+
+	/// Metrics of the data root of the last finalized block, like the number of extrinsics
+	/// dropped from it because they cannot be decoded.
+	#[pallet::storage]
+	#[pallet::getter(fn data_root_metrics)]
+	pub type DataRootMetrics<T: Config> = StorageValue<_, submitted_data::Metrics, ValueQuery>;
+
 	#[derive(DefaultNoBound)]
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
@@ -1461,6 +1507,14 @@ impl<T: Config> Pallet<T> {
 		<ParentHash<T>>::put(parent_hash);
 		<BlockHash<T>>::insert(*number - One::one(), parent_hash);
 
+		// Enable the scheduled header extension version, if any.
+		if let Some((at, version)) = PendingHeaderExtensionVersion::<T>::get() {
+			if *number >= at {
+				HeaderExtensionVersion::<T>::put(version);
+				PendingHeaderExtensionVersion::<T>::kill();
+			}
+		}
+
 		// Remove previous block data from storage
 		BlockWeight::<T>::kill();
 	}
@@ -1519,7 +1573,6 @@ impl<T: Config> Pallet<T> {
 		// stay to be inspected by the client and will be cleared by `Self::initialize`.
 		let number = <Number<T>>::get();
 		let parent_hash = <ParentHash<T>>::get();
-		let digest = <Digest<T>>::get();
 
 		let extrinsics = Self::take_extrinsics().collect::<Vec<_>>();
 		let opaques = extrinsics
@@ -1529,8 +1582,33 @@ impl<T: Config> Pallet<T> {
 			.collect::<Result<Vec<_>, _>>()
 			.expect("Any extrinsic MUST be decoded as OpaqueExtrinsic .qed");
 
-		let data_root =
-			submitted_data::extrinsics_root::<T::SubmittedDataExtractor, _>(opaques.iter());
+		let roots = submitted_data::data_roots::<T::SubmittedDataExtractor, _>(
+			opaques.iter(),
+			T::DataRootLeafVersion::get(),
+			T::DataRootMode::get(),
+		);
+		// Lets proofs of this block be built without loading all its submitted data first.
+		DataRootLeaves::<T>::put(roots.leaves);
+		// Lets pallets of the next block, like bridges, access the data root of this block.
+		DataRoot::<T>::put(roots.data_root);
+		// Lets operators detect extrinsics dropped from the data root.
+		DataRootMetrics::<T>::put(roots.metrics);
+
+		// Commit the data root of each application into the header digest, so light clients
+		// can verify the data of one application without the data of the whole block.
+		if !roots.app_roots.is_empty() {
+			let apps_root = submitted_data::apps_root(&roots.app_roots);
+			Self::deposit_log(generic::DigestItem::Consensus(
+				submitted_data::APPS_ROOT_ENGINE_ID,
+				apps_root.encode(),
+			));
+		}
+		// Tag headers whose commitments are not KZG ones, so light clients can tell which scheme
+		// built their extension.
+		if let Some(item) = <T::CommitmentScheme as CommitmentScheme>::ID.digest_item() {
+			Self::deposit_log(item);
+		}
+		let digest = <Digest<T>>::get();
 
 		// move block hash pruning window by one block
 		let block_hash_count = T::BlockHashCount::get();
@@ -1569,6 +1647,7 @@ impl<T: Config> Pallet<T> {
 			data_root,
 			block_length,
 			number.unique_saturated_into(),
+			Self::header_extension_version(),
 		);
 
 		let extrinsics_root = extrinsics_data_root::<T::Hashing>(extrinsics);
@@ -1597,6 +1676,15 @@ impl<T: Config> Pallet<T> {
 		header
 	}
 
+	/// Returns the header extension version used to build the header of block `number`, taking
+	/// into account any pending version switch.
+	pub fn header_extension_version_at(number: BlockNumberFor<T>) -> ExtensionVersion {
+		match PendingHeaderExtensionVersion::<T>::get() {
+			Some((at, version)) if number >= at => version,
+			_ => HeaderExtensionVersion::<T>::get(),
+		}
+	}
+
 	/// Deposits a log and ensures it matches the block's log data.
 	///
 	/// # <weight>
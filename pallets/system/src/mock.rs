@@ -70,6 +70,7 @@ parameter_types! {
 
 parameter_types! {
 	pub static Killed: Vec<u64> = vec![];
+	pub static PaddingSeed: PaddingSeedPolicy = PaddingSeedPolicy::Zero;
 }
 
 pub struct RecordKilled;
@@ -90,6 +91,7 @@ impl Config for Test {
 	type HeaderExtensionBuilder = frame_system::header_builder::da::HeaderExtensionBuilder<Test>;
 	type OnKilledAccount = RecordKilled;
 	type OnSetCode = ();
+	type PaddingSeed = PaddingSeed;
 	type PalletInfo = PalletInfo;
 	type Randomness = TestRandomness<Test>;
 	type RuntimeCall = RuntimeCall;
@@ -0,0 +1,1145 @@
+use core::fmt::Debug;
+
+use avail_core::{AppId, OpaqueExtrinsic};
+use binary_merkle_tree::{merkle_proof, merkle_root, verify_proof, Leaf, MerkleProof};
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_core::H256;
+use sp_io::hashing::keccak_256;
+use sp_runtime::{traits::Keccak256, ConsensusEngineId};
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+
+pub mod nmt;
+
+const LOG_TARGET: &str = "runtime::system::submitted_data";
+
+/// Engine id of the header digest item which contains the `apps_root` of the block.
+pub const APPS_ROOT_ENGINE_ID: ConsensusEngineId = *b"APPR";
+
+/// Information about `submitted_data_root` and `submitted_data_proof` methods.
+///
+/// The metrics of the data root of each block are stored in `DataRootMetrics`.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct Metrics {
+	/// Number of extrinsics containing one or more submitted data.
+	pub data_submit_extrinsics: u32,
+	/// Total number of submitted data.
+	pub data_submit_leaves: u32,
+	/// Total number of analysed extrinsic.
+	pub total_extrinsics: u32,
+	/// Number of extrinsics which cannot be decoded, so they are dropped from the data root.
+	pub failed_decode_extrinsics: u32,
+}
+
+/// Format of the leaves of the data root.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub enum LeafVersion {
+	/// Each leaf is the submitted data itself.
+	#[default]
+	V1,
+	/// Each leaf is a `ProvenanceLeaf`, so verifiers can check who submitted the data.
+	V2,
+}
+
+/// Structure of the data root tree.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub enum DataRootMode {
+	/// Binary Merkle tree with one leaf per submitted data, in block order.
+	#[default]
+	Binary,
+	/// Namespaced Merkle tree with one leaf per extrinsic, sorted by `AppId`. See `nmt`.
+	Namespaced,
+}
+
+/// Application and account which submitted some data.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DataSource {
+	pub app_id: AppId,
+	/// Account id of the transaction signer, or `H256::zero()` for unsigned transactions.
+	pub sender: H256,
+}
+
+/// Submitted data along with its source.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SubmittedData {
+	pub source: DataSource,
+	pub data: Vec<u8>,
+}
+
+impl SubmittedData {
+	pub fn new(source: DataSource, data: Vec<u8>) -> Self {
+		Self { source, data }
+	}
+
+	/// Returns the data root leaf of this data using the `version` format.
+	pub fn into_leaf(self, version: LeafVersion) -> Vec<u8> {
+		match version {
+			LeafVersion::V1 => self.data,
+			LeafVersion::V2 => ProvenanceLeaf::new(self.source, &self.data)
+				.to_bytes()
+				.to_vec(),
+		}
+	}
+}
+
+/// Data root leaf of `LeafVersion::V2`.
+///
+/// It is packed as `app_id (u32 big-endian) ++ sender ++ keccak_256(data)`, so it can be
+/// rebuilt by on-chain verifiers with `abi.encodePacked(uint32, bytes32, bytes32)`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProvenanceLeaf {
+	pub app_id: AppId,
+	pub sender: H256,
+	pub data_hash: H256,
+}
+
+impl ProvenanceLeaf {
+	/// Length of the packed leaf.
+	pub const LEN: usize = 4 + 32 + 32;
+
+	pub fn new(source: DataSource, data: &[u8]) -> Self {
+		Self {
+			app_id: source.app_id,
+			sender: source.sender,
+			data_hash: H256(keccak_256(data)),
+		}
+	}
+
+	pub fn to_bytes(&self) -> [u8; Self::LEN] {
+		let mut bytes = [0u8; Self::LEN];
+		bytes[..4].copy_from_slice(&self.app_id.0.to_be_bytes());
+		bytes[4..36].copy_from_slice(self.sender.as_bytes());
+		bytes[36..].copy_from_slice(self.data_hash.as_bytes());
+		bytes
+	}
+
+	/// Unpacks a leaf, returning `None` if `bytes` has not the expected length.
+	pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+		if bytes.len() != Self::LEN {
+			return None;
+		}
+		let mut app_id = [0u8; 4];
+		app_id.copy_from_slice(&bytes[..4]);
+		Some(Self {
+			app_id: AppId(u32::from_be_bytes(app_id)),
+			sender: H256::from_slice(&bytes[4..36]),
+			data_hash: H256::from_slice(&bytes[36..]),
+		})
+	}
+}
+
+/// Extracts the `data` field from some types of extrinsics.
+pub trait Extractor {
+	type Error: Debug;
+	/// Returns the `data` field of `encoded_extrinsic` if it contains one, like a
+	/// `Avail::SubmitData` call, along with the application and signer of the extrinsic.
+	///
+	/// The `metrics` will be used to write accountability information about the whole process.
+	fn extract(
+		extrinsic: &OpaqueExtrinsic,
+		metrics: &mut Metrics,
+	) -> Result<Vec<SubmittedData>, Self::Error>;
+}
+
+#[cfg(any(feature = "std", test))]
+impl Extractor for () {
+	type Error = ();
+
+	fn extract(_: &OpaqueExtrinsic, _: &mut Metrics) -> Result<Vec<SubmittedData>, ()> {
+		Ok(vec![])
+	}
+}
+
+/// It is similar to `Extractor` but it uses `C` type for calls, instead of `AppExtrinsic`.
+pub trait Filter<C> {
+	/// Returns the `data` field of `call` if it is a one or multiple valid `da_ctrl::submit_data` call.
+	///
+	/// The `source` of the transaction containing `call` is attached to each returned data.
+	fn filter(call: C, source: DataSource, metrics: &mut Metrics) -> Vec<SubmittedData>;
+}
+
+#[cfg(any(feature = "std", test))]
+impl<C> Filter<C> for () {
+	fn filter(_: C, _: DataSource, _: &mut Metrics) -> Vec<SubmittedData> {
+		vec![]
+	}
+}
+
+/// Relation of a call with the submitted data.
+#[derive(Debug, PartialEq, Eq)]
+pub enum DataCallKind<'a, C> {
+	/// The call submits `data`, like `DataAvailability::submit_data`.
+	Data(&'a [u8]),
+	/// The call dispatches the wrapped calls, like `Utility::batch` or `Sudo::sudo`.
+	Wrapper(Vec<&'a C>),
+	/// The call neither submits data nor wraps other calls.
+	Other,
+}
+
+/// Marks the calls which carry submitted data, directly or through the calls they wrap.
+///
+/// It is implemented by the runtime call and shared by the data root `Filter` and the
+/// `CheckAppId` signed extension, so both agree on which calls carry data.
+pub trait DataCall: Sized {
+	fn data_call_kind(&self) -> DataCallKind<'_, Self>;
+}
+
+/// Maximum number of wrappers nested around a call, e.g. `sudo(batch(submit_data))` has two.
+///
+/// It bounds the walk of `inner_calls`, shared by the data root `Filter` and `CheckAppId`.
+pub const MAX_WRAPPER_DEPTH: usize = 4;
+
+/// Error of `inner_calls` when some call is nested deeper than `MAX_WRAPPER_DEPTH` wrappers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MaxWrapperDepthExceeded;
+
+/// Returns the calls reached from `call` through its wrappers, in dispatch order: `Some(data)`
+/// for each data call and `None` for any other call.
+///
+/// It fails if any call is nested deeper than `MAX_WRAPPER_DEPTH` wrappers.
+pub fn inner_calls<C: DataCall>(call: &C) -> Result<Vec<Option<&[u8]>>, MaxWrapperDepthExceeded> {
+	let mut inner = Vec::new();
+	let mut stack = sp_std::vec![(call, 0)];
+	while let Some((call, depth)) = stack.pop() {
+		match call.data_call_kind() {
+			DataCallKind::Data(data) => inner.push(Some(data)),
+			DataCallKind::Wrapper(calls) => {
+				if depth >= MAX_WRAPPER_DEPTH {
+					return Err(MaxWrapperDepthExceeded);
+				}
+				stack.extend(calls.into_iter().rev().map(|call| (call, depth + 1)));
+			},
+			DataCallKind::Other => inner.push(None),
+		}
+	}
+	Ok(inner)
+}
+
+/// Returns the non-empty submitted data of `call` and of all the calls it wraps, in dispatch
+/// order.
+///
+/// Nothing is returned if the calls are nested deeper than `MAX_WRAPPER_DEPTH`. `CheckAppId`
+/// rejects such transactions, unless they use `AppId(0)`.
+pub fn data_of<C: DataCall>(
+	call: &C,
+	source: DataSource,
+	metrics: &mut Metrics,
+) -> Vec<SubmittedData> {
+	let Ok(inner) = inner_calls(call) else {
+		log::warn!(
+			target: LOG_TARGET,
+			"Calls nested deeper than {MAX_WRAPPER_DEPTH} wrappers are skipped"
+		);
+		return Vec::new();
+	};
+
+	inner
+		.into_iter()
+		.flatten()
+		.filter(|data| !data.is_empty())
+		.map(|data| {
+			metrics.data_submit_leaves += 1;
+			SubmittedData::new(source, data.to_vec())
+		})
+		.collect()
+}
+
+/// Concatenates the data of the calls of one transaction, which is a single leaf in the proofs
+/// built from calls.
+fn concat_data(submitted: Vec<SubmittedData>, source: DataSource) -> SubmittedData {
+	let data = submitted
+		.into_iter()
+		.flat_map(|submitted| submitted.data)
+		.collect();
+	SubmittedData::new(source, data)
+}
+
+/// Joins the data of the calls of one transaction into its leaf of the application root.
+///
+/// Each data is prefixed by its compact length, so the leaf commits to where each data ends.
+fn app_leaf(submitted: &[SubmittedData], source: DataSource) -> SubmittedData {
+	let data = submitted
+		.iter()
+		.flat_map(|submitted| submitted.data.encode())
+		.collect();
+	SubmittedData::new(source, data)
+}
+
+fn extract_and_inspect<E>(opaque: &OpaqueExtrinsic, metrics: &mut Metrics) -> Vec<SubmittedData>
+where
+	E: Extractor,
+	E::Error: Debug,
+{
+	let extracted = E::extract(opaque, metrics);
+	metrics.total_extrinsics += 1;
+	if let Err(e) = extracted.as_ref() {
+		log::error!("Extractor cannot decode opaque: {e:?}");
+		metrics.failed_decode_extrinsics += 1;
+	}
+	let submitted = extracted
+		.unwrap_or_default()
+		.into_iter()
+		.filter(|submitted| !submitted.data.is_empty())
+		.collect::<Vec<_>>();
+	if !submitted.is_empty() {
+		metrics.data_submit_extrinsics += 1;
+	}
+	submitted
+}
+
+/// Construct a root hash of Binary Merkle Tree created from given filtered `app_extrincs`.
+pub fn extrinsics_root<'a, E, I>(opaque_itr: I, version: LeafVersion) -> H256
+where
+	E: Extractor,
+	E::Error: Debug,
+	I: Iterator<Item = &'a OpaqueExtrinsic>,
+{
+	extrinsics_root_and_leaves::<E, I>(opaque_itr, version).0
+}
+
+/// Same as `extrinsics_root` but it also returns the number of leaves of the tree.
+pub fn extrinsics_root_and_leaves<'a, E, I>(opaque_itr: I, version: LeafVersion) -> (H256, u32)
+where
+	E: Extractor,
+	E::Error: Debug,
+	I: Iterator<Item = &'a OpaqueExtrinsic>,
+{
+	let mut metrics = Metrics::default();
+	let submitted_data = opaque_itr
+		.flat_map(|ext| extract_and_inspect::<E>(ext, &mut metrics))
+		.map(|submitted| submitted.into_leaf(version));
+	let (root, leaves) = root(submitted_data);
+	log_root(root, leaves, &metrics);
+
+	(root, leaves)
+}
+
+/// Construct the data root of `opaque_itr` using the `mode` tree and `version` leaves. It also
+/// returns the number of leaves of the tree.
+pub fn data_root<'a, E, I>(opaque_itr: I, version: LeafVersion, mode: DataRootMode) -> (H256, u32)
+where
+	E: Extractor,
+	E::Error: Debug,
+	I: Iterator<Item = &'a OpaqueExtrinsic>,
+{
+	let roots = data_roots::<E, I>(opaque_itr, version, mode);
+	(roots.data_root, roots.leaves)
+}
+
+/// Roots of the submitted data of a block.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DataRoots {
+	/// Data root, as built by `data_root`.
+	pub data_root: H256,
+	/// Number of leaves of the data root.
+	pub leaves: u32,
+	/// Metrics collected while building the roots.
+	pub metrics: Metrics,
+	/// Data root of each application, as `(AppId, root)` pairs in increasing `AppId` order.
+	///
+	/// Each extrinsic with submitted data is one leaf of the root of its application.
+	pub app_roots: Vec<(AppId, H256)>,
+}
+
+/// Same as `data_root` but it also returns the `Metrics` and the application roots, built in the
+/// same pass over `opaque_itr`.
+pub fn data_roots<'a, E, I>(opaque_itr: I, version: LeafVersion, mode: DataRootMode) -> DataRoots
+where
+	E: Extractor,
+	E::Error: Debug,
+	I: Iterator<Item = &'a OpaqueExtrinsic>,
+{
+	let mut metrics = Metrics::default();
+	let mut binary = StreamingProof::new();
+	let mut namespaced = Vec::new();
+	let mut apps = BTreeMap::<AppId, StreamingProof>::new();
+	for opaque in opaque_itr {
+		let extracted = extract_and_inspect::<E>(opaque, &mut metrics);
+		let Some(source) = extracted.first().map(|submitted| submitted.source) else {
+			continue;
+		};
+
+		apps.entry(source.app_id)
+			.or_insert_with(StreamingProof::new)
+			.push(app_leaf(&extracted, source).into_leaf(version), false);
+		match mode {
+			DataRootMode::Binary => {
+				for submitted in extracted {
+					binary.push(submitted.into_leaf(version), false);
+				}
+			},
+			DataRootMode::Namespaced => {
+				let submitted = concat_data(extracted, source);
+				namespaced.push((source.app_id, submitted.into_leaf(version)));
+			},
+		}
+	}
+
+	let (data_root, leaves) = match mode {
+		DataRootMode::Binary => {
+			let leaves = u32::try_from(binary.leaves).unwrap_or(u32::MAX);
+			let root = binary.root();
+			log_root(root, leaves, &metrics);
+
+			(root, leaves)
+		},
+		DataRootMode::Namespaced => {
+			let (root, leaves) = nmt::root(namespaced);
+			log::debug!(
+				target: LOG_TARGET,
+				"Build namespaced data root: {:?}, leaves: {leaves}, metrics: {:?}",
+				root,
+				metrics
+			);
+
+			(root, leaves)
+		},
+	};
+	let app_roots = apps
+		.into_iter()
+		.map(|(app_id, tree)| (app_id, tree.root()))
+		.collect();
+
+	DataRoots {
+		data_root,
+		leaves,
+		metrics,
+		app_roots,
+	}
+}
+
+/// Construct a root hash of Binary Merkle Tree created from given filtered `calls` and the
+/// source of their transactions.
+pub fn calls_root<F, C, I>(calls: I, version: LeafVersion) -> H256
+where
+	F: Filter<C>,
+	I: Iterator<Item = (DataSource, C)>,
+{
+	let mut metrics = Metrics::default();
+	let submitted_data = calls
+		.flat_map(|(source, c)| F::filter(c, source, &mut metrics))
+		.map(|submitted| submitted.into_leaf(version));
+	let (root, leaves) = root(submitted_data);
+	log_root(root, leaves, &metrics);
+
+	root
+}
+
+/// Construct a root hash of a Binary Merkle Tree created from given leaves. It also returns the
+/// number of leaves.
+///
+/// In case an empty list of leaves is passed the function returns a 0-filled hash.
+fn root<I: Iterator<Item = Vec<u8>>>(submitted_data: I) -> (H256, u32) {
+	let mut leaves = 0u32;
+	let submitted_data = submitted_data.inspect(|_| leaves = leaves.saturating_add(1));
+	let root = merkle_root::<Keccak256, _>(submitted_data);
+
+	(root, leaves)
+}
+
+/// Logs the `root` and the `metrics` collected while extracting its `leaves`.
+fn log_root(root: H256, leaves: u32, metrics: &Metrics) {
+	log::debug!(
+		target: LOG_TARGET,
+		"Build submitted data root: {:?}, leaves: {leaves}, metrics: {:?}",
+		root,
+		metrics
+	);
+}
+
+/// Incremental builder of the Keccak-256 Binary Merkle Tree used by `binary_merkle_tree`.
+///
+/// Leaves are hashed as soon as they are pushed, and only the roots of the completed subtrees
+/// are kept, so the memory footprint is `O(log n)` instead of `O(n)`. If `target` is set, the
+/// Merkle proof of that leaf is collected along the way.
+struct StreamingProof {
+	/// Roots of completed subtrees as `(level, index, hash)`, with decreasing levels.
+	subtrees: Vec<(u32, usize, H256)>,
+	/// Number of pushed leaves.
+	leaves: usize,
+	/// Index and data of the leaf to prove.
+	target: Option<(usize, Vec<u8>)>,
+	/// Sibling of the target path at each level, if any.
+	siblings: Vec<Option<H256>>,
+}
+
+impl StreamingProof {
+	fn new() -> Self {
+		Self {
+			subtrees: Vec::new(),
+			leaves: 0,
+			target: None,
+			siblings: Vec::new(),
+		}
+	}
+
+	/// Pushes the next leaf. If `is_target`, its proof will be generated.
+	fn push(&mut self, data: Vec<u8>, is_target: bool) {
+		let index = self.leaves;
+		let hash = H256(keccak_256(&data));
+		if is_target {
+			self.target = Some((index, data));
+			// Left siblings of the target path are the completed subtrees so far.
+			for (level, index, hash) in self.subtrees.clone() {
+				self.record(level, index, hash);
+			}
+		}
+		self.leaves = self.leaves.saturating_add(1);
+
+		self.record(0, index, hash);
+		let (mut level, mut index, mut hash) = (0, index, hash);
+		while let Some(&(top_level, _, top_hash)) = self.subtrees.last() {
+			if top_level != level {
+				break;
+			}
+			self.subtrees.pop();
+			(level, index, hash) = (level + 1, index / 2, combine(top_hash, hash));
+			self.record(level, index, hash);
+		}
+		self.subtrees.push((level, index, hash));
+	}
+
+	/// Stores `hash` if the node `(level, index)` is a sibling of the target path.
+	fn record(&mut self, level: u32, index: usize, hash: H256) {
+		let Some((target, _)) = self.target.as_ref() else {
+			return;
+		};
+		if (target >> level) ^ 1 != index {
+			return;
+		}
+		let level = level as usize;
+		if self.siblings.len() <= level {
+			self.siblings.resize(level + 1, None);
+		}
+		self.siblings[level] = Some(hash);
+	}
+
+	/// Folds the remaining subtrees and returns the proof of the target leaf, if any.
+	///
+	/// The last node of a level without sibling is promoted to the next level, like in
+	/// `binary_merkle_tree`.
+	fn finish(mut self) -> Option<MerkleProof<H256, Vec<u8>>> {
+		let root = self.fold()?;
+		let (leaf_index, leaf) = self.target?;
+		Some(MerkleProof {
+			root,
+			proof: self.siblings.into_iter().flatten().collect(),
+			number_of_leaves: self.leaves,
+			leaf_index,
+			leaf,
+		})
+	}
+}
+
+impl StreamingProof {
+	/// Returns the root of the tree, or a 0-filled hash if no leaf was pushed.
+	fn root(mut self) -> H256 {
+		self.fold().unwrap_or_default()
+	}
+
+	fn fold(&mut self) -> Option<H256> {
+		let (_, _, mut root) = self.subtrees.pop()?;
+		while let Some((level, index, left)) = self.subtrees.pop() {
+			self.record(level, index + 1, root);
+			root = combine(left, root);
+			self.record(level + 1, index / 2, root);
+		}
+		Some(root)
+	}
+}
+
+/// Hashes the concatenation of two nodes.
+fn combine(left: H256, right: H256) -> H256 {
+	let mut combined = [0u8; 64];
+	combined[..32].copy_from_slice(left.as_bytes());
+	combined[32..].copy_from_slice(right.as_bytes());
+	H256(keccak_256(&combined))
+}
+
+/// Creates the Merkle Proof of the submitted data items in `app_extrinsics` filtered and
+/// extracted by `E` and the given `data_index`.
+///
+/// If `data_index` is greater than the number of Merkle leaves, it will return `None`.
+///
+/// Submitted data is hashed as it is extracted, so only one extrinsic is kept in memory.
+pub fn extrinsics_proof<'a, E, I>(
+	app_extrinsics: I,
+	data_index: u32,
+	version: LeafVersion,
+) -> Option<MerkleProof<H256, Vec<u8>>>
+where
+	E: Extractor,
+	E::Error: Debug,
+	I: Iterator<Item = &'a OpaqueExtrinsic>,
+{
+	let mut metrics = Metrics::default();
+	let data_index = usize::try_from(data_index).ok()?;
+
+	let mut tree = StreamingProof::new();
+	for submitted in app_extrinsics.flat_map(|ext| extract_and_inspect::<E>(ext, &mut metrics)) {
+		let is_target = tree.leaves == data_index;
+		tree.push(submitted.into_leaf(version), is_target);
+	}
+
+	proof(tree, data_index, &metrics)
+}
+
+/// Creates the Merkle Proof of the submitted data items in `calls` filtered by `F` and
+/// the given `data_index`. Each item of `calls` is the source and call of one transaction.
+///
+/// If `transaction_index` is greater than the number transactions in the block, it will return `None`.
+/// If `data_index` is greater than the number of Merkle leaves, it will return `None`.
+///
+/// Submitted data is hashed as it is filtered, so only one call is kept in memory.
+pub fn calls_proof<F, I, C>(
+	calls: I,
+	transaction_index: u32,
+	version: LeafVersion,
+) -> Option<MerkleProof<H256, Vec<u8>>>
+where
+	F: Filter<C>,
+	I: Iterator<Item = (DataSource, C)>,
+{
+	let mut metrics = Metrics::default();
+	let transaction_index = usize::try_from(transaction_index).ok()?;
+
+	let mut tree = StreamingProof::new();
+	let mut data_index = None;
+	for (index, (source, call)) in calls.enumerate() {
+		let submitted = concat_data(F::filter(call, source, &mut metrics), source);
+		if submitted.data.is_empty() {
+			continue;
+		}
+
+		let is_target = index == transaction_index;
+		if is_target {
+			data_index = Some(tree.leaves);
+		}
+		tree.push(submitted.into_leaf(version), is_target);
+	}
+
+	proof(tree, data_index?, &metrics)
+}
+
+/// Construct a Merkle Proof for `submit_data` given by `data_index` and stores
+/// information about the process into `metrics`.
+///
+/// If `data_index` is greater than the number of Merkle leaves, it will return `None`.
+fn proof(
+	tree: StreamingProof,
+	data_index: usize,
+	metrics: &Metrics,
+) -> Option<MerkleProof<H256, Vec<u8>>> {
+	if data_index >= tree.leaves {
+		return None;
+	}
+
+	let proof = tree.finish()?;
+	log::debug!(
+		target: LOG_TARGET,
+		"Build submitted data proof of index {data_index}: {:?} metrics: {:?}",
+		proof,
+		metrics
+	);
+
+	Some(proof)
+}
+
+/// Proof of the submitted data of one transaction under the data root of its application, and
+/// of that application root under the `apps_root` of the block.
+#[derive(Debug, PartialEq, Eq)]
+pub struct AppDataProof {
+	pub app_id: AppId,
+	/// Proof of the transaction data under the application root.
+	pub data_proof: MerkleProof<H256, Vec<u8>>,
+	/// Proof of the encoded `(AppId, H256)` application root under the `apps_root`.
+	pub app_root_proof: MerkleProof<H256, Vec<u8>>,
+}
+
+/// Construct the root hash of the Binary Merkle Tree of the encoded `app_roots`.
+///
+/// In case an empty list of application roots is passed the function returns a 0-filled hash.
+pub fn apps_root(app_roots: &[(AppId, H256)]) -> H256 {
+	merkle_root::<Keccak256, _>(app_roots.iter().map(Encode::encode))
+}
+
+/// Creates the proof of the submitted data of `transaction_index` in `calls` under the root of
+/// its application, and the proof of that root under the `apps_root`.
+///
+/// If `transaction_index` has no submitted data, it will return `None`.
+pub fn app_calls_proof<F, I, C>(
+	calls: I,
+	transaction_index: u32,
+	version: LeafVersion,
+) -> Option<AppDataProof>
+where
+	F: Filter<C>,
+	I: Iterator<Item = (DataSource, C)>,
+{
+	let mut metrics = Metrics::default();
+	let transaction_index = usize::try_from(transaction_index).ok()?;
+
+	let mut trees = BTreeMap::<AppId, StreamingProof>::new();
+	let mut target_app = None;
+	for (index, (source, call)) in calls.enumerate() {
+		let submitted = F::filter(call, source, &mut metrics)
+			.into_iter()
+			.filter(|submitted| !submitted.data.is_empty())
+			.collect::<Vec<_>>();
+		if submitted.is_empty() {
+			continue;
+		}
+		let submitted = app_leaf(&submitted, source);
+
+		let is_target = index == transaction_index;
+		if is_target {
+			target_app = Some(source.app_id);
+		}
+		trees
+			.entry(source.app_id)
+			.or_insert_with(StreamingProof::new)
+			.push(submitted.into_leaf(version), is_target);
+	}
+	let target_app = target_app?;
+
+	let mut data_proof = None;
+	let mut app_roots = Vec::with_capacity(trees.len());
+	for (app_id, tree) in trees {
+		let root = if app_id == target_app {
+			let proof = tree.finish()?;
+			let root = proof.root;
+			data_proof = Some(proof);
+			root
+		} else {
+			tree.root()
+		};
+		app_roots.push((app_id, root));
+	}
+
+	let app_index = app_roots.iter().position(|(id, _)| *id == target_app)?;
+	let leaves = app_roots.iter().map(Encode::encode).collect::<Vec<_>>();
+	let app_root_proof = merkle_proof::<Keccak256, _, _>(leaves, app_index);
+	log::debug!(
+		target: LOG_TARGET,
+		"Build app data proof of transaction {transaction_index}: {:?} metrics: {:?}",
+		app_root_proof,
+		metrics
+	);
+
+	Some(AppDataProof {
+		app_id: target_app,
+		data_proof: data_proof?,
+		app_root_proof,
+	})
+}
+
+/// Creates the proof of all the submitted data of `app_id` in `calls` under the namespaced data
+/// root, or of its absence if `app_id` has no submitted data.
+pub fn namespace_calls_proof<F, I, C>(
+	calls: I,
+	app_id: AppId,
+	version: LeafVersion,
+) -> nmt::NamespaceProof
+where
+	F: Filter<C>,
+	I: Iterator<Item = (DataSource, C)>,
+{
+	let mut metrics = Metrics::default();
+	let leaves = calls
+		.filter_map(|(source, call)| {
+			let submitted = concat_data(F::filter(call, source, &mut metrics), source);
+			(!submitted.data.is_empty()).then(|| (source.app_id, submitted.into_leaf(version)))
+		})
+		.collect();
+
+	let proof = nmt::proof(leaves, app_id);
+	log::debug!(
+		target: LOG_TARGET,
+		"Build namespace proof of app {app_id:?}: {:?} metrics: {:?}",
+		proof,
+		metrics
+	);
+	proof
+}
+
+/// Verify that `proof` contains all the submitted data of `app_id` under the namespaced data
+/// `root`, or that `app_id` has no submitted data.
+pub fn verify_namespace(root: H256, app_id: AppId, proof: &nmt::NamespaceProof) -> bool {
+	nmt::verify(root, app_id, proof)
+}
+
+/// Verify Merkle Proof correctness versus given root hash.
+///
+/// The proof is NOT expected to contain leaf hash as the first
+/// element, but only all adjacent nodes required to eventually by process of
+/// concatenating and hashing end up with given root hash.
+///
+/// The proof must not contain the root hash.
+pub fn verify<I>(
+	root: H256,
+	proof: I,
+	number_of_submitted_data: u32,
+	data_index: u32,
+	data_hash: H256,
+) -> bool
+where
+	I: IntoIterator<Item = H256>,
+{
+	let leaf = Leaf::Hash(data_hash);
+	verify_proof::<Keccak256, _, _>(
+		&root,
+		proof,
+		number_of_submitted_data as usize,
+		data_index as usize,
+		leaf,
+	)
+}
+
+#[cfg(test)]
+mod test {
+	use std::vec;
+
+	use avail_core::{AppId, OpaqueExtrinsic};
+	use binary_merkle_tree::{merkle_proof, merkle_root, verify_proof, Leaf};
+	use codec::{Decode, Encode};
+	use sp_core::H256;
+	use sp_io::hashing::keccak_256;
+	use sp_runtime::traits::Keccak256;
+	use test_case::test_case;
+
+	use crate::submitted_data::{
+		app_calls_proof, app_leaf, apps_root, calls_proof, data_roots, namespace_calls_proof, nmt,
+		verify_namespace, DataRootMode, DataSource, Extractor, Filter, LeafVersion, Metrics,
+		ProvenanceLeaf, StreamingProof, SubmittedData,
+	};
+
+	// dummy filter implementation that skips empty strings in vector
+	impl<C> Filter<C> for String
+	where
+		String: From<C>,
+	{
+		fn filter(d: C, source: DataSource, _: &mut Metrics) -> Vec<SubmittedData> {
+			let s = String::try_from(d).unwrap();
+			if s.is_empty() {
+				vec![]
+			} else {
+				vec![SubmittedData::new(source, s.into_bytes())]
+			}
+		}
+	}
+
+	/// Dummy extractor which cannot decode extrinsics ending with `0xff`, finds no data in
+	/// extrinsics ending with `0`, and submits the whole encoded extrinsic otherwise.
+	struct TestExtractor;
+
+	impl Extractor for TestExtractor {
+		type Error = ();
+
+		fn extract(
+			opaque: &OpaqueExtrinsic,
+			metrics: &mut Metrics,
+		) -> Result<Vec<SubmittedData>, ()> {
+			let encoded = opaque.encode();
+			match encoded.last() {
+				Some(0xff) => Err(()),
+				Some(0) => Ok(vec![]),
+				_ => {
+					metrics.data_submit_leaves += 1;
+					Ok(vec![SubmittedData::new(DataSource::default(), encoded)])
+				},
+			}
+		}
+	}
+
+	fn opaque(bytes: &[u8]) -> OpaqueExtrinsic {
+		OpaqueExtrinsic::decode(&mut bytes.to_vec().encode().as_slice()).unwrap()
+	}
+
+	#[test_case(DataRootMode::Binary; "binary tree")]
+	#[test_case(DataRootMode::Namespaced; "namespaced tree")]
+	fn data_root_metrics_count_failed_decodes(mode: DataRootMode) {
+		let opaques = vec![opaque(&[1, 2]), opaque(&[3, 0]), opaque(&[4, 0xff])];
+
+		let metrics = data_roots::<TestExtractor, _>(opaques.iter(), LeafVersion::V1, mode).metrics;
+
+		let expected = Metrics {
+			data_submit_extrinsics: 1,
+			data_submit_leaves: 1,
+			total_extrinsics: 3,
+			failed_decode_extrinsics: 1,
+		};
+		assert_eq!(metrics, expected);
+	}
+
+	fn source(app_id: u32, sender: u8) -> DataSource {
+		DataSource {
+			app_id: AppId(app_id),
+			sender: H256::repeat_byte(sender),
+		}
+	}
+
+	#[test]
+	fn test_data_proof_with_skipped_tx() {
+		let tx1_data: String = String::from("0");
+		let tx2_data: String = String::new(); // tx should be skipped
+		let tx3_data: String = String::from("1");
+		let tx4_data: String = String::from("2");
+
+		let submitted_data = vec![tx1_data, tx2_data, tx3_data, tx4_data]
+			.into_iter()
+			.map(|data| (DataSource::default(), data))
+			.collect::<Vec<_>>();
+
+		// leaf 0 044852b2a670ade5407e78fb2863c51de9fcb96542a07186fe3aeda6bb8a116d
+		// leaf 1 c89efdaa54c0f20c7adf612882df0950f5a951637e0307cdcb4c672f298b8bc6
+		// leaf 2 ad7c5bef027816a800da1736444fb58a807ef4c9603b7848673f7e3a68eb14a5
+		// intermediate root (leaf[0], leaf[1])  0b4aa17bff8fc189efb37609ac5ea9fca0df4c834a6fbac74b24c8119c40fef2
+		// data_root 0f1d00f4d84258b8d99bfc4748ff45b8039f108f43ca47e22ac5a1eab2e8c02d
+
+		if let Some(da_proof) =
+			calls_proof::<String, _, _>(submitted_data.clone().into_iter(), 0, LeafVersion::V1)
+		{
+			assert_eq!(da_proof.leaf_index, 0);
+			assert_eq!(
+				format!("{:#x}", da_proof.root),
+				"0x0f1d00f4d84258b8d99bfc4748ff45b8039f108f43ca47e22ac5a1eab2e8c02d"
+			);
+			assert_eq!(da_proof.proof.len(), 2);
+			assert_eq!(
+				format!("{:#x}", da_proof.proof[0]),
+				"0xc89efdaa54c0f20c7adf612882df0950f5a951637e0307cdcb4c672f298b8bc6"
+			);
+			assert_eq!(
+				format!("{:#x}", da_proof.proof[1]),
+				"0xad7c5bef027816a800da1736444fb58a807ef4c9603b7848673f7e3a68eb14a5"
+			);
+			assert_eq!(da_proof.number_of_leaves, 3);
+		} else {
+			panic!("Proof not generated for the transaction index 0!");
+		}
+
+		// proof should not be generated when there is not data
+		assert_eq!(
+			None,
+			calls_proof::<String, _, _>(submitted_data.clone().into_iter(), 1, LeafVersion::V1)
+		);
+
+		if let Some(da_proof) =
+			calls_proof::<String, _, _>(submitted_data.clone().into_iter(), 2, LeafVersion::V1)
+		{
+			assert_eq!(da_proof.leaf_index, 1);
+			assert_eq!(
+				format!("{:#x}", da_proof.root),
+				"0x0f1d00f4d84258b8d99bfc4748ff45b8039f108f43ca47e22ac5a1eab2e8c02d"
+			);
+			assert_eq!(da_proof.proof.len(), 2);
+			assert_eq!(
+				format!("{:#x}", da_proof.proof[0]),
+				"0x044852b2a670ade5407e78fb2863c51de9fcb96542a07186fe3aeda6bb8a116d"
+			);
+			assert_eq!(
+				format!("{:#x}", da_proof.proof[1]),
+				"0xad7c5bef027816a800da1736444fb58a807ef4c9603b7848673f7e3a68eb14a5"
+			);
+			assert_eq!(da_proof.number_of_leaves, 3);
+		} else {
+			panic!("Proof not generated for the transaction index 2!");
+		}
+
+		if let Some(da_proof) =
+			calls_proof::<String, _, _>(submitted_data.clone().into_iter(), 3, LeafVersion::V1)
+		{
+			assert_eq!(da_proof.leaf_index, 2);
+			assert_eq!(
+				format!("{:#x}", da_proof.root),
+				"0x0f1d00f4d84258b8d99bfc4748ff45b8039f108f43ca47e22ac5a1eab2e8c02d"
+			);
+			assert_eq!(da_proof.proof.len(), 1);
+			assert_eq!(
+				format!("{:#x}", da_proof.proof[0]),
+				"0x0b4aa17bff8fc189efb37609ac5ea9fca0df4c834a6fbac74b24c8119c40fef2"
+			);
+			assert_eq!(da_proof.number_of_leaves, 3);
+		} else {
+			panic!("Proof not generated for the transaction index 3!");
+		}
+
+		// submit index that does not exists and proof should not be generated
+		assert_eq!(
+			None,
+			calls_proof::<String, _, _>(submitted_data.clone().into_iter(), 15, LeafVersion::V1)
+		);
+	}
+
+	#[test]
+	fn streaming_proof_matches_merkle_proof() {
+		for leaves in 1..=33u32 {
+			let data = (0..leaves)
+				.map(|i| i.to_le_bytes().to_vec())
+				.collect::<Vec<_>>();
+
+			for target in 0..data.len() {
+				let mut tree = StreamingProof::new();
+				for (i, leaf) in data.iter().enumerate() {
+					tree.push(leaf.clone(), i == target);
+				}
+
+				let expected = merkle_proof::<Keccak256, _, _>(data.clone(), target);
+				assert_eq!(
+					tree.finish(),
+					Some(expected),
+					"{leaves} leaves, target {target}"
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn app_data_proof_links_data_to_apps_root() {
+		let calls = vec![
+			(source(1, 0), String::from("0")),
+			(source(2, 0), String::from("1")),
+			(source(1, 0), String::new()), // tx should be skipped
+			(source(1, 0), String::from("2")),
+			(source(0, 0), String::from("3")),
+		];
+
+		// Each leaf is the length-prefixed data of one transaction.
+		let leaf = |data: &str| data.as_bytes().encode();
+		let app_1_root = merkle_root::<Keccak256, _>(vec![leaf("0"), leaf("2")]);
+		let app_roots = vec![
+			(AppId(0), merkle_root::<Keccak256, _>(vec![leaf("3")])),
+			(AppId(1), app_1_root),
+			(AppId(2), merkle_root::<Keccak256, _>(vec![leaf("1")])),
+		];
+		let root = apps_root(&app_roots);
+
+		let proof =
+			app_calls_proof::<String, _, _>(calls.clone().into_iter(), 3, LeafVersion::V1).unwrap();
+		assert_eq!(proof.app_id, AppId(1));
+		assert_eq!(proof.data_proof.root, app_1_root);
+		assert_eq!(proof.data_proof.leaf, leaf("2"));
+		assert_eq!(proof.data_proof.leaf_index, 1);
+		assert_eq!(proof.app_root_proof.root, root);
+		assert_eq!(proof.app_root_proof.leaf, (AppId(1), app_1_root).encode());
+		assert!(verify_proof::<Keccak256, _, _>(
+			&root,
+			proof.app_root_proof.proof,
+			app_roots.len(),
+			1,
+			Leaf::Value(&(AppId(1), app_1_root).encode()),
+		));
+
+		assert_eq!(
+			app_calls_proof::<String, _, _>(calls.clone().into_iter(), 2, LeafVersion::V1),
+			None
+		);
+		assert_eq!(
+			app_calls_proof::<String, _, _>(calls.into_iter(), 5, LeafVersion::V1),
+			None
+		);
+	}
+
+	#[test]
+	fn app_leaf_commits_to_data_boundaries() {
+		let leaf = |data: &[&str]| {
+			let submitted = data
+				.iter()
+				.map(|d| SubmittedData::new(source(1, 0), d.as_bytes().to_vec()))
+				.collect::<Vec<_>>();
+			app_leaf(&submitted, source(1, 0)).data
+		};
+
+		assert_ne!(leaf(&["ab", "c"]), leaf(&["a", "bc"]));
+		assert_ne!(leaf(&["ab", "c"]), leaf(&["abc"]));
+	}
+
+	#[test]
+	fn app_roots_are_built_with_the_data_root() {
+		let opaques = vec![opaque(&[1, 2]), opaque(&[3, 0]), opaque(&[4, 5])];
+
+		let roots =
+			data_roots::<TestExtractor, _>(opaques.iter(), LeafVersion::V1, DataRootMode::Binary);
+
+		// `TestExtractor` submits the encoded extrinsics with data under `AppId(0)`.
+		let data = [opaques[0].encode(), opaques[2].encode()];
+		assert_eq!(roots.data_root, merkle_root::<Keccak256, _>(data.clone()));
+		assert_eq!(roots.leaves, 2);
+		let app_leaves = data.iter().map(|d| d.encode());
+		assert_eq!(
+			roots.app_roots,
+			vec![(AppId(0), merkle_root::<Keccak256, _>(app_leaves))]
+		);
+	}
+
+	#[test]
+	fn provenance_leaf_round_trip() {
+		let leaf = ProvenanceLeaf::new(source(7, 0xAA), b"data");
+		let bytes = leaf.to_bytes();
+
+		assert_eq!(&bytes[..4], &7u32.to_be_bytes());
+		assert_eq!(&bytes[4..36], &[0xAA; 32]);
+		assert_eq!(&bytes[36..], &keccak_256(b"data"));
+		assert_eq!(ProvenanceLeaf::from_bytes(&bytes), Some(leaf));
+		assert_eq!(ProvenanceLeaf::from_bytes(&bytes[1..]), None);
+	}
+
+	#[test]
+	fn v2_data_proof_commits_to_source() {
+		let calls = vec![
+			(source(1, 1), String::from("0")),
+			(source(2, 2), String::from("1")),
+			(source(3, 3), String::from("2")),
+		];
+		let leaves = calls
+			.iter()
+			.map(|(source, data)| ProvenanceLeaf::new(*source, data.as_bytes()).to_bytes())
+			.collect::<Vec<_>>();
+
+		let proof =
+			calls_proof::<String, _, _>(calls.clone().into_iter(), 1, LeafVersion::V2).unwrap();
+		assert_eq!(proof.root, merkle_root::<Keccak256, _>(leaves.clone()));
+		assert_eq!(proof.leaf, leaves[1].to_vec());
+		assert_eq!(
+			ProvenanceLeaf::from_bytes(&proof.leaf),
+			Some(ProvenanceLeaf {
+				app_id: AppId(2),
+				sender: H256::repeat_byte(2),
+				data_hash: H256(keccak_256(b"1")),
+			})
+		);
+
+		// The same data from another source has another root.
+		let v1_proof = calls_proof::<String, _, _>(calls.into_iter(), 1, LeafVersion::V1).unwrap();
+		assert_ne!(proof.root, v1_proof.root);
+	}
+
+	#[test]
+	fn namespace_calls_proof_skips_empty_calls() {
+		let calls = vec![
+			(source(2, 0), String::from("0")),
+			(source(1, 0), String::from("1")),
+			(source(1, 0), String::new()), // tx should be skipped
+			(source(2, 0), String::from("2")),
+		];
+		let (root, leaves) = nmt::root(vec![
+			(AppId(2), b"0".to_vec()),
+			(AppId(1), b"1".to_vec()),
+			(AppId(2), b"2".to_vec()),
+		]);
+		assert_eq!(leaves, 3);
+
+		let proof = namespace_calls_proof::<String, _, _>(
+			calls.clone().into_iter(),
+			AppId(2),
+			LeafVersion::V1,
+		);
+		assert_eq!(proof.leaves, vec![b"0".to_vec(), b"2".to_vec()]);
+		assert!(verify_namespace(root, AppId(2), &proof));
+
+		let proof =
+			namespace_calls_proof::<String, _, _>(calls.into_iter(), AppId(3), LeafVersion::V1);
+		assert!(proof.leaves.is_empty());
+		assert!(verify_namespace(root, AppId(3), &proof));
+	}
+}
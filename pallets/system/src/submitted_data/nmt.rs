@@ -0,0 +1,369 @@
+//! Namespaced Merkle tree of the submitted data, where the namespace of each leaf is the `AppId`
+//! of its transaction.
+//!
+//! Leaves are sorted by namespace and every node commits to the minimum and maximum namespace
+//! below it. A proof of the leaves of one namespace also proves that no other leaf of that
+//! namespace exists, and a proof of a single leaf of another namespace can show that a namespace
+//! has no leaves at all.
+//!
+//! The tree is split like RFC 6962: the left subtree of `n` leaves holds the largest power of two
+//! smaller than `n`.
+
+use avail_core::AppId;
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use sp_core::H256;
+use sp_io::hashing::keccak_256;
+use sp_runtime::RuntimeDebug;
+use sp_std::{iter::Peekable, vec::Vec};
+
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+
+/// Hash of a node and the namespace range of the leaves below it.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub struct NamespacedHash {
+	pub min: u32,
+	pub max: u32,
+	pub hash: H256,
+}
+
+impl NamespacedHash {
+	/// Hashes a leaf as `keccak_256(0x00 ++ namespace ++ data)`.
+	pub fn leaf(namespace: u32, data: &[u8]) -> Self {
+		let mut preimage = Vec::with_capacity(1 + 4 + data.len());
+		preimage.push(LEAF_PREFIX);
+		preimage.extend_from_slice(&namespace.to_be_bytes());
+		preimage.extend_from_slice(data);
+
+		Self {
+			min: namespace,
+			max: namespace,
+			hash: H256(keccak_256(&preimage)),
+		}
+	}
+
+	/// Hashes two children as `keccak_256(0x01 ++ left ++ right)`, where each child is packed as
+	/// `min ++ max ++ hash`.
+	pub fn node(left: &Self, right: &Self) -> Self {
+		let mut preimage = [0u8; 1 + 2 * Self::PACKED_LEN];
+		preimage[0] = NODE_PREFIX;
+		preimage[1..=Self::PACKED_LEN].copy_from_slice(&left.packed());
+		preimage[1 + Self::PACKED_LEN..].copy_from_slice(&right.packed());
+
+		Self {
+			min: left.min.min(right.min),
+			max: left.max.max(right.max),
+			hash: H256(keccak_256(&preimage)),
+		}
+	}
+
+	const PACKED_LEN: usize = 4 + 4 + 32;
+
+	fn packed(&self) -> [u8; Self::PACKED_LEN] {
+		let mut packed = [0u8; Self::PACKED_LEN];
+		packed[..4].copy_from_slice(&self.min.to_be_bytes());
+		packed[4..8].copy_from_slice(&self.max.to_be_bytes());
+		packed[8..].copy_from_slice(self.hash.as_bytes());
+		packed
+	}
+}
+
+/// Proof of all the leaves of one namespace, or of its absence.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[derive(Clone, Default, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub struct NamespaceProof {
+	/// Number of leaves of the tree.
+	pub number_of_leaves: u32,
+	/// Index of the first proven leaf.
+	pub start: u32,
+	/// Leaves of the namespace, in block order. It is empty for absence proofs.
+	pub leaves: Vec<Vec<u8>>,
+	/// For absence proofs, the leaf at the position where the namespace would be.
+	pub absence_leaf: Option<NamespacedHash>,
+	/// Roots of the subtrees around the proven leaves, from left to right.
+	pub nodes: Vec<NamespacedHash>,
+}
+
+/// Sorts `leaves` by namespace, keeping the block order inside each namespace.
+fn sorted(mut leaves: Vec<(AppId, Vec<u8>)>) -> Vec<(AppId, Vec<u8>)> {
+	leaves.sort_by_key(|(app_id, _)| app_id.0);
+	leaves
+}
+
+/// Number of leaves of the left subtree of a tree of `n > 1` leaves.
+fn split(n: usize) -> usize {
+	let mut k = 1;
+	while k << 1 < n {
+		k <<= 1;
+	}
+	k
+}
+
+fn subtree_root(leaves: &[NamespacedHash]) -> NamespacedHash {
+	match leaves.len() {
+		0 => NamespacedHash::default(),
+		1 => leaves[0],
+		n => {
+			let k = split(n);
+			NamespacedHash::node(&subtree_root(&leaves[..k]), &subtree_root(&leaves[k..]))
+		},
+	}
+}
+
+/// Returns the root hash of the tree of `leaves` and its number of leaves.
+///
+/// In case an empty list of leaves is passed the function returns a 0-filled hash.
+pub fn root(leaves: Vec<(AppId, Vec<u8>)>) -> (H256, u32) {
+	let hashes = sorted(leaves)
+		.iter()
+		.map(|(app_id, data)| NamespacedHash::leaf(app_id.0, data))
+		.collect::<Vec<_>>();
+	let number_of_leaves = u32::try_from(hashes.len()).unwrap_or(u32::MAX);
+
+	(subtree_root(&hashes).hash, number_of_leaves)
+}
+
+/// Pushes the roots of the subtrees of `leaves` which are outside of `[start, end)`.
+fn collect_nodes(
+	leaves: &[NamespacedHash],
+	offset: usize,
+	start: usize,
+	end: usize,
+	nodes: &mut Vec<NamespacedHash>,
+) {
+	let len = leaves.len();
+	if offset + len <= start || offset >= end {
+		nodes.push(subtree_root(leaves));
+		return;
+	}
+	if len == 1 {
+		return;
+	}
+
+	let k = split(len);
+	collect_nodes(&leaves[..k], offset, start, end, nodes);
+	collect_nodes(&leaves[k..], offset + k, start, end, nodes);
+}
+
+/// Creates the proof of all the leaves of `app_id`, or of its absence if it has no leaves.
+pub fn proof(leaves: Vec<(AppId, Vec<u8>)>, app_id: AppId) -> NamespaceProof {
+	let leaves = sorted(leaves);
+	let hashes = leaves
+		.iter()
+		.map(|(app_id, data)| NamespacedHash::leaf(app_id.0, data))
+		.collect::<Vec<_>>();
+	let number_of_leaves = u32::try_from(hashes.len()).unwrap_or(u32::MAX);
+	if hashes.is_empty() {
+		return NamespaceProof::default();
+	}
+
+	let start = leaves.partition_point(|(id, _)| id.0 < app_id.0);
+	let end = leaves.partition_point(|(id, _)| id.0 <= app_id.0);
+	let (start, end, absence_leaf) = if start < end {
+		(start, end, None)
+	} else {
+		// The first leaf of a greater namespace, or the last leaf if there is none.
+		let index = start.min(hashes.len() - 1);
+		(index, index + 1, Some(hashes[index]))
+	};
+
+	let mut nodes = Vec::new();
+	collect_nodes(&hashes, 0, start, end, &mut nodes);
+	let leaves = match absence_leaf {
+		Some(_) => Vec::new(),
+		None => leaves
+			.into_iter()
+			.skip(start)
+			.take(end - start)
+			.map(|(_, data)| data)
+			.collect(),
+	};
+
+	NamespaceProof {
+		number_of_leaves,
+		start: start as u32,
+		leaves,
+		absence_leaf,
+		nodes,
+	}
+}
+
+/// Rebuilds the root of the subtree of `len` leaves at `offset`, checking that the nodes outside
+/// of `[start, end)` do not contain any leaf of `namespace`.
+#[allow(clippy::too_many_arguments)]
+fn rebuild<R, N>(
+	len: usize,
+	offset: usize,
+	start: usize,
+	end: usize,
+	namespace: u32,
+	range: &mut R,
+	nodes: &mut Peekable<N>,
+) -> Option<NamespacedHash>
+where
+	R: Iterator<Item = NamespacedHash>,
+	N: Iterator<Item = NamespacedHash>,
+{
+	if offset + len <= start {
+		let node = nodes.next()?;
+		return (node.max < namespace).then_some(node);
+	}
+	if offset >= end {
+		let node = nodes.next()?;
+		return (node.min > namespace).then_some(node);
+	}
+	if len == 1 {
+		return range.next();
+	}
+
+	let k = split(len);
+	let left = rebuild(k, offset, start, end, namespace, range, nodes)?;
+	let right = rebuild(len - k, offset + k, start, end, namespace, range, nodes)?;
+	// Leaves are sorted by namespace.
+	(left.max <= right.min).then(|| NamespacedHash::node(&left, &right))
+}
+
+/// Verifies that `proof` contains all the leaves of `app_id` under `root`, or that `app_id` has
+/// no leaves at all.
+pub fn verify(root: H256, app_id: AppId, proof: &NamespaceProof) -> bool {
+	let namespace = app_id.0;
+	let number_of_leaves = proof.number_of_leaves as usize;
+	if number_of_leaves == 0 {
+		return root.is_zero()
+			&& proof.leaves.is_empty()
+			&& proof.absence_leaf.is_none()
+			&& proof.nodes.is_empty();
+	}
+
+	let range = match proof.absence_leaf {
+		Some(leaf) => {
+			if !proof.leaves.is_empty() || leaf.min != leaf.max || leaf.min == namespace {
+				return false;
+			}
+			sp_std::vec![leaf]
+		},
+		None => proof
+			.leaves
+			.iter()
+			.map(|data| NamespacedHash::leaf(namespace, data))
+			.collect(),
+	};
+	let start = proof.start as usize;
+	let end = start.saturating_add(range.len());
+	if range.is_empty() || end > number_of_leaves {
+		return false;
+	}
+
+	let mut range = range.into_iter();
+	let mut nodes = proof.nodes.iter().copied().peekable();
+	let Some(rebuilt) = rebuild(
+		number_of_leaves,
+		0,
+		start,
+		end,
+		namespace,
+		&mut range,
+		&mut nodes,
+	) else {
+		return false;
+	};
+
+	range.next().is_none() && nodes.peek().is_none() && rebuilt.hash == root
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn leaves() -> Vec<(AppId, Vec<u8>)> {
+		[
+			(3, "a"),
+			(1, "b"),
+			(3, "c"),
+			(5, "d"),
+			(1, "e"),
+			(8, "f"),
+			(3, "g"),
+		]
+		.into_iter()
+		.map(|(app_id, data)| (AppId(app_id), data.as_bytes().to_vec()))
+		.collect()
+	}
+
+	#[test]
+	fn namespace_proofs_are_complete() {
+		let (root, number_of_leaves) = root(leaves());
+		assert_eq!(number_of_leaves, 7);
+
+		let proof = proof(leaves(), AppId(3));
+		assert_eq!(
+			proof.leaves,
+			vec![b"a".to_vec(), b"c".to_vec(), b"g".to_vec()]
+		);
+		assert!(verify(root, AppId(3), &proof));
+		// The proof of one namespace is not valid for another one.
+		assert!(!verify(root, AppId(1), &proof));
+
+		// Leaves cannot be hidden.
+		let mut partial = proof.clone();
+		partial.leaves.pop();
+		assert!(!verify(root, AppId(3), &partial));
+
+		for app_id in [1, 5, 8] {
+			assert!(verify(
+				root,
+				AppId(app_id),
+				&super::proof(leaves(), AppId(app_id))
+			));
+		}
+	}
+
+	#[test]
+	fn namespace_absence_proofs() {
+		let (root, _) = root(leaves());
+
+		for app_id in [0, 2, 4, 6, 9] {
+			let proof = proof(leaves(), AppId(app_id));
+			assert!(proof.leaves.is_empty());
+			assert!(verify(root, AppId(app_id), &proof), "app {app_id}");
+		}
+
+		// An absence proof is not valid for a present namespace.
+		let proof = proof(leaves(), AppId(4));
+		assert_eq!(proof.absence_leaf, Some(NamespacedHash::leaf(5, b"d")));
+		assert!(!verify(root, AppId(5), &proof));
+	}
+
+	#[test]
+	fn empty_tree() {
+		let (root, number_of_leaves) = root(Vec::new());
+		assert_eq!((root, number_of_leaves), (H256::zero(), 0));
+
+		let proof = proof(Vec::new(), AppId(1));
+		assert!(verify(root, AppId(1), &proof));
+		assert!(!verify(H256::repeat_byte(1), AppId(1), &proof));
+	}
+
+	#[test]
+	fn proofs_of_every_tree_size() {
+		for len in 1..=17u32 {
+			let leaves = (0..len)
+				.map(|i| (AppId(i / 2 * 2), i.to_le_bytes().to_vec()))
+				.collect::<Vec<_>>();
+			let (root, _) = root(leaves.clone());
+
+			for app_id in 0..=len + 1 {
+				let proof = proof(leaves.clone(), AppId(app_id));
+				assert!(
+					verify(root, AppId(app_id), &proof),
+					"{len} leaves, app {app_id}"
+				);
+			}
+		}
+	}
+}
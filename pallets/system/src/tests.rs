@@ -25,6 +25,7 @@ use sp_runtime::{
 	traits::{BlakeTwo256, Header as _},
 	DispatchError, DispatchErrorWithPostInfo,
 };
+use test_case::test_case;
 
 use crate::*;
 
@@ -601,6 +602,131 @@ fn prunes_block_hash_mappings() {
 	})
 }
 
+#[test]
+fn header_extension_version_switches_at_scheduled_block() {
+	new_test_ext().execute_with(|| {
+		PendingHeaderExtensionVersion::<Test>::put((3, ExtensionVersion::V2));
+		assert_eq!(System::header_extension_version_at(2), ExtensionVersion::V1);
+		assert_eq!(System::header_extension_version_at(3), ExtensionVersion::V2);
+
+		for n in 1..=4 {
+			System::initialize(&n, &[n as u8 - 1; 32].into(), &Default::default());
+			let header = System::finalize();
+
+			let expected = if n < 3 {
+				ExtensionVersion::V1
+			} else {
+				ExtensionVersion::V2
+			};
+			assert_eq!(ExtensionVersion::from(&header.extension), expected);
+		}
+
+		assert_eq!(System::header_extension_version(), ExtensionVersion::V2);
+		assert_eq!(System::pending_header_extension_version(), None);
+	})
+}
+
+#[test]
+fn commitment_schemes_build_distinct_commitments() {
+	use avail_core::{header::HeaderExtension, AppExtrinsic};
+	use header_builder::{
+		build_extension,
+		commitment::{Kate, MerkleRows, Mock},
+		CommitmentScheme,
+	};
+
+	let xts = vec![AppExtrinsic::from(vec![1u8; 64])];
+	fn build<S: CommitmentScheme>(xts: &[AppExtrinsic]) -> (usize, Vec<u8>) {
+		let extension = build_extension::<S>(
+			xts,
+			H256::zero(),
+			limits::BlockLength::default(),
+			1,
+			Seed::default(),
+			ExtensionVersion::V1,
+		);
+		match extension {
+			HeaderExtension::V1(ext) => (ext.commitment.rows as usize, ext.commitment.commitment),
+			_ => unreachable!("V1 extension was requested"),
+		}
+	}
+
+	let (rows, kate) = build::<Kate>(&xts);
+	let (_, merkle_rows) = build::<MerkleRows>(&xts);
+	let (_, mock) = build::<Mock>(&xts);
+
+	assert_eq!(merkle_rows.len(), rows * 32);
+	assert_eq!(mock.len(), kate.len());
+	assert!(mock.iter().all(|b| *b == 0));
+	assert_ne!(kate, mock);
+	assert_ne!(kate, merkle_rows);
+}
+
+#[test]
+fn headers_are_tagged_with_their_commitment_scheme() {
+	use header_builder::COMMITMENT_SCHEME_ENGINE_ID;
+	use sp_runtime::generic::{Digest, DigestItem};
+
+	// `Kate` headers keep their untagged format.
+	assert_eq!(CommitmentSchemeId::Kate.digest_item(), None);
+	new_test_ext().execute_with(|| {
+		System::initialize(&1, &[0u8; 32].into(), &Default::default());
+		let header = System::finalize();
+		assert_eq!(
+			CommitmentSchemeId::from_digest(&header.digest),
+			CommitmentSchemeId::Kate
+		);
+	});
+
+	for scheme in [CommitmentSchemeId::MerkleRows, CommitmentSchemeId::Mock] {
+		let item = scheme.digest_item().unwrap();
+		assert_eq!(
+			item,
+			DigestItem::Consensus(COMMITMENT_SCHEME_ENGINE_ID, scheme.encode())
+		);
+
+		let digest = Digest {
+			logs: vec![DigestItem::Other(vec![1, 2, 3]), item],
+		};
+		assert_eq!(CommitmentSchemeId::from_digest(&digest), scheme);
+	}
+}
+
+#[test_case(PaddingSeedPolicy::Zero ; "zero seed")]
+#[test_case(PaddingSeedPolicy::Randomness ; "randomness seed")]
+fn rebuilt_grid_matches_header_commitment(policy: PaddingSeedPolicy) {
+	use avail_core::header::HeaderExtension;
+	use header_builder::{commitment::Kate, randomness_seed, MIN_GRID_WIDTH};
+	use kate::gridgen::EvaluationGrid;
+
+	PaddingSeed::set(policy);
+	new_test_ext().execute_with(|| {
+		System::initialize(&1, &[0u8; 32].into(), &Default::default());
+		let header = System::finalize();
+		let HeaderExtension::V1(extension) = header.extension else {
+			unreachable!("V1 is the default header extension version");
+		};
+
+		// Same seed and grid as the ones rebuilt by the Kate RPC, where `babe_vrf` returns
+		// `randomness_seed`.
+		let seed = match policy {
+			PaddingSeedPolicy::Zero => Seed::default(),
+			PaddingSeedPolicy::Randomness => randomness_seed::<Test>(),
+		};
+		let block_length = System::block_length();
+		let grid = EvaluationGrid::from_extrinsics(
+			vec![],
+			MIN_GRID_WIDTH,
+			block_length.cols.0 as usize,
+			block_length.rows.0 as usize,
+			seed,
+		)
+		.unwrap();
+
+		assert_eq!(extension.commitment.commitment, Kate::commit(&grid));
+	});
+}
+
 #[test]
 fn set_code_checks_works() {
 	struct ReadRuntimeVersion(Vec<u8>);
@@ -7,8 +7,13 @@ use avail_core::{
 	header::HeaderExtension, traits::ExtendedHeader, AppExtrinsic, AppId, DataProof,
 	OpaqueExtrinsic,
 };
-use da_runtime::{apis::DataAvailApi, Runtime, UncheckedExtrinsic};
-use frame_system::{limits::BlockLength, submitted_data};
+use da_runtime::{apis::DataAvailApi, impls::submitted_data_source, Runtime, UncheckedExtrinsic};
+use frame_system::{
+	header_builder::MIN_GRID_WIDTH,
+	limits::BlockLength,
+	submitted_data::{self, nmt::NamespaceProof, DataRootMode, LeafVersion, ProvenanceLeaf},
+	CommitmentSchemeId, PaddingSeedPolicy,
+};
 use jsonrpsee::{
 	core::{async_trait, Error as JsonRpseeError, RpcResult},
 	proc_macros::rpc,
@@ -24,8 +29,10 @@ use kate_recovery::matrix::Dimensions;
 use moka::future::Cache;
 use rayon::prelude::*;
 use sc_client_api::BlockBackend;
-use sp_api::ProvideRuntimeApi;
+use serde::{Deserialize, Serialize};
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
+use sp_core::H256;
 use sp_runtime::{
 	generic::{Digest, SignedBlock},
 	traits::{Block as BlockT, Header},
@@ -33,6 +40,34 @@ use sp_runtime::{
 
 pub type HashOf<Block> = <Block as BlockT>::Hash;
 
+/// `DataProof` extended with the source of the proven data, when the data root uses
+/// `LeafVersion::V2` leaves.
+///
+/// The source fields are omitted for `LeafVersion::V1` leaves, so it is backward compatible with
+/// `DataProof`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataProofV2 {
+	#[serde(flatten)]
+	pub data_proof: DataProof,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub app_id: Option<AppId>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub sender: Option<H256>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub data_hash: Option<H256>,
+}
+
+/// Proof of the submitted data of one transaction under the data root of its application, and
+/// of that application root under the `apps_root` committed in the header digest.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppDataProof {
+	pub app_id: AppId,
+	pub data_proof: DataProof,
+	pub app_root_proof: DataProof,
+}
+
 #[rpc(client, server)]
 pub trait KateApi<Block>
 where
@@ -63,7 +98,21 @@ where
 		&self,
 		transaction_index: u32,
 		at: Option<HashOf<Block>>,
-	) -> RpcResult<DataProof>;
+	) -> RpcResult<DataProofV2>;
+
+	#[method(name = "kate_queryAppDataProof")]
+	async fn query_app_data_proof(
+		&self,
+		transaction_index: u32,
+		at: Option<HashOf<Block>>,
+	) -> RpcResult<AppDataProof>;
+
+	#[method(name = "kate_queryNamespaceProof")]
+	async fn query_namespace_proof(
+		&self,
+		app_id: AppId,
+		at: Option<HashOf<Block>>,
+	) -> RpcResult<NamespaceProof>;
 }
 
 #[cfg(feature = "metrics")]
@@ -104,7 +153,21 @@ where
 		&self,
 		transaction_index: u32,
 		at: Option<HashOf<Block>>,
-	) -> RpcResult<(DataProof, u128)>;
+	) -> RpcResult<(DataProofV2, u128)>;
+
+	#[method(name = "kate_queryAppDataProofMetrics")]
+	async fn query_app_data_proof_metrics(
+		&self,
+		transaction_index: u32,
+		at: Option<HashOf<Block>>,
+	) -> RpcResult<(AppDataProof, u128)>;
+
+	#[method(name = "kate_queryNamespaceProofMetrics")]
+	async fn query_namespace_proof_metrics(
+		&self,
+		app_id: AppId,
+		at: Option<HashOf<Block>>,
+	) -> RpcResult<(NamespaceProof, u128)>;
 }
 
 #[allow(clippy::type_complexity)]
@@ -207,17 +270,25 @@ where
 		Ok(signed_block)
 	}
 
-	/// If feature `secure_padding_fill` is enabled then the returned seed is generated using Babe VRF.
-	/// Otherwise, it will use the default `Seed` value.
+	/// Returns the padding seed of block `at`, following the `PaddingSeedPolicy` of its runtime.
 	fn get_seed(&self, at: Block::Hash) -> RpcResult<Seed> {
-		if cfg!(feature = "secure_padding_fill") {
-			self.client
-				.runtime_api()
-				.babe_vrf(at)
-				.map_err(|e| internal_err!("Babe VRF not found for block {}: {:?}", at, e))
+		let api = self.client.runtime_api();
+		let api_version = api
+			.api_version::<dyn DataAvailApi<Block>>(at)
+			.map_err(|e| internal_err!("DataAvailApi version cannot be fetched: {:?}", e))?
+			.unwrap_or(1);
+		// Runtimes before `padding_seed_policy` fill the padding using the zero seed.
+		let policy = if api_version >= 4 {
+			api.padding_seed_policy(at)
+				.map_err(|e| internal_err!("Padding seed policy cannot be fetched: {:?}", e))?
 		} else {
-			Ok(Seed::default())
-		}
+			PaddingSeedPolicy::Zero
+		};
+
+		padding_seed(policy, || {
+			api.babe_vrf(at)
+				.map_err(|e| internal_err!("Babe VRF not found for block {}: {:?}", at, e))
+		})
 	}
 
 	/// The signed_block needs to be finalized.
@@ -239,7 +310,6 @@ where
 					.map(AppExtrinsic::from)
 					.collect();
 
-				// Use Babe's VRF
 				let seed = self.get_seed(block_hash)?;
 				let block_length: BlockLength = self
 					.client
@@ -247,18 +317,7 @@ where
 					.block_length(block_hash)
 					.map_err(|e| internal_err!("Block Length cannot be fetched: {:?}", e))?;
 
-				let mut evals = kate::gridgen::EvaluationGrid::from_extrinsics(
-					xts_by_id.clone(),
-					4,
-					block_length.cols.0.try_into().expect("TODO"), // 'cols' is the # of cols, so width
-					block_length.rows.0.try_into().expect("TODO"), // 'rows' is the # of rows, so height
-					seed,
-				)
-				.map_err(|e| internal_err!("Building evals grid failed: {:?}", e))?;
-
-				evals = evals
-					.extend_columns(NonZeroU16::new(2).expect("2>0"))
-					.map_err(|e| internal_err!("Error extending grid {:?}", e))?;
+				let evals = extended_eval_grid(xts_by_id, block_length, seed)?;
 
 				Ok::<_, JsonRpseeError>(Arc::new(evals))
 			})
@@ -374,6 +433,7 @@ where
 		let execution_start = std::time::Instant::now();
 
 		let signed_block = self.get_signed_and_finalized_block(at)?;
+		ensure_kate_commitments(signed_block.block.header())?;
 		let evals = self.get_eval_grid(&signed_block).await?;
 		let polys = self.get_poly_grid(&signed_block).await?;
 
@@ -428,33 +488,94 @@ where
 		&self,
 		transaction_index: u32,
 		at: Option<HashOf<Block>>,
-	) -> RpcResult<DataProof> {
+	) -> RpcResult<DataProofV2> {
+		let execution_start = std::time::Instant::now();
+
+		let block = self.get_signed_block(at)?.block;
+		let data_proof = data_proof(&*self.client, &block, transaction_index)?;
+
+		// Execution Time Metric
+		KateRpcMetrics::observe_query_data_proof_execution_time(execution_start.elapsed());
+
+		Ok(data_proof)
+	}
+
+	async fn query_app_data_proof(
+		&self,
+		transaction_index: u32,
+		at: Option<HashOf<Block>>,
+	) -> RpcResult<AppDataProof> {
 		let execution_start = std::time::Instant::now();
 
 		let block = self.get_signed_block(at)?.block;
+		let leaf_version = leaf_version(&*self.client, block.header().hash())?;
 		let calls = block
 			.extrinsics()
 			.iter()
 			.flat_map(|extrinsic| UncheckedExtrinsic::try_from(extrinsic).ok())
-			.map(|extrinsic| extrinsic.function);
+			.map(|extrinsic| (submitted_data_source(&extrinsic), extrinsic.function));
 
 		// Build the proof.
-		let merkle_proof = submitted_data::calls_proof::<Runtime, _, _>(calls, transaction_index)
-			.ok_or_else(|| {
+		let proof = submitted_data::app_calls_proof::<Runtime, _, _>(
+			calls,
+			transaction_index,
+			leaf_version,
+		)
+		.ok_or_else(|| {
 			internal_err!(
-				"Data proof cannot be generated for transaction index={} at block {:?}",
+				"App data proof cannot be generated for transaction index={} at block {:?}",
 				transaction_index,
 				at
 			)
 		})?;
 
-		let data_proof = DataProof::try_from(&merkle_proof)
-			.map_err(|e| internal_err!("Data proof cannot be loaded from merkle root: {:?}", e));
+		let data_proof = DataProof::try_from(&proof.data_proof)
+			.map_err(|e| internal_err!("Data proof cannot be loaded from app root: {:?}", e))?;
+		let app_root_proof = DataProof::try_from(&proof.app_root_proof).map_err(|e| {
+			internal_err!("App root proof cannot be loaded from apps root: {:?}", e)
+		})?;
 
 		// Execution Time Metric
-		KateRpcMetrics::observe_query_data_proof_execution_time(execution_start.elapsed());
+		KateRpcMetrics::observe_query_app_data_proof_execution_time(execution_start.elapsed());
+
+		Ok(AppDataProof {
+			app_id: proof.app_id,
+			data_proof,
+			app_root_proof,
+		})
+	}
 
-		data_proof
+	async fn query_namespace_proof(
+		&self,
+		app_id: AppId,
+		at: Option<HashOf<Block>>,
+	) -> RpcResult<NamespaceProof> {
+		let execution_start = std::time::Instant::now();
+
+		let block = self.get_signed_block(at)?.block;
+		let block_hash = block.header().hash();
+		if data_root_mode(&*self.client, block_hash)? != DataRootMode::Namespaced {
+			return Err(internal_err!(
+				"Block {:?} does not use a namespaced data root",
+				block_hash
+			));
+		}
+
+		let leaf_version = leaf_version(&*self.client, block_hash)?;
+		let calls = block
+			.extrinsics()
+			.iter()
+			.flat_map(|extrinsic| UncheckedExtrinsic::try_from(extrinsic).ok())
+			.map(|extrinsic| (submitted_data_source(&extrinsic), extrinsic.function));
+
+		// Build the proof.
+		let proof =
+			submitted_data::namespace_calls_proof::<Runtime, _, _>(calls, app_id, leaf_version);
+
+		// Execution Time Metric
+		KateRpcMetrics::observe_query_namespace_proof_execution_time(execution_start.elapsed());
+
+		Ok(proof)
 	}
 }
 
@@ -524,13 +645,185 @@ where
 		&self,
 		transaction_index: u32,
 		at: Option<HashOf<Block>>,
-	) -> RpcResult<(DataProof, u128)> {
+	) -> RpcResult<(DataProofV2, u128)> {
 		let start = std::time::Instant::now();
 		let result = self.query_data_proof(transaction_index, at).await;
 		let elapsed = start.elapsed();
 
 		result.map(|r| (r, elapsed.as_micros()))
 	}
+
+	async fn query_app_data_proof_metrics(
+		&self,
+		transaction_index: u32,
+		at: Option<HashOf<Block>>,
+	) -> RpcResult<(AppDataProof, u128)> {
+		let start = std::time::Instant::now();
+		let result = self.query_app_data_proof(transaction_index, at).await;
+		let elapsed = start.elapsed();
+
+		result.map(|r| (r, elapsed.as_micros()))
+	}
+
+	async fn query_namespace_proof_metrics(
+		&self,
+		app_id: AppId,
+		at: Option<HashOf<Block>>,
+	) -> RpcResult<(NamespaceProof, u128)> {
+		let start = std::time::Instant::now();
+		let result = self.query_namespace_proof(app_id, at).await;
+		let elapsed = start.elapsed();
+
+		result.map(|r| (r, elapsed.as_micros()))
+	}
+}
+
+/// Builds the data proof of the transaction at `transaction_index` of `block`, against the binary
+/// data root of its header.
+pub fn data_proof<Client, Block>(
+	client: &Client,
+	block: &Block,
+	transaction_index: u32,
+) -> RpcResult<DataProofV2>
+where
+	Block: BlockT<Extrinsic = OpaqueExtrinsic>,
+	Client: ProvideRuntimeApi<Block>,
+	Client::Api: DataAvailApi<Block>,
+{
+	let block_hash = block.header().hash();
+
+	// Skip blocks without submitted data, using the leaves recorded by the runtime.
+	let api = client.runtime_api();
+	let api_version = api
+		.api_version::<dyn DataAvailApi<Block>>(block_hash)
+		.map_err(|e| internal_err!("DataAvailApi version cannot be fetched: {:?}", e))?
+		.unwrap_or(1);
+	if api_version >= 5 {
+		let leaves = api
+			.data_root_leaves(block_hash)
+			.map_err(|e| internal_err!("Data root leaves cannot be fetched: {:?}", e))?;
+		if leaves == 0 {
+			return Err(internal_err!(
+				"Data proof cannot be generated, block {:?} has no submitted data",
+				block_hash
+			));
+		}
+	}
+
+	if data_root_mode(client, block_hash)? != DataRootMode::Binary {
+		return Err(internal_err!(
+			"Block {:?} uses a namespaced data root, use `kate_queryNamespaceProof` instead",
+			block_hash
+		));
+	}
+
+	let leaf_version = leaf_version(client, block_hash)?;
+	let calls = block
+		.extrinsics()
+		.iter()
+		.flat_map(|extrinsic| UncheckedExtrinsic::try_from(extrinsic).ok())
+		.map(|extrinsic| (submitted_data_source(&extrinsic), extrinsic.function));
+
+	// Build the proof.
+	let merkle_proof =
+		submitted_data::calls_proof::<Runtime, _, _>(calls, transaction_index, leaf_version)
+			.ok_or_else(|| {
+				internal_err!(
+					"Data proof cannot be generated for transaction index={} at block {:?}",
+					transaction_index,
+					block_hash
+				)
+			})?;
+
+	let data_proof = DataProof::try_from(&merkle_proof)
+		.map_err(|e| internal_err!("Data proof cannot be loaded from merkle root: {:?}", e))?;
+	let source = match leaf_version {
+		LeafVersion::V1 => None,
+		LeafVersion::V2 => Some(
+			ProvenanceLeaf::from_bytes(&merkle_proof.leaf)
+				.ok_or_else(|| internal_err!("Invalid data root leaf"))?,
+		),
+	};
+
+	Ok(DataProofV2 {
+		data_proof,
+		app_id: source.map(|leaf| leaf.app_id),
+		sender: source.map(|leaf| leaf.sender),
+		data_hash: source.map(|leaf| leaf.data_hash),
+	})
+}
+
+fn leaf_version<Client, Block>(client: &Client, at: Block::Hash) -> RpcResult<LeafVersion>
+where
+	Block: BlockT,
+	Client: ProvideRuntimeApi<Block>,
+	Client::Api: DataAvailApi<Block>,
+{
+	let api = client.runtime_api();
+	let api_version = api
+		.api_version::<dyn DataAvailApi<Block>>(at)
+		.map_err(|e| internal_err!("DataAvailApi version cannot be fetched: {:?}", e))?
+		.unwrap_or(1);
+	// Runtimes before `data_root_leaf_version` only use the submitted data as leaf.
+	if api_version < 6 {
+		return Ok(LeafVersion::V1);
+	}
+
+	api.data_root_leaf_version(at)
+		.map_err(|e| internal_err!("Data root leaf version cannot be fetched: {:?}", e))
+}
+
+fn data_root_mode<Client, Block>(client: &Client, at: Block::Hash) -> RpcResult<DataRootMode>
+where
+	Block: BlockT,
+	Client: ProvideRuntimeApi<Block>,
+	Client::Api: DataAvailApi<Block>,
+{
+	let api = client.runtime_api();
+	let api_version = api
+		.api_version::<dyn DataAvailApi<Block>>(at)
+		.map_err(|e| internal_err!("DataAvailApi version cannot be fetched: {:?}", e))?
+		.unwrap_or(1);
+	// Runtimes before `data_root_mode` only build binary data roots.
+	if api_version < 7 {
+		return Ok(DataRootMode::Binary);
+	}
+
+	api.data_root_mode(at)
+		.map_err(|e| internal_err!("Data root mode cannot be fetched: {:?}", e))
+}
+
+/// Returns the padding seed selected by `policy`, where `babe_vrf` fetches the seed derived from
+/// the block randomness.
+fn padding_seed(
+	policy: PaddingSeedPolicy,
+	babe_vrf: impl FnOnce() -> RpcResult<Seed>,
+) -> RpcResult<Seed> {
+	match policy {
+		PaddingSeedPolicy::Zero => Ok(Seed::default()),
+		PaddingSeedPolicy::Randomness => babe_vrf(),
+	}
+}
+
+/// Rebuilds the data grid of a block from its `app_extrinsics`, as done by the header builder,
+/// and extends its columns.
+fn extended_eval_grid(
+	app_extrinsics: Vec<AppExtrinsic>,
+	block_length: BlockLength,
+	seed: Seed,
+) -> RpcResult<EvaluationGrid> {
+	let evals = EvaluationGrid::from_extrinsics(
+		app_extrinsics,
+		MIN_GRID_WIDTH,
+		block_length.cols.0.try_into().expect("TODO"), // 'cols' is the # of cols, so width
+		block_length.rows.0.try_into().expect("TODO"), // 'rows' is the # of rows, so height
+		seed,
+	)
+	.map_err(|e| internal_err!("Building evals grid failed: {:?}", e))?;
+
+	evals
+		.extend_columns(NonZeroU16::new(2).expect("2>0"))
+		.map_err(|e| internal_err!("Error extending grid {:?}", e))
 }
 
 fn non_extended_dimensions(ext_dims: Dimensions) -> RpcResult<Dimensions> {
@@ -550,3 +843,64 @@ fn non_extended_dimensions(ext_dims: Dimensions) -> RpcResult<Dimensions> {
 
 	Ok(dimensions)
 }
+
+/// Cell proofs can only be generated for blocks committed using the `Kate` scheme, as tagged in
+/// their own header digest.
+fn ensure_kate_commitments<H: Header>(header: &H) -> RpcResult<()> {
+	let scheme = CommitmentSchemeId::from_digest(header.digest());
+	if scheme != CommitmentSchemeId::Kate {
+		return Err(internal_err!(
+			"Cell proofs are not available for {:?} commitments",
+			scheme
+		));
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use avail_core::header::HeaderExtension;
+	use da_runtime::System;
+	use frame_system::header_builder::randomness_seed;
+	use sp_core::Get;
+	use sp_runtime::BuildStorage;
+
+	use super::*;
+
+	type PaddingSeed = <Runtime as frame_system::Config>::PaddingSeed;
+
+	/// The commitments of the rows of the grid rebuilt by the RPC must be the ones of the header,
+	/// so cell proofs can be verified by light clients.
+	#[test]
+	fn rebuilt_grid_matches_header_commitment() {
+		let storage = frame_system::GenesisConfig::<Runtime>::default()
+			.build_storage()
+			.unwrap();
+		sp_io::TestExternalities::from(storage).execute_with(|| {
+			System::initialize(&1, &Default::default(), &Default::default());
+			let header = System::finalize();
+			let commitment = match header.extension {
+				HeaderExtension::V1(ext) => ext.commitment.commitment,
+				HeaderExtension::V2(ext) => ext.commitment.commitment,
+			};
+
+			// Same inputs as the ones fetched by `get_seed` and `get_eval_grid` from the runtime
+			// API of the block.
+			let seed =
+				padding_seed(PaddingSeed::get(), || Ok(randomness_seed::<Runtime>())).unwrap();
+			let evals = extended_eval_grid(vec![], System::block_length(), seed).unwrap();
+
+			let srs = kate::couscous::multiproof_params();
+			let rebuilt: Vec<u8> = evals
+				.make_polynomial_grid()
+				.unwrap()
+				.commitments(&srs)
+				.unwrap()
+				.iter()
+				.flat_map(|c| c.to_bytes().unwrap())
+				.collect();
+			assert_eq!(rebuilt, commitment);
+		});
+	}
+}
@@ -0,0 +1,344 @@
+#![deny(unused_crate_dependencies)]
+use std::{marker::PhantomData, sync::Arc};
+
+use avail_base::metrics::avail::NomadRpcMetrics;
+use avail_core::{header::HeaderExtension, traits::ExtendedHeader, OpaqueExtrinsic};
+use da_runtime::apis::{
+	DABridgeApi as DABridgeRuntimeApi, DataAvailApi, NomadHomeApi as NomadHomeRuntimeApi,
+};
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+};
+use kate_rpc::{data_proof, DataProofV2};
+use nomad_da_bridge::range::range_proof;
+use sc_client_api::BlockBackend;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{Bytes, H256};
+use sp_runtime::{
+	generic::Digest,
+	traits::{Block as BlockT, Header, UniqueSaturatedInto},
+};
+
+pub type HashOf<Block> = <Block as BlockT>::Hash;
+
+/// State of the Nomad home, as needed by relayers and updaters.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HomeState {
+	/// Latest root of the home tree signed by the updater.
+	pub committed_root: H256,
+	/// Current root of the home tree.
+	pub root: H256,
+	/// Number of messages of the home tree.
+	pub count: u32,
+}
+
+/// Nomad Merkle proof of a message of the home tree, as expected by the replica contracts.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HomeMessageProof {
+	/// Hash of the proven message.
+	pub leaf: H256,
+	/// Leaf index of the message in the home tree.
+	pub leaf_index: u32,
+	/// Committed home root the message is proven against.
+	pub root: H256,
+	/// Sibling of each level of the tree, from the leaves to the root.
+	pub path: Vec<H256>,
+}
+
+/// Data proof of a transaction and Nomad proof of the message which dispatched the data root of
+/// its block, so a destination contract can verify the data end-to-end.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BridgedDataProof {
+	pub data_proof: DataProofV2,
+	/// Formatted Nomad message, as emitted by the `Dispatch` event of the home.
+	pub message: Bytes,
+	/// Leaf index of the message in the home tree.
+	pub leaf_index: u32,
+	/// Committed home root the message is proven against.
+	pub home_root: H256,
+	/// Nomad Merkle proof of the message against `home_root`.
+	pub message_proof: Vec<H256>,
+	/// Proof of the data root against the range root, if the message is a
+	/// `DataRootRangeMessage`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub range_proof: Option<DataRootRangeProof>,
+}
+
+/// Keccak-256 Binary Merkle proof of the data root of a block against the range root of a
+/// `DataRootRangeMessage`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataRootRangeProof {
+	pub data_root: H256,
+	/// Block number minus the start block of the range.
+	pub leaf_index: u32,
+	pub number_of_leaves: u32,
+	pub proof: Vec<H256>,
+}
+
+#[rpc(client, server)]
+pub trait NomadHomeApi<Block>
+where
+	Block: BlockT,
+{
+	#[method(name = "nomad_homeState")]
+	fn home_state(&self, at: Option<HashOf<Block>>) -> RpcResult<HomeState>;
+
+	#[method(name = "nomad_homeNonce")]
+	fn home_nonce(&self, destination_domain: u32, at: Option<HashOf<Block>>) -> RpcResult<u32>;
+
+	#[method(name = "nomad_homeMessageProof")]
+	fn home_message_proof(
+		&self,
+		leaf_index: u32,
+		at: Option<HashOf<Block>>,
+	) -> RpcResult<HomeMessageProof>;
+}
+
+#[rpc(client, server)]
+pub trait NomadDABridgeApi<Block>
+where
+	Block: BlockT,
+{
+	#[method(name = "nomad_queryBridgedDataProof")]
+	fn query_bridged_data_proof(
+		&self,
+		transaction_index: u32,
+		destination_domain: u32,
+		at: Option<HashOf<Block>>,
+	) -> RpcResult<BridgedDataProof>;
+}
+
+#[cfg(feature = "metrics")]
+#[rpc(client, server)]
+pub trait NomadDABridgeApiMetrics<Block>
+where
+	Block: BlockT,
+{
+	#[method(name = "nomad_queryBridgedDataProofMetrics")]
+	fn query_bridged_data_proof_metrics(
+		&self,
+		transaction_index: u32,
+		destination_domain: u32,
+		at: Option<HashOf<Block>>,
+	) -> RpcResult<(BridgedDataProof, u128)>;
+}
+
+pub struct NomadHome<Client, Block> {
+	client: Arc<Client>,
+	_block: PhantomData<Block>,
+}
+
+impl<Client, Block> NomadHome<Client, Block> {
+	pub fn new(client: Arc<Client>) -> Self {
+		Self {
+			client,
+			_block: PhantomData,
+		}
+	}
+}
+
+macro_rules! internal_err {
+	($($arg:tt)*) => {{
+		JsonRpseeError::Custom(format!($($arg)*))
+	}}
+}
+
+impl<Client, Block> NomadHome<Client, Block>
+where
+	Block: BlockT,
+	Client: HeaderBackend<Block>,
+{
+	fn at_or_best(&self, at: Option<<Block as BlockT>::Hash>) -> <Block as BlockT>::Hash {
+		at.unwrap_or_else(|| self.client.info().best_hash)
+	}
+}
+
+impl<Client, Block> NomadHomeApiServer<Block> for NomadHome<Client, Block>
+where
+	Block: BlockT,
+	Client: Send + Sync + 'static,
+	Client: HeaderBackend<Block> + ProvideRuntimeApi<Block>,
+	Client::Api: NomadHomeRuntimeApi<Block>,
+{
+	fn home_state(&self, at: Option<HashOf<Block>>) -> RpcResult<HomeState> {
+		let at = self.at_or_best(at);
+		let api = self.client.runtime_api();
+		let runtime_err = |e| internal_err!("Home state cannot be fetched: {:?}", e);
+
+		Ok(HomeState {
+			committed_root: api.committed_root(at).map_err(runtime_err)?,
+			root: api.tree_root(at).map_err(runtime_err)?,
+			count: api.tree_count(at).map_err(runtime_err)?,
+		})
+	}
+
+	fn home_nonce(&self, destination_domain: u32, at: Option<HashOf<Block>>) -> RpcResult<u32> {
+		let at = self.at_or_best(at);
+		self.client
+			.runtime_api()
+			.nonce(at, destination_domain)
+			.map_err(|e| internal_err!("Home nonce cannot be fetched: {:?}", e))
+	}
+
+	fn home_message_proof(
+		&self,
+		leaf_index: u32,
+		at: Option<HashOf<Block>>,
+	) -> RpcResult<HomeMessageProof> {
+		let at = self.at_or_best(at);
+		let proof = self
+			.client
+			.runtime_api()
+			.message_proof(at, leaf_index)
+			.map_err(|e| internal_err!("Home message proof cannot be fetched: {:?}", e))?
+			.ok_or_else(|| internal_err!("Message {} cannot be proven", leaf_index))?;
+
+		Ok(HomeMessageProof {
+			leaf: proof.leaf,
+			leaf_index: proof.index,
+			root: proof.root,
+			path: proof.path,
+		})
+	}
+}
+
+pub struct NomadDABridge<Client, Block> {
+	client: Arc<Client>,
+	_block: PhantomData<Block>,
+}
+
+impl<Client, Block> NomadDABridge<Client, Block> {
+	pub fn new(client: Arc<Client>) -> Self {
+		Self {
+			client,
+			_block: PhantomData,
+		}
+	}
+}
+
+impl<Client, Block> NomadDABridgeApiServer<Block> for NomadDABridge<Client, Block>
+where
+	Block: BlockT<Extrinsic = OpaqueExtrinsic>,
+	<Block as BlockT>::Header: ExtendedHeader<
+		<<Block as BlockT>::Header as Header>::Number,
+		<Block as BlockT>::Hash,
+		Digest,
+		HeaderExtension,
+	>,
+	Client: Send + Sync + 'static,
+	Client: HeaderBackend<Block> + ProvideRuntimeApi<Block> + BlockBackend<Block>,
+	Client::Api: DataAvailApi<Block> + DABridgeRuntimeApi<Block>,
+{
+	fn query_bridged_data_proof(
+		&self,
+		transaction_index: u32,
+		destination_domain: u32,
+		at: Option<HashOf<Block>>,
+	) -> RpcResult<BridgedDataProof> {
+		let execution_start = std::time::Instant::now();
+
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let block = self
+			.client
+			.block(at)
+			.map_err(|e| internal_err!("Invalid block {:?}: {:?}", at, e))?
+			.ok_or_else(|| internal_err!("Missing block {:?}", at))?
+			.block;
+		let data_proof = data_proof(&*self.client, &block, transaction_index)?;
+		let block_number: u32 = (*block.header().number()).unique_saturated_into();
+
+		// Data roots are dispatched after their blocks, so the latest finalized home state is used.
+		let finalized = self.client.info().finalized_hash;
+		let message_proof = self
+			.client
+			.runtime_api()
+			.data_root_message_proof(finalized, block_number, destination_domain)
+			.map_err(|e| internal_err!("Data root message proof cannot be fetched: {:?}", e))?
+			.ok_or_else(|| {
+				internal_err!(
+					"Data root of block {} was not dispatched to domain {}",
+					block_number,
+					destination_domain
+				)
+			})?;
+
+		let dispatch = &message_proof.dispatch;
+		let range_proof = if dispatch.is_range {
+			let data_root_of = |number: u32| -> RpcResult<H256> {
+				let header = self
+					.client
+					.hash(number.into())
+					.and_then(|hash| hash.map_or(Ok(None), |hash| self.client.header(hash)))
+					.map_err(|e| internal_err!("Invalid block {}: {:?}", number, e))?
+					.ok_or_else(|| internal_err!("Missing block {}", number))?;
+				Ok(header.extension().data_root())
+			};
+			let data_roots = (dispatch.start_block..=dispatch.end_block)
+				.map(data_root_of)
+				.collect::<RpcResult<Vec<_>>>()?;
+			let proof = range_proof(
+				&data_roots,
+				block_number.saturating_sub(dispatch.start_block),
+			)
+			.filter(|proof| proof.root == dispatch.root)
+			.ok_or_else(|| {
+				internal_err!("Data root of block {} is not in its range", block_number)
+			})?;
+			Some(DataRootRangeProof {
+				data_root: proof.leaf,
+				leaf_index: proof.leaf_index as u32,
+				number_of_leaves: proof.number_of_leaves as u32,
+				proof: proof.proof,
+			})
+		} else {
+			None
+		};
+
+		// Execution Time Metric
+		NomadRpcMetrics::observe_query_bridged_data_proof_execution_time(execution_start.elapsed());
+
+		Ok(BridgedDataProof {
+			data_proof,
+			message: message_proof.message.into(),
+			leaf_index: message_proof.proof.index,
+			home_root: message_proof.proof.root,
+			message_proof: message_proof.proof.path,
+			range_proof,
+		})
+	}
+}
+
+#[cfg(feature = "metrics")]
+impl<Client, Block> NomadDABridgeApiMetricsServer<Block> for NomadDABridge<Client, Block>
+where
+	Block: BlockT<Extrinsic = OpaqueExtrinsic>,
+	<Block as BlockT>::Header: ExtendedHeader<
+		<<Block as BlockT>::Header as Header>::Number,
+		<Block as BlockT>::Hash,
+		Digest,
+		HeaderExtension,
+	>,
+	Client: Send + Sync + 'static,
+	Client: HeaderBackend<Block> + ProvideRuntimeApi<Block> + BlockBackend<Block>,
+	Client::Api: DataAvailApi<Block> + DABridgeRuntimeApi<Block>,
+{
+	fn query_bridged_data_proof_metrics(
+		&self,
+		transaction_index: u32,
+		destination_domain: u32,
+		at: Option<HashOf<Block>>,
+	) -> RpcResult<(BridgedDataProof, u128)> {
+		let start = std::time::Instant::now();
+		let result = self.query_bridged_data_proof(transaction_index, destination_domain, at);
+		let elapsed = start.elapsed();
+
+		result.map(|r| (r, elapsed.as_micros()))
+	}
+}
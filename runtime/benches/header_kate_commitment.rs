@@ -2,7 +2,10 @@ use avail_core::{AppExtrinsic, BlockLengthColumns, BLOCK_CHUNK_SIZE, NORMAL_DISP
 use da_control::Config as DAConfig;
 use da_runtime::Runtime;
 use frame_support::traits::Get as _;
-use frame_system::{header_builder::hosted_header_builder, limits::BlockLength};
+use frame_system::{
+	header_builder::{hosted_header_builder, HeaderExtensionVersion},
+	limits::BlockLength,
+};
 use sp_core::H256;
 use sp_std::iter::repeat;
 
@@ -36,5 +39,12 @@ fn commitment_builder_with(txs: Vec<AppExtrinsic>, block_length: BlockLength) {
 	let root = H256::zero();
 	let block_number: u32 = 0;
 
-	let _ = hosted_header_builder::build(txs, root, block_length, block_number, seed);
+	let _ = hosted_header_builder::build(
+		txs,
+		root,
+		block_length,
+		block_number,
+		seed,
+		HeaderExtensionVersion::V1,
+	);
 }
@@ -1,10 +1,13 @@
 use crate::version::VERSION;
 use avail_core::{currency::Balance, header::HeaderExtension, OpaqueExtrinsic};
-use frame_support::{
-	traits::{KeyOwnerProofSystem, Randomness},
-	weights::Weight,
+use frame_support::{traits::KeyOwnerProofSystem, weights::Weight};
+use frame_system::{
+	limits::BlockLength,
+	submitted_data::{DataRootMode, LeafVersion, Metrics as DataRootMetrics},
+	CommitmentScheme, CommitmentSchemeId, ExtensionVersion, PaddingSeedPolicy,
 };
-use frame_system::limits::BlockLength;
+use nomad_da_bridge::DataRootMessageProof;
+use nomad_home::MessageProof;
 use pallet_transaction_payment::{FeeDetails, RuntimeDispatchInfo};
 use sp_api::{decl_runtime_apis, impl_runtime_apis};
 use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
@@ -24,16 +27,40 @@ use sp_version::RuntimeVersion;
 use crate::Identity;
 use crate::{
 	constants, mmr, AccountId, AuthorityDiscovery, Babe, Block, BlockNumber, EpochDuration,
-	Executive, Grandpa, Historical, Index, InherentDataExt, Mmr, NominationPools, OpaqueMetadata,
-	Runtime, RuntimeCall, Seed, SessionKeys, System, TransactionPayment,
+	Executive, Grandpa, Historical, Index, InherentDataExt, Mmr, NomadDABridge, NomadHome,
+	NominationPools, OpaqueMetadata, Runtime, RuntimeCall, Seed, SessionKeys, System,
+	TransactionPayment,
 };
 
 decl_runtime_apis! {
+	#[api_version(8)]
 	pub trait DataAvailApi {
 		fn block_length() -> BlockLength;
 		fn babe_vrf() -> Seed;
+		/// Header extension version used to build the header of block `block_number`.
+		#[api_version(2)]
+		fn header_extension_version(block_number: u32) -> ExtensionVersion;
+		/// Commitment scheme used to build the header extension.
+		#[api_version(3)]
+		fn commitment_scheme() -> CommitmentSchemeId;
+		/// Source of the seed used to fill the padding cells of the data grid.
+		#[api_version(4)]
+		fn padding_seed_policy() -> PaddingSeedPolicy;
+		/// Number of submitted data leaves of the data root of the block.
+		#[api_version(5)]
+		fn data_root_leaves() -> u32;
+		/// Format of the leaves of the data root.
+		#[api_version(6)]
+		fn data_root_leaf_version() -> LeafVersion;
+		/// Structure of the data root tree.
+		#[api_version(7)]
+		fn data_root_mode() -> DataRootMode;
+		/// Metrics of the data root of the block.
+		#[api_version(8)]
+		fn data_root_metrics() -> DataRootMetrics;
 	}
 
+	#[api_version(2)]
 	pub trait ExtensionBuilder {
 		fn build_extension(
 			extrinsics: Vec<OpaqueExtrinsic>,
@@ -43,6 +70,42 @@ decl_runtime_apis! {
 		) -> HeaderExtension;
 
 		fn build_data_root( extrinsics: Vec<OpaqueExtrinsic>) -> H256;
+
+		/// Same as `build_extension` but using the given header extension `version`.
+		#[api_version(2)]
+		fn build_versioned_extension(
+			extrinsics: Vec<OpaqueExtrinsic>,
+			data_root: H256,
+			block_length: BlockLength,
+			block_number: u32,
+			version: ExtensionVersion,
+		) -> HeaderExtension;
+	}
+
+	#[api_version(2)]
+	pub trait DABridgeApi {
+		/// Nomad message which dispatched the data root of `block_number` to
+		/// `destination_domain`, and its proof against the committed home root.
+		fn data_root_message_proof(
+			block_number: u32,
+			destination_domain: u32,
+		) -> Option<DataRootMessageProof>;
+		/// Last block whose data root was dispatched to `destination_domain`.
+		#[api_version(2)]
+		fn last_dispatched_block(destination_domain: u32) -> Option<u32>;
+	}
+
+	pub trait NomadHomeApi {
+		/// Latest root of the home tree signed by the updater.
+		fn committed_root() -> H256;
+		/// Current root of the home tree.
+		fn tree_root() -> H256;
+		/// Number of messages of the home tree.
+		fn tree_count() -> u32;
+		/// Nonce of the next message dispatched to `destination_domain`.
+		fn nonce(destination_domain: u32) -> u32;
+		/// Proof of the message at `leaf_index` against the committed home root.
+		fn message_proof(leaf_index: u32) -> Option<MessageProof>;
 	}
 }
 
@@ -52,6 +115,35 @@ pub(crate) const fn runtime_api_versions() -> Cow<'static, [([u8; 8], u32)]> {
 
 pub static NATIVE_VERSION: &RuntimeVersion = &VERSION;
 
+fn build_header_extension(
+	extrinsics: Vec<OpaqueExtrinsic>,
+	data_root: H256,
+	block_length: BlockLength,
+	block_number: u32,
+	version: ExtensionVersion,
+) -> HeaderExtension {
+	use frame_system::HeaderExtensionBuilder as _;
+
+	type UncheckedExtrinsic = <Runtime as frame_system::Config>::UncheckedExtrinsic;
+
+	let app_extrinsics = extrinsics
+		.into_iter()
+		.filter_map(|opaque| {
+			let unchecked = UncheckedExtrinsic::try_from(&opaque).ok()?;
+			let app_ext = unchecked.into();
+			Some(app_ext)
+		})
+		.collect::<Vec<_>>();
+
+	frame_system::header_builder::da::HeaderExtensionBuilder::<Runtime>::build(
+		app_extrinsics,
+		data_root,
+		block_length,
+		block_number,
+		version,
+	)
+}
+
 impl_runtime_apis! {
 	impl sp_api::Core<Block> for Runtime {
 		fn version() -> RuntimeVersion {
@@ -326,14 +418,38 @@ impl_runtime_apis! {
 		}
 
 		fn babe_vrf() -> Seed {
-			use frame_system::Config;
-			use sp_runtime::traits::Hash;
+			frame_system::header_builder::randomness_seed::<Runtime>()
+		}
+
+		fn header_extension_version(block_number: u32) -> ExtensionVersion {
+			System::header_extension_version_at(block_number)
+		}
+
+		fn commitment_scheme() -> CommitmentSchemeId {
+			<<Runtime as frame_system::Config>::CommitmentScheme as CommitmentScheme>::ID
+		}
+
+		fn padding_seed_policy() -> PaddingSeedPolicy {
+			use frame_support::traits::Get;
+			<Runtime as frame_system::Config>::PaddingSeed::get()
+		}
+
+		fn data_root_leaves() -> u32 {
+			System::data_root_leaves()
+		}
 
-			let epoc_and_block = <Runtime as Config>::Randomness::random_seed();
-			let seed = <Runtime as Config>::Hashing::hash_of(&epoc_and_block);
-			log::info!("Runtime::get_babe_vrf(): epoc_and_block: {epoc_and_block:?}, seed: {seed:?}");
+		fn data_root_leaf_version() -> LeafVersion {
+			use frame_support::traits::Get;
+			<Runtime as frame_system::Config>::DataRootLeafVersion::get()
+		}
+
+		fn data_root_mode() -> DataRootMode {
+			use frame_support::traits::Get;
+			<Runtime as frame_system::Config>::DataRootMode::get()
+		}
 
-			seed.into()
+		fn data_root_metrics() -> DataRootMetrics {
+			System::data_root_metrics()
 		}
 	}
 
@@ -341,7 +457,12 @@ impl_runtime_apis! {
 	impl crate::apis::ExtensionBuilder<Block> for Runtime {
 		fn build_data_root( extrinsics: Vec<OpaqueExtrinsic>) -> H256  {
 			type Extractor = <Runtime as frame_system::Config>::SubmittedDataExtractor;
-			frame_system::submitted_data::extrinsics_root::<Extractor, _>(extrinsics.iter())
+			use frame_support::traits::Get;
+			let version = <Runtime as frame_system::Config>::DataRootLeafVersion::get();
+			let mode = <Runtime as frame_system::Config>::DataRootMode::get();
+			let (data_root, _) =
+				frame_system::submitted_data::data_root::<Extractor, _>(extrinsics.iter(), version, mode);
+			data_root
 		}
 
 		fn build_extension(
@@ -350,24 +471,53 @@ impl_runtime_apis! {
 			block_length: BlockLength,
 			block_number: u32,
 		) -> HeaderExtension {
-			use frame_system::HeaderExtensionBuilder as _;
-
-			type UncheckedExtrinsic = <Runtime as frame_system::Config>::UncheckedExtrinsic;
-
-			let app_extrinsics = extrinsics
-				.into_iter()
-				.filter_map(|opaque| {
-					let unchecked = UncheckedExtrinsic::try_from(&opaque).ok()?;
-					let app_ext = unchecked.into();
-					Some(app_ext)
-				})
-				.collect::<Vec<_>>();
-
-			frame_system::header_builder::da::HeaderExtensionBuilder::<Runtime>::build(
-			app_extrinsics,
-			data_root,
-			block_length,
-			block_number)
+			let version = System::header_extension_version_at(block_number);
+			build_header_extension(extrinsics, data_root, block_length, block_number, version)
+		}
+
+		fn build_versioned_extension(
+			extrinsics: Vec<OpaqueExtrinsic>,
+			data_root: H256,
+			block_length: BlockLength,
+			block_number: u32,
+			version: ExtensionVersion,
+		) -> HeaderExtension {
+			build_header_extension(extrinsics, data_root, block_length, block_number, version)
+		}
+	}
+
+	impl crate::apis::DABridgeApi<Block> for Runtime {
+		fn data_root_message_proof(
+			block_number: u32,
+			destination_domain: u32,
+		) -> Option<DataRootMessageProof> {
+			NomadDABridge::data_root_message_proof(block_number, destination_domain)
+		}
+
+		fn last_dispatched_block(destination_domain: u32) -> Option<u32> {
+			NomadDABridge::last_dispatched_block(destination_domain)
+		}
+	}
+
+	impl crate::apis::NomadHomeApi<Block> for Runtime {
+		fn committed_root() -> H256 {
+			NomadHome::base().committed_root
+		}
+
+		fn tree_root() -> H256 {
+			NomadHome::tree_root()
+		}
+
+		fn tree_count() -> u32 {
+			NomadHome::tree_count()
+		}
+
+		fn nonce(destination_domain: u32) -> u32 {
+			NomadHome::nonces(destination_domain)
+		}
+
+		fn message_proof(leaf_index: u32) -> Option<MessageProof> {
+			NomadHome::message_proof(leaf_index)
 		}
 	}
 
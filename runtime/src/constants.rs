@@ -376,14 +376,36 @@ pub mod da {
 }
 
 pub mod nomad {
+	use frame_support::weights::constants::WEIGHT_REF_TIME_PER_SECOND;
 	use sp_core::H256;
 
 	use super::*;
 
 	parameter_types! {
 		pub const DABridgePalletId: H256 = H256::repeat_byte(1);
+		/// Recipient of the governance messages of the governor domain.
+		pub const GovernanceRouterAddress: H256 = H256::repeat_byte(2);
+		pub const UpdaterBond: Balance = 10_000 * AVL;
+		pub const SlashRewardShare: Perbill = Perbill::from_percent(10);
+		pub const UnbondingDelay: BlockNumber = 7 * time::DAYS;
+		/// Remote roots are accepted after 30 minutes, like on the Nomad replica contracts.
+		pub const OptimisticPeriod: BlockNumber = 30 * time::MINUTES;
+		pub const MaxHandlerWeight: Weight =
+			Weight::from_parts(WEIGHT_REF_TIME_PER_SECOND / 10, 64 * 1024);
 	}
 	pub type MaxMessageBodyBytes = ConstU32<2048>;
+	/// Body of `MaxMessageBodyBytes` and header of 76 bytes.
+	pub type MaxMessageBytes = ConstU32<2124>;
+	pub type MaxDataRootRange = ConstU32<128>;
+	/// Around 20 minutes with 20 seconds blocks.
+	pub type AutoDispatchPeriod = ConstU32<60>;
+	pub type MaxAutoDispatchDestinations = ConstU32<8>;
+	/// Around 3 hours with 20 seconds blocks.
+	pub type MmrRootHistory = ConstU32<600>;
+	/// Around two months of automatic dispatches every `AutoDispatchPeriod`.
+	pub type MaxDataRootDispatchRecords = ConstU32<4_320>;
+	/// Dispatched blocks remembered per recipient, if the blocks are dispatched out of order.
+	pub type MaxDispatchedRanges = ConstU32<64>;
 }
 
 /// Macro to set a value (e.g. when using the `parameter_types` macro) to either a production value
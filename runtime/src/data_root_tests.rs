@@ -1,15 +1,26 @@
 use avail_core::asdr::AppUncheckedExtrinsic;
-use avail_core::OpaqueExtrinsic;
+use avail_core::{InvalidTransactionCustomId, OpaqueExtrinsic};
 use codec::Decode;
 use da_control::{Call as DaCall, CheckAppId};
+use frame_support::weights::Weight;
 use frame_system::{
-	submitted_data::extrinsics_root, CheckEra, CheckGenesis, CheckNonZeroSender, CheckNonce,
-	CheckSpecVersion, CheckTxVersion, CheckWeight,
+	submitted_data::{
+		extrinsics_root, DataSource, Filter as _, LeafVersion, Metrics, ProvenanceLeaf,
+		SubmittedData, MAX_WRAPPER_DEPTH,
+	},
+	CheckEra, CheckGenesis, CheckNonZeroSender, CheckNonce, CheckSpecVersion, CheckTxVersion,
+	CheckWeight,
 };
 use hex_literal::hex;
 use pallet_transaction_payment::ChargeTransactionPayment;
 use sp_core::{sr25519::Signature, H256};
-use sp_runtime::{generic::Era, AccountId32, MultiAddress};
+use sp_runtime::{
+	generic::Era,
+	transaction_validity::{
+		InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+	},
+	AccountId32, BuildStorage, MultiAddress,
+};
 use test_case::test_case;
 
 use super::*;
@@ -67,5 +78,255 @@ fn decode_submit_call() {
 #[test_case( submit_call() => submit_call_expected(); "Submit data 0")]
 fn data_root_filter(extrinsic: Vec<u8>) -> H256 {
 	let opaque = OpaqueExtrinsic::decode(&mut extrinsic.as_slice()).unwrap();
-	extrinsics_root::<Runtime, _>([opaque].iter())
+	extrinsics_root::<Runtime, _>([opaque].iter(), LeafVersion::V1)
+}
+
+#[test]
+fn v2_data_root_includes_app_id_and_sender() {
+	let extrinsic = submit_call();
+	let opaque = OpaqueExtrinsic::decode(&mut extrinsic.as_slice()).unwrap();
+
+	let leaf = ProvenanceLeaf {
+		app_id: AppId(1),
+		sender: hex!("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d").into(),
+		data_hash: H256(sp_io::hashing::keccak_256(b"Test submit data")),
+	};
+	let expected = H256(sp_io::hashing::keccak_256(&leaf.to_bytes()));
+
+	assert_eq!(
+		extrinsics_root::<Runtime, _>([opaque].iter(), LeafVersion::V2),
+		expected
+	);
+}
+
+fn data_call() -> RuntimeCall {
+	RuntimeCall::DataAvailability(DaCall::submit_data {
+		data: b"Test submit data".to_vec().try_into().unwrap(),
+	})
+}
+
+fn remark_call() -> RuntimeCall {
+	RuntimeCall::System(frame_system::Call::remark { remark: vec![] })
+}
+
+fn batch(call: RuntimeCall) -> RuntimeCall {
+	RuntimeCall::Utility(pallet_utility::Call::batch { calls: vec![call] })
+}
+
+fn batch_all(call: RuntimeCall) -> RuntimeCall {
+	RuntimeCall::Utility(pallet_utility::Call::batch_all { calls: vec![call] })
+}
+
+fn force_batch(call: RuntimeCall) -> RuntimeCall {
+	RuntimeCall::Utility(pallet_utility::Call::force_batch { calls: vec![call] })
+}
+
+fn as_derivative(call: RuntimeCall) -> RuntimeCall {
+	RuntimeCall::Utility(pallet_utility::Call::as_derivative {
+		index: 0,
+		call: Box::new(call),
+	})
+}
+
+fn dispatch_as(call: RuntimeCall) -> RuntimeCall {
+	RuntimeCall::Utility(pallet_utility::Call::dispatch_as {
+		as_origin: Box::new(OriginCaller::system(frame_system::RawOrigin::Root)),
+		call: Box::new(call),
+	})
+}
+
+fn with_weight(call: RuntimeCall) -> RuntimeCall {
+	RuntimeCall::Utility(pallet_utility::Call::with_weight {
+		call: Box::new(call),
+		weight: Weight::zero(),
+	})
+}
+
+fn as_multi(call: RuntimeCall) -> RuntimeCall {
+	RuntimeCall::Multisig(pallet_multisig::Call::as_multi {
+		threshold: 2,
+		other_signatories: vec![AccountId32::new([1; 32])],
+		maybe_timepoint: None,
+		call: Box::new(call),
+		max_weight: Weight::zero(),
+	})
+}
+
+fn as_multi_threshold_1(call: RuntimeCall) -> RuntimeCall {
+	RuntimeCall::Multisig(pallet_multisig::Call::as_multi_threshold_1 {
+		other_signatories: vec![AccountId32::new([1; 32])],
+		call: Box::new(call),
+	})
+}
+
+fn sudo(call: RuntimeCall) -> RuntimeCall {
+	RuntimeCall::Sudo(pallet_sudo::Call::sudo {
+		call: Box::new(call),
+	})
+}
+
+fn sudo_unchecked_weight(call: RuntimeCall) -> RuntimeCall {
+	RuntimeCall::Sudo(pallet_sudo::Call::sudo_unchecked_weight {
+		call: Box::new(call),
+		weight: Weight::zero(),
+	})
+}
+
+fn sudo_as(call: RuntimeCall) -> RuntimeCall {
+	RuntimeCall::Sudo(pallet_sudo::Call::sudo_as {
+		who: MultiAddress::Id(AccountId32::new([1; 32])),
+		call: Box::new(call),
+	})
+}
+
+fn schedule(call: RuntimeCall) -> RuntimeCall {
+	RuntimeCall::Scheduler(pallet_scheduler::Call::schedule {
+		when: 10,
+		maybe_periodic: None,
+		priority: 0,
+		call: Box::new(call),
+	})
+}
+
+fn schedule_named(call: RuntimeCall) -> RuntimeCall {
+	RuntimeCall::Scheduler(pallet_scheduler::Call::schedule_named {
+		id: [0; 32],
+		when: 10,
+		maybe_periodic: None,
+		priority: 0,
+		call: Box::new(call),
+	})
+}
+
+fn schedule_after(call: RuntimeCall) -> RuntimeCall {
+	RuntimeCall::Scheduler(pallet_scheduler::Call::schedule_after {
+		after: 10,
+		maybe_periodic: None,
+		priority: 0,
+		call: Box::new(call),
+	})
+}
+
+fn schedule_named_after(call: RuntimeCall) -> RuntimeCall {
+	RuntimeCall::Scheduler(pallet_scheduler::Call::schedule_named_after {
+		id: [0; 32],
+		after: 10,
+		maybe_periodic: None,
+		priority: 0,
+		call: Box::new(call),
+	})
+}
+
+fn mandate(call: RuntimeCall) -> RuntimeCall {
+	RuntimeCall::Mandate(pallet_mandate::Call::mandate {
+		call: Box::new(call),
+	})
+}
+
+fn filter(call: RuntimeCall) -> Vec<SubmittedData> {
+	Runtime::filter(call, DataSource::default(), &mut Metrics::default())
+}
+
+fn check_app_id(app_id: u32, call: &RuntimeCall) -> TransactionValidity {
+	let storage = frame_system::GenesisConfig::<Runtime>::default()
+		.build_storage()
+		.unwrap();
+	sp_io::TestExternalities::new(storage).execute_with(|| {
+		da_control::NextAppId::<Runtime>::put(AppId(2));
+		CheckAppId::<Runtime>::from(AppId(app_id)).do_validate(call)
+	})
+}
+
+fn forbidden_app_id() -> TransactionValidity {
+	Err(TransactionValidityError::Invalid(
+		InvalidTransaction::Custom(InvalidTransactionCustomId::ForbiddenAppId as u8),
+	))
+}
+
+/// The data root and `CheckAppId` must agree on every wrapper: a wrapped `submit_data` is
+/// included in the data root and can use a non-zero `AppId`, while any other wrapped call is
+/// excluded and must use `AppId(0)`.
+#[test_case(batch; "Utility::batch")]
+#[test_case(batch_all; "Utility::batch_all")]
+#[test_case(force_batch; "Utility::force_batch")]
+#[test_case(as_derivative; "Utility::as_derivative")]
+#[test_case(dispatch_as; "Utility::dispatch_as")]
+#[test_case(with_weight; "Utility::with_weight")]
+#[test_case(as_multi; "Multisig::as_multi")]
+#[test_case(as_multi_threshold_1; "Multisig::as_multi_threshold_1")]
+#[test_case(sudo; "Sudo::sudo")]
+#[test_case(sudo_unchecked_weight; "Sudo::sudo_unchecked_weight")]
+#[test_case(sudo_as; "Sudo::sudo_as")]
+#[test_case(schedule; "Scheduler::schedule")]
+#[test_case(schedule_named; "Scheduler::schedule_named")]
+#[test_case(schedule_after; "Scheduler::schedule_after")]
+#[test_case(schedule_named_after; "Scheduler::schedule_named_after")]
+#[test_case(mandate; "Mandate::mandate")]
+fn wrapped_calls_are_handled_consistently(wrap: fn(RuntimeCall) -> RuntimeCall) {
+	assert_wrapper_is_handled_consistently(wrap);
+}
+
+fn assert_wrapper_is_handled_consistently(wrap: impl Fn(RuntimeCall) -> RuntimeCall) {
+	let wrapped_data = wrap(data_call());
+	assert_eq!(
+		filter(wrapped_data.clone()),
+		vec![SubmittedData::new(
+			DataSource::default(),
+			b"Test submit data".to_vec()
+		)]
+	);
+	assert_eq!(
+		check_app_id(1, &wrapped_data),
+		Ok(ValidTransaction::default())
+	);
+
+	let wrapped_remark = wrap(remark_call());
+	assert!(filter(wrapped_remark.clone()).is_empty());
+	assert_eq!(check_app_id(1, &wrapped_remark), forbidden_app_id());
+	assert_eq!(
+		check_app_id(0, &wrapped_remark),
+		Ok(ValidTransaction::default())
+	);
+}
+
+/// Nested wrappers are walked the same way by the data root and `CheckAppId`.
+#[test_case(sudo, batch; "Sudo::sudo(Utility::batch)")]
+#[test_case(sudo_as, force_batch; "Sudo::sudo_as(Utility::force_batch)")]
+#[test_case(as_multi, batch_all; "Multisig::as_multi(Utility::batch_all)")]
+#[test_case(as_multi_threshold_1, sudo; "Multisig::as_multi_threshold_1(Sudo::sudo)")]
+#[test_case(batch, as_derivative; "Utility::batch(Utility::as_derivative)")]
+#[test_case(dispatch_as, with_weight; "Utility::dispatch_as(Utility::with_weight)")]
+#[test_case(schedule, batch; "Scheduler::schedule(Utility::batch)")]
+#[test_case(mandate, schedule_named_after; "Mandate::mandate(Scheduler::schedule_named_after)")]
+fn nested_wrapped_calls_are_handled_consistently(
+	outer: fn(RuntimeCall) -> RuntimeCall,
+	inner: fn(RuntimeCall) -> RuntimeCall,
+) {
+	assert_wrapper_is_handled_consistently(|call| outer(inner(call)));
+}
+
+/// Calls nested deeper than `MAX_WRAPPER_DEPTH` are not part of the data root, and they are only
+/// valid transactions with `AppId(0)`.
+#[test]
+fn too_deeply_nested_calls_are_rejected() {
+	let nested = |call: RuntimeCall| (0..MAX_WRAPPER_DEPTH).fold(call, |call, _| batch(call));
+	let max_recursion_exceeded = Err(TransactionValidityError::Invalid(
+		InvalidTransaction::Custom(InvalidTransactionCustomId::MaxRecursionExceeded as u8),
+	));
+
+	let deepest = nested(data_call());
+	assert_eq!(filter(deepest.clone()).len(), 1);
+	assert_eq!(check_app_id(1, &deepest), Ok(ValidTransaction::default()));
+
+	let too_deep = sudo(nested(data_call()));
+	assert!(filter(too_deep.clone()).is_empty());
+	assert_eq!(check_app_id(1, &too_deep), max_recursion_exceeded);
+	assert_eq!(check_app_id(0, &too_deep), Ok(ValidTransaction::default()));
+
+	let too_deep_remark = sudo(nested(remark_call()));
+	assert_eq!(check_app_id(1, &too_deep_remark), max_recursion_exceeded);
+	assert_eq!(
+		check_app_id(0, &too_deep_remark),
+		Ok(ValidTransaction::default())
+	);
 }
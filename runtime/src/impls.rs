@@ -2,12 +2,13 @@ use crate::voter_bags;
 use crate::SessionKeys;
 use crate::SLOT_DURATION;
 use crate::{
-	constants, prod_or_fast, weights, AccountId, AccountIndex, Babe, Balances, Block, BlockNumber,
-	Bounties, ElectionProviderMultiPhase, GrandpaId, Hash, Historical, ImOnline, ImOnlineId, Index,
-	Indices, Moment, NominationPools, Offences, OriginCaller, PalletInfo, Preimage,
-	ReserveIdentifier, Runtime, RuntimeCall, RuntimeEvent, RuntimeHoldReason, RuntimeOrigin,
-	RuntimeVersion, Session, Signature, SignedPayload, Staking, System, TechnicalCommittee,
-	Timestamp, TransactionPayment, Treasury, UncheckedExtrinsic, VoterList, MINUTES, VERSION,
+	constants, mmr, prod_or_fast, weights, AccountId, AccountIndex, Babe, Balances, Block,
+	BlockNumber, Bounties, ElectionProviderMultiPhase, GrandpaId, Hash, Historical, ImOnline,
+	ImOnlineId, Index, Indices, Mmr, Moment, NomadDABridge, NominationPools, Offences,
+	OriginCaller, PalletInfo, Preimage, ReserveIdentifier, Runtime, RuntimeCall, RuntimeEvent,
+	RuntimeHoldReason, RuntimeOrigin, RuntimeVersion, Session, Signature, SignedPayload, Staking,
+	System, TechnicalCommittee, Timestamp, TransactionPayment, Treasury, UncheckedExtrinsic,
+	VoterList, MINUTES, VERSION,
 };
 use avail_core::currency::{Balance, AVL, CENTS, NANO_AVL, PICO_AVL};
 use avail_core::AppId;
@@ -41,6 +42,7 @@ use pallet_transaction_payment::CurrencyAdapter;
 use pallet_transaction_payment::Multiplier;
 use pallet_transaction_payment::TargetedFeeAdjustment;
 use sp_core::crypto::KeyTypeId;
+use sp_core::H256;
 use sp_runtime::generic::Era;
 use sp_runtime::traits;
 use sp_runtime::traits::BlakeTwo256;
@@ -48,10 +50,10 @@ use sp_runtime::traits::Bounded;
 use sp_runtime::traits::OpaqueKeys;
 use sp_runtime::FixedPointNumber;
 use sp_runtime::FixedU128;
+use sp_runtime::MultiAddress;
 use sp_runtime::Perbill;
 use sp_runtime::Perquintill;
 use sp_runtime::{Percent, Permill};
-use sp_std::rc::Rc;
 use sp_std::vec;
 use sp_std::vec::Vec;
 
@@ -120,21 +122,79 @@ impl da_control::Config for Runtime {
 }
 
 impl nomad_updater_manager::Config for Runtime {
+	type Currency = Balances;
 	type RuntimeEvent = RuntimeEvent;
+	type SlashRewardShare = constants::nomad::SlashRewardShare;
+	type Slashed = Treasury;
+	type UnbondingDelay = constants::nomad::UnbondingDelay;
+	type UpdaterBond = constants::nomad::UpdaterBond;
+}
+
+parameter_types! {
+	pub const NomadHomePalletId: PalletId = PalletId(*b"nmd/home");
+	/// Funds the existential deposit of the relayer reward pot on upgrade.
+	pub NomadRelayerPotSource: AccountId = Treasury::account_id();
 }
 
 impl nomad_home::Config for Runtime {
 	type MaxMessageBodyBytes = constants::nomad::MaxMessageBodyBytes;
+	type OnPrunedMessages = NomadDABridge;
+	type PalletId = NomadHomePalletId;
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = weights::nomad_home::WeightInfo<Runtime>;
 }
 
+/// Proves the block hashes with the MMR of `ParentNumberAndHash` leaves.
+pub struct MmrBlockHashes;
+
+impl nomad_da_bridge::BlockHashMmr<BlockNumber, Hash> for MmrBlockHashes {
+	type Proof = mmr::Proof<mmr::Hash>;
+
+	fn leaf_count_and_root() -> (u64, Hash) {
+		(Mmr::mmr_leaves(), Mmr::mmr_root())
+	}
+
+	fn proof_leaf_count(proof: &Self::Proof) -> u64 {
+		proof.leaf_count
+	}
+
+	fn verify_proof(root: Hash, number: BlockNumber, hash: Hash, proof: Self::Proof) -> bool {
+		let leaf = mmr::DataOrHash::<mmr::Hashing, mmr::Leaf>::Data((number, hash));
+		pallet_mmr::verify_leaves_proof::<mmr::Hashing, _>(root, vec![leaf], proof).is_ok()
+	}
+}
+
 impl nomad_da_bridge::Config for Runtime {
+	type AutoDispatchPeriod = constants::nomad::AutoDispatchPeriod;
+	type BlockHashMmr = MmrBlockHashes;
 	type DABridgePalletId = constants::nomad::DABridgePalletId;
+	type MaxAutoDispatchDestinations = constants::nomad::MaxAutoDispatchDestinations;
+	type MaxDataRootDispatchRecords = constants::nomad::MaxDataRootDispatchRecords;
+	type MaxDataRootRange = constants::nomad::MaxDataRootRange;
+	type MaxDispatchedRanges = constants::nomad::MaxDispatchedRanges;
+	type MmrRootHistory = constants::nomad::MmrRootHistory;
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = weights::nomad_da_bridge::WeightInfo<Runtime>;
 }
 
+impl nomad_replica::Config for Runtime {
+	type LocalDomain = nomad_home::LocalDomain<Runtime>;
+	type MaxHandlerWeight = constants::nomad::MaxHandlerWeight;
+	type MaxMessageBytes = constants::nomad::MaxMessageBytes;
+	type MessageHandler = NomadGovernanceRouter;
+	type OptimisticPeriod = constants::nomad::OptimisticPeriod;
+	type RuntimeEvent = RuntimeEvent;
+}
+
+impl nomad_governance_router::Config for Runtime {
+	type GovernanceOrigin = EitherOfDiverse<
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionMoreThan<AccountId, TechnicalCollective, 1, 2>,
+	>;
+	type RouterAddress = constants::nomad::GovernanceRouterAddress;
+	type RuntimeEvent = RuntimeEvent;
+}
+
 impl pallet_offences::Config for Runtime {
 	type IdentificationTuple = pallet_session::historical::IdentificationTuple<Self>;
 	type OnOffenceHandler = Staking;
@@ -693,35 +753,72 @@ parameter_types! {
 		BlockLength::max_with_normal_ratio(5 * 1024 * 1024, NORMAL_DISPATCH_RATIO);
 }
 
-/// Filters and extracts `data` from `call` if it is a `DataAvailability::submit_data` type.
-impl submitted_data::Filter<RuntimeCall> for Runtime {
-	fn filter(call: RuntimeCall, metrics: submitted_data::RcMetrics) -> Vec<Vec<u8>> {
-		metrics.borrow_mut().total_extrinsics += 1;
-
-		match call {
-			RuntimeCall::DataAvailability(da_control::Call::submit_data { data })
-				if !data.is_empty() =>
-			{
-				let mut metrics = metrics.borrow_mut();
-				metrics.data_submit_leaves += 1;
-				metrics.data_submit_extrinsics += 1;
-				vec![data.into_inner()]
-			},
-			RuntimeCall::Utility(pallet_utility::Call::batch { calls })
-			| RuntimeCall::Utility(pallet_utility::Call::batch_all { calls })
-			| RuntimeCall::Utility(pallet_utility::Call::force_batch { calls }) => {
-				Self::process_calls(calls, &metrics)
-			},
-			_ => vec![],
+/// Marks `DataAvailability::submit_data` as the data call, and the calls which dispatch other
+/// calls as wrappers. Data wrapped by any of them is part of the data root and its `AppId` is
+/// checked by `CheckAppId`.
+impl submitted_data::DataCall for RuntimeCall {
+	fn data_call_kind(&self) -> submitted_data::DataCallKind<'_, Self> {
+		use submitted_data::DataCallKind::{Data, Other, Wrapper};
+
+		match self {
+			RuntimeCall::DataAvailability(call) => call.submitted_data().map_or(Other, Data),
+			RuntimeCall::Utility(
+				pallet_utility::Call::batch { calls }
+				| pallet_utility::Call::batch_all { calls }
+				| pallet_utility::Call::force_batch { calls },
+			) => Wrapper(calls.iter().collect()),
+			RuntimeCall::Utility(
+				pallet_utility::Call::as_derivative { call, .. }
+				| pallet_utility::Call::dispatch_as { call, .. }
+				| pallet_utility::Call::with_weight { call, .. },
+			)
+			| RuntimeCall::Multisig(
+				pallet_multisig::Call::as_multi { call, .. }
+				| pallet_multisig::Call::as_multi_threshold_1 { call, .. },
+			)
+			| RuntimeCall::Sudo(
+				pallet_sudo::Call::sudo { call }
+				| pallet_sudo::Call::sudo_unchecked_weight { call, .. }
+				| pallet_sudo::Call::sudo_as { call, .. },
+			)
+			| RuntimeCall::Scheduler(
+				pallet_scheduler::Call::schedule { call, .. }
+				| pallet_scheduler::Call::schedule_named { call, .. }
+				| pallet_scheduler::Call::schedule_after { call, .. }
+				| pallet_scheduler::Call::schedule_named_after { call, .. },
+			)
+			| RuntimeCall::Mandate(pallet_mandate::Call::mandate { call }) => Wrapper(vec![call.as_ref()]),
+			_ => Other,
 		}
 	}
+}
+
+/// Filters and extracts `data` from `call` and its wrapped calls, using its `DataCall`
+/// implementation.
+impl submitted_data::Filter<RuntimeCall> for Runtime {
+	fn filter(
+		call: RuntimeCall,
+		source: submitted_data::DataSource,
+		metrics: &mut submitted_data::Metrics,
+	) -> Vec<submitted_data::SubmittedData> {
+		submitted_data::data_of(&call, source, metrics)
+	}
+}
 
-	/// This function processes a list of calls and returns their data as Vec<Vec<u8>>
-	fn process_calls(calls: Vec<RuntimeCall>, metrics: &submitted_data::RcMetrics) -> Vec<Vec<u8>> {
-		calls
-			.into_iter()
-			.flat_map(|call| Self::filter(call, Rc::clone(metrics)))
-			.collect()
+/// Returns the application and the signer of `extrinsic`.
+///
+/// Only `MultiAddress::Id` signers are recorded, because other addresses would need the state
+/// to be resolved. Any other signer is reported as `H256::zero()`.
+pub fn submitted_data_source(extrinsic: &UncheckedExtrinsic) -> submitted_data::DataSource {
+	use avail_core::traits::GetAppId;
+
+	let sender = match extrinsic.signature.as_ref() {
+		Some((MultiAddress::Id(id), _, _)) => H256(id.clone().into()),
+		_ => H256::zero(),
+	};
+	submitted_data::DataSource {
+		app_id: extrinsic.app_id(),
+		sender,
 	}
 }
 
@@ -731,11 +828,15 @@ impl submitted_data::Extractor for Runtime {
 
 	fn extract(
 		opaque: &OpaqueExtrinsic,
-		metrics: submitted_data::RcMetrics,
-	) -> Result<Vec<Vec<u8>>, Self::Error> {
+		metrics: &mut submitted_data::Metrics,
+	) -> Result<Vec<submitted_data::SubmittedData>, Self::Error> {
 		let extrinsic = UncheckedExtrinsic::try_from(opaque)?;
-		let data =
-			<Runtime as submitted_data::Filter<RuntimeCall>>::filter(extrinsic.function, metrics);
+		let source = submitted_data_source(&extrinsic);
+		let data = <Runtime as submitted_data::Filter<RuntimeCall>>::filter(
+			extrinsic.function,
+			source,
+			metrics,
+		);
 
 		Ok(data)
 	}
@@ -757,6 +858,12 @@ impl frame_system::Config for Runtime {
 	type BlockLength = RuntimeBlockLength;
 	/// Block & extrinsics weights: base values and limits.
 	type BlockWeights = constants::system::RuntimeBlockWeights;
+	/// The commitment scheme of the header extension.
+	type CommitmentScheme = frame_system::header_builder::commitment::Kate;
+	/// Format of the data root leaves.
+	type DataRootLeafVersion = frame_support::traits::GetDefault;
+	/// Structure of the data root tree.
+	type DataRootMode = frame_support::traits::GetDefault;
 	/// The weight of database operations that the runtime can invoke.
 	type DbWeight = RocksDbWeight;
 	/// The type for hashing blocks and tries.
@@ -776,6 +883,8 @@ impl frame_system::Config for Runtime {
 	type OnNewAccount = ();
 	// What to do if runtime code change: Default behaviour.
 	type OnSetCode = ();
+	/// Padding cells of the data grid are filled using the zero seed.
+	type PaddingSeed = frame_support::traits::GetDefault;
 	/// Converts a module to the index of the module in `construct_runtime!`.
 	///
 	/// This type is being generated by `construct_runtime!`.
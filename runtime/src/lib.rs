@@ -148,6 +148,8 @@ construct_runtime!(
 		NominationPools: pallet_nomination_pools = 36,
 		Identity: pallet_identity = 37,
 		Mandate: pallet_mandate = 38,
+		NomadReplica: nomad_replica = 39,
+		NomadGovernanceRouter: nomad_governance_router = 40,
 	}
 );
 
@@ -188,8 +190,11 @@ mod benches {
 
 		[frame_system, SystemBench::<Runtime>]
 		[da_control, $crate::DataAvailability]
+		[nomad_updater_manager, $crate::NomadUpdaterManager]
 		[nomad_home, $crate::NomadHome]
 		[nomad_da_bridge, $crate::NomadDABridge]
+		[nomad_replica, $crate::NomadReplica]
+		[nomad_governance_router, $crate::NomadGovernanceRouter]
 		[pallet_identity, $crate::Identity]
 		[pallet_mandate, $crate::Mandate]
 	);
@@ -259,6 +264,8 @@ mod tests {
 		<nomad_updater_manager::Pallet<Runtime> as TryState<BlockNumber>>::try_state(block, All)?;
 		<nomad_home::Pallet<Runtime> as TryState<BlockNumber>>::try_state(block, All)?;
 		<nomad_da_bridge::Pallet<Runtime> as TryState<BlockNumber>>::try_state(block, All)?;
+		<nomad_replica::Pallet<Runtime> as TryState<BlockNumber>>::try_state(block, All)?;
+		<nomad_governance_router::Pallet<Runtime> as TryState<BlockNumber>>::try_state(block, All)?;
 		<pallet_preimage::Pallet<Runtime> as TryState<BlockNumber>>::try_state(block, All)?;
 		<pallet_multisig::Pallet<Runtime> as TryState<BlockNumber>>::try_state(block, All)?;
 		<pallet_bags_list::Pallet<Runtime, pallet_bags_list::Instance1> as TryState<
@@ -334,13 +341,18 @@ mod tests {
 		size_of::<nomad_updater_manager::Call<Runtime>>();
 	const NOMAD_HOME_CALL_SIZE: usize = size_of::<nomad_home::Call<Runtime>>();
 	const NOMAD_BRIDGE_CALL_SIZE: usize = size_of::<nomad_da_bridge::Call<Runtime>>();
+	const NOMAD_REPLICA_CALL_SIZE: usize = size_of::<nomad_replica::Call<Runtime>>();
+	const NOMAD_GOVERNANCE_ROUTER_CALL_SIZE: usize =
+		size_of::<nomad_governance_router::Call<Runtime>>();
 
 	#[test_case( RUNTIME_CALL_SIZE => 160)]
 	#[test_case( DA_CALL_SIZE => 32)]
 	#[test_case( SYSTEM_CALL_SIZE => 32)]
-	#[test_case( NOMAD_UPDATER_MANAGER_CALL_SIZE => 0)]
+	#[test_case( NOMAD_UPDATER_MANAGER_CALL_SIZE => 21)]
 	#[test_case( NOMAD_HOME_CALL_SIZE => 152)]
-	#[test_case( NOMAD_BRIDGE_CALL_SIZE => 48)]
+	#[test_case( NOMAD_BRIDGE_CALL_SIZE => 64)]
+	#[test_case( NOMAD_REPLICA_CALL_SIZE => 152)]
+	#[test_case( NOMAD_GOVERNANCE_ROUTER_CALL_SIZE => 40)]
 	fn call_size(size: usize) -> usize {
 		const MAX_CALL_SIZE: usize = 208;
 		assert!(
@@ -6,7 +6,7 @@ use sp_runtime::{
 };
 use sp_std::vec::Vec;
 
-use crate::{migration, AllPalletsWithSystem, Runtime, RuntimeCall};
+use crate::{impls, migration, AllPalletsWithSystem, Runtime, RuntimeCall};
 
 /// An index to a block.
 pub type BlockNumber = u32;
@@ -77,7 +77,10 @@ pub type Executive = frame_executive::Executive<
 
 /// All migrations executed on runtime upgrade as a nested tuple of types implementing
 /// `OnRuntimeUpgrade`.
-type Migrations = (migration::Migration,);
+type Migrations = (
+	migration::Migration,
+	nomad_home::migration::FundRelayerPot<Runtime, impls::NomadRelayerPotSource>,
+);
 
 /// ID type for named reserves.
 pub type ReserveIdentifier = [u8; 8];